@@ -74,10 +74,57 @@ export type {
   ProjectionMortalityRow,
   ProjectionFertilityRow,
   ProjectionMigrationRow,
+  ProjectionEmigrationRow,
+  ProjectionDimension,
+  ProjectionState,
+  ProjectionTransition,
+  ProjectionInterregionalMigration,
+  ProjectionRegion,
+  InterregionalMigrationTable,
+  InterregionalFlow,
+  ProjectionHeadshipRates,
+  ProjectionParticipationRates,
+  ParticipationTarget,
+  DemandProfile,
+  FiscalFlow,
+  FiscalProfile,
+  FiscalSettings,
+  ProjectionSurvivalRow,
+  ProjectionBirthSurvival,
+  YearRows,
+  RateInterpolation,
+  MigrationTiming,
+  FertilityExposure,
+  FertilityScheduleModel,
+  FertilityTarget,
   ProjectionRunRequest,
   ProjectionYearResult,
+  Indicators,
+  AgeSexCount,
+  ComponentDetail,
+  RegionAudit,
+  RegionYearResult,
+  LifeTableResult,
   CohortSnapshot,
   YearPopulationSnapshot,
+  BackcastYearResult,
+  StateYearResult,
+  HouseholdTypeYear,
+  HouseholdYear,
+  LaborForceYear,
+  RegionDemand,
+  ProfileDemand,
+  ServiceDemandYear,
+  FiscalItem,
+  FiscalYear,
+  StablePopulationResult,
+  ErrorProcess,
+  ErrorModel,
+  StochasticSettings,
+  Quantiles,
+  StochasticYear,
+  LeeCarterParameters,
+  LeeCarterFit,
   InputDataStats,
   ProjectionRunResponse,
   ProjectionProgressPayload,
@@ -1,9 +1,11 @@
 import type { 
+  Gender,
   Scenario, 
   ProjectionResult, 
   ProjectionProgress,
   NewScenario 
 } from './demographic';
+import type { Shock } from './shock';
 
 // ============================================================
 // MESSAGE ENVELOPE
@@ -151,6 +153,174 @@ export interface ProjectionMigrationRow {
   readonly female: number;
 }
 
+/** Emigration rate by age: share of the cohort leaving during the year */
+export interface ProjectionEmigrationRow {
+  readonly age: number;
+  readonly male: number;   // 0-1
+  readonly female: number;
+}
+
+/** Extra population dimension, e.g. education or citizenship */
+export interface ProjectionDimension {
+  readonly name: string;
+  readonly states: ProjectionState[];
+  readonly transitions?: ProjectionTransition[];
+  readonly birthState?: string;  // state of all newborns (default: mother's state)
+}
+
+/** Base population and rate differentials of one state */
+export interface ProjectionState {
+  readonly id: string;
+  readonly population: ProjectionPopulationRow[];
+  readonly relativeFertility?: number;       // multiplier, default 1
+  readonly relativeMortalityMale?: number;   // multiplier, default 1
+  readonly relativeMortalityFemale?: number;
+  readonly migrationShare?: number;          // 0-1, shares sum to 1; default: remainder by base population
+}
+
+/** Transition rates from one state to another */
+export interface ProjectionTransition {
+  readonly from: string;
+  readonly to: string;
+  readonly year?: number;  // anchor year (default: base year)
+  readonly rates: { readonly age: number; readonly male: number; readonly female: number }[];  // 0-1
+}
+
+/** Regions projected together with migration between them */
+export interface ProjectionInterregionalMigration {
+  readonly regions: ProjectionRegion[];  // replace population; own or shared rate tables
+  readonly tables: InterregionalMigrationTable[];  // anchored by year
+}
+
+/** Base population and rate tables of one region; missing tables use the request's */
+export interface ProjectionRegion {
+  readonly id: string;
+  readonly population: ProjectionPopulationRow[];
+  readonly mortality?: ProjectionMortalityRow[];  // held for the whole projection
+  readonly fertility?: ProjectionFertilityRow[];
+  readonly migration?: ProjectionMigrationRow[];  // own net migrants instead of a share
+  readonly migrationShare?: number;  // 0-1 of the request's migration; default: remainder by base population
+}
+
+/** Origin → destination move rates between regions for one year */
+export interface InterregionalMigrationTable {
+  readonly year: number;
+  readonly flows: InterregionalFlow[];
+}
+
+/** Share of an origin cohort moving to the destination during the year */
+export interface InterregionalFlow {
+  readonly originRegionId: string;
+  readonly destinationRegionId: string;
+  readonly age: number;
+  readonly male: number;  // 0-1
+  readonly female: number;
+}
+
+/** Headship rates of one household type; a row applies up to the next row's age */
+export interface ProjectionHeadshipRates {
+  readonly householdType: string;
+  readonly year?: number;  // anchor year (default: base year)
+  readonly rows: { readonly age: number; readonly male: number; readonly female: number }[];  // 0-1
+}
+
+/** Labor force participation rates; a row applies up to the next row's age */
+export interface ProjectionParticipationRates {
+  readonly year?: number;  // anchor year (default: base year)
+  readonly rows: { readonly age: number; readonly male: number; readonly female: number }[];  // 0-1
+}
+
+/** Participation target for an age range, reached linearly from the base year */
+export interface ParticipationTarget {
+  readonly year: number;
+  readonly minAge: number;
+  readonly maxAge: number;
+  readonly male?: number;    // 0-1, omitted keeps the rates
+  readonly female?: number;
+}
+
+/** Named age profile for service demand; a value applies up to the next value's age */
+export interface DemandProfile {
+  readonly name: string;   // e.g. 'pupils', 'bedDays'
+  readonly unit?: string;
+  readonly values: { readonly age: number; readonly male: number; readonly female: number }[];  // per person, >= 0
+}
+
+/** Direction of a fiscal flow */
+export type FiscalFlow = 'revenue' | 'expenditure';
+
+/** Per-capita base-year age profile of a revenue or expenditure item */
+export interface FiscalProfile {
+  readonly name: string;   // e.g. 'pensions', 'health'
+  readonly flow: FiscalFlow;
+  readonly growth?: number;  // yearly growth of the values (default: productivityGrowth)
+  readonly values: { readonly age: number; readonly male: number; readonly female: number }[];
+}
+
+/** Profiles and growth for a fiscal projection */
+export interface FiscalSettings {
+  readonly profiles: FiscalProfile[];
+  readonly productivityGrowth?: number;  // e.g. 0.015, default 0
+}
+
+/** Five-year survival ratio by age group (5Lx+5 / 5Lx) */
+export interface ProjectionSurvivalRow {
+  readonly age: number;
+  readonly male: number;
+  readonly female: number;
+}
+
+/** Survival of births to the 0-4 group (5L0 / 5·l0) */
+export interface ProjectionBirthSurvival {
+  readonly male: number;
+  readonly female: number;
+}
+
+/** Rate rows anchored to a year (time-varying assumptions) */
+export interface YearRows<T> {
+  readonly year: number;
+  readonly rows: T[];
+}
+
+/** How rates are derived between anchor years */
+export type RateInterpolation = 'linear' | 'constant';
+
+/** When net migrants arrive within each projected year */
+export type MigrationTiming = 'start' | 'midPeriod' | 'end';
+
+/** Female population used as exposure for fertility rates */
+export type FertilityExposure = 'start' | 'average';
+
+/** How yearly stochastic errors evolve */
+export type ErrorProcess =
+  | { readonly type: 'randomWalk' }
+  | { readonly type: 'autoregressive'; readonly phi: number };
+
+/** Error model for one stochastic component */
+export interface ErrorModel {
+  readonly process?: ErrorProcess;  // default random walk
+  readonly sd?: number;             // yearly innovation, default 0
+}
+
+/** Monte Carlo settings */
+export interface StochasticSettings {
+  readonly simulations: number;  // 1-10000
+  readonly seed?: number;
+  readonly tfr?: ErrorModel;        // children per woman
+  readonly e0?: ErrorModel;         // years
+  readonly migration?: ErrorModel;  // log multiplier of net migration
+}
+
+/** Shape of generated fertility schedules */
+export type FertilityScheduleModel = 'shift' | 'gamma';
+
+/** Fertility assumption for one year; missing values are interpolated */
+export interface FertilityTarget {
+  readonly year: number;
+  readonly tfr?: number;      // children per woman
+  readonly meanAge?: number;  // mean age at childbearing
+}
+
 /** Run projection request payload */
 export interface ProjectionRunRequest {
   readonly workspaceId: string;
@@ -161,6 +331,41 @@ export interface ProjectionRunRequest {
   readonly mortality: ProjectionMortalityRow[];
   readonly fertility: ProjectionFertilityRow[];
   readonly migration?: ProjectionMigrationRow[];  // optional
+  /** Anchors for later years; base-year tables are mortality/fertility/migration */
+  readonly mortalitySeries?: YearRows<ProjectionMortalityRow>[];
+  readonly fertilitySeries?: YearRows<ProjectionFertilityRow>[];
+  readonly migrationSeries?: YearRows<ProjectionMigrationRow>[];
+  /** Emigration rates on top of migration counts (which then hold immigration) */
+  readonly emigrationRates?: ProjectionEmigrationRow[];
+  readonly emigrationSeries?: YearRows<ProjectionEmigrationRow>[];
+  /** TFR and mean-age targets; generate fertility after the base year (excludes fertilitySeries) */
+  readonly fertilityTargets?: FertilityTarget[];
+  readonly fertilityModel?: FertilityScheduleModel;  // default 'shift'
+  /** Past mortality; forecasts mortality after the base year with Lee-Carter (excludes mortalitySeries) */
+  readonly mortalityHistory?: YearRows<ProjectionMortalityRow>[];
+  readonly interpolation?: RateInterpolation;  // default 'linear'
+  readonly migrationTiming?: MigrationTiming;  // default 'start'
+  readonly infantSeparationFactor?: number;  // share of infant deaths in birth year, default 0.85
+  readonly fertilityExposure?: FertilityExposure;  // default 'start'
+  readonly openAgeGroup?: number;  // first age of the open-ended group, default 120
+  readonly ageInterval?: 1 | 5;  // 5 = abridged groups and steps, default 1; endYear - baseYear must then be a multiple of 5
+  readonly survivalRatios?: ProjectionSurvivalRow[];  // required when ageInterval is 5
+  readonly birthSurvival?: ProjectionBirthSurvival;  // required when ageInterval is 5
+  readonly includeStablePopulation?: boolean;  // Leslie matrices and stable population of base-year rates
+  readonly includeLifeTables?: boolean;  // full period life tables by year and sex
+  readonly includeComponents?: boolean;  // deaths, births and migration by age (single-year mode)
+  readonly audit?: boolean;  // balancing-equation check per year (single-year mode)
+  readonly backcastToYear?: number;  // reverse survival back to this year (single-year mode)
+  readonly dimension?: ProjectionDimension;  // population is then given by state
+  readonly interregionalMigration?: ProjectionInterregionalMigration;  // population is then given by region
+  readonly headshipRates?: ProjectionHeadshipRates[];  // household projections
+  readonly participationRates?: ProjectionParticipationRates[];  // labor force projections
+  readonly participationTargets?: ParticipationTarget[];
+  readonly demandProfiles?: DemandProfile[];  // service demand projections
+  readonly fiscal?: FiscalSettings;  // revenue and expenditure projections
+  /** Scenario shocks to mortality, fertility and migration (single-year mode) */
+  readonly shocks?: Shock[];
+  readonly stochastic?: StochasticSettings;  // single-year mode only
 }
 
 /** Single year result */
@@ -170,8 +375,92 @@ export interface ProjectionYearResult {
   readonly births: number;
   readonly deaths: number;
   readonly netMigration: number;
+  readonly unmetEmigration: number;  // net emigration exceeding the population
   readonly naturalChange: number;
   readonly growthRate: number;
+  /** Period life expectancy from the year's mortality (single-year mode, mortality shared by all regions) */
+  readonly e0Male?: number;
+  readonly e0Female?: number;
+  readonly e65Male?: number;
+  readonly e65Female?: number;
+  readonly indicators: Indicators;
+  /** Components by age (when requested) */
+  readonly components?: ComponentDetail;
+  /** Balancing-equation audit over all regions (when requested) */
+  readonly audit?: RegionAudit;
+  /** Results by region (multiregional projections) */
+  readonly regions?: RegionYearResult[];
+}
+
+/** One region of a multiregional projection in a projected year */
+export interface RegionYearResult {
+  readonly regionId: string;
+  readonly totalPopulation: number;
+  readonly births: number;
+  readonly deaths: number;
+  readonly netMigration: number;  // international
+  readonly internalIn: number;    // moves from the other regions
+  readonly internalOut: number;
+  readonly e0Male?: number;
+  readonly e0Female?: number;
+  readonly indicators: Indicators;
+  readonly audit?: RegionAudit;
+  readonly population: YearPopulationSnapshot;  // end of year
+}
+
+/** Check of P(t+1) = P(t) + B - D + M for one projected year */
+export interface RegionAudit {
+  readonly internalMigration: number;     // net interregional moves in
+  readonly discrepancy: number;           // P(t+1) - (P(t) + B - D + M)
+  readonly maxCohortDiscrepancy: number;  // cohort balance not carried to the next age
+  readonly openAgeLoss: number;           // balance missing from the open age group
+  readonly clampedEmigration: number;     // net emigration exceeding the cohorts
+  readonly clampedRates: number;          // mortality/emigration/fertility rates clamped
+  readonly clampedPopulation: number;     // added by zeroing negative cohorts
+}
+
+/** Persons by single year of age and sex */
+export interface AgeSexCount {
+  readonly age: number;
+  readonly male: number;
+  readonly female: number;
+}
+
+/** Components of change by age; deaths and migration by age at start of year */
+export interface ComponentDetail {
+  readonly deaths: AgeSexCount[];
+  readonly newbornDeathsMale: number;    // deaths of the year's births before year end
+  readonly newbornDeathsFemale: number;
+  readonly birthsByMotherAge: { readonly age: number; readonly births: number }[];
+  readonly migration: AgeSexCount[];     // applied net migration
+}
+
+/** Standard demographic indicators for one projected year */
+export interface Indicators {
+  readonly tfr: number;
+  readonly crudeBirthRate: number;         // per 1,000 mid-year population
+  readonly crudeDeathRate: number;         // per 1,000 mid-year population
+  readonly oldAgeDependencyRatio: number;  // 65+ per 100 aged 15-64
+  readonly totalDependencyRatio: number;   // 0-14 and 65+ per 100 aged 15-64
+  readonly potentialSupportRatio: number;  // 15-64 per person 65+
+  readonly meanAge: number;
+  readonly shareUnder15: number;           // percent of end-of-year population
+  readonly share15To64: number;
+  readonly share65Plus: number;
+  readonly share80Plus: number;
+}
+
+/** Period life table for one sex and year */
+export interface LifeTableResult {
+  readonly year: number;
+  readonly gender: Gender;
+  readonly ages: number[];
+  readonly qx: number[];
+  readonly lx: number[];
+  readonly dx: number[];
+  readonly personYears: number[];  // L_x
+  readonly tx: number[];
+  readonly ex: number[];
 }
 
 /** Population snapshot by age and sex for a single year */
@@ -181,6 +470,96 @@ export interface CohortSnapshot {
   readonly female: number;
 }
 
+/** Population estimated back to the start of one year by reverse survival */
+export interface BackcastYearResult {
+  readonly year: number;
+  readonly totalPopulation: number;
+  readonly births: number;              // implied by the estimated women
+  readonly deaths: number;
+  readonly netMigration: number;
+  readonly newbornDiscrepancy: number;  // observed age 0 minus implied newborns
+  readonly clampedPopulation: number;   // added where reversed migration went negative
+  readonly population: YearPopulationSnapshot;
+}
+
+/** One state of the extra dimension in a projected year */
+export interface StateYearResult {
+  readonly year: number;
+  readonly state: string;
+  readonly totalPopulation: number;
+  readonly births: number;
+  readonly deaths: number;
+  readonly netMigration: number;
+  readonly transfersIn: number;   // including newborns assigned to the birth state
+  readonly transfersOut: number;
+  readonly population: YearPopulationSnapshot;  // end of year
+}
+
+/** Households of one type in a year */
+export interface HouseholdTypeYear {
+  readonly householdType: string;
+  readonly households: number;
+  readonly byAgeOfHead: AgeSexCount[];
+}
+
+/** Projected households in a year */
+export interface HouseholdYear {
+  readonly year: number;
+  readonly totalHouseholds: number;
+  readonly averageSize: number;  // persons per household
+  readonly types: HouseholdTypeYear[];
+}
+
+/** Projected labor force in a year */
+export interface LaborForceYear {
+  readonly year: number;
+  readonly laborForce: number;
+  readonly maleLaborForce: number;
+  readonly femaleLaborForce: number;
+  readonly activeShare: number;               // labor force per 100 persons aged 15+
+  readonly economicDependencyRatio: number;   // persons outside the labor force per 100 in it
+  readonly byAge: AgeSexCount[];
+}
+
+/** Demand of one profile in one region */
+export interface RegionDemand {
+  readonly regionId: string;
+  readonly demand: number;
+  readonly male: number;
+  readonly female: number;
+}
+
+/** Demand of one profile in a year */
+export interface ProfileDemand {
+  readonly name: string;
+  readonly unit?: string;
+  readonly total: number;
+  readonly regions: RegionDemand[];
+}
+
+/** Projected service demand in a year */
+export interface ServiceDemandYear {
+  readonly year: number;
+  readonly profiles: ProfileDemand[];  // in request order
+}
+
+/** Amount of one fiscal item in a year */
+export interface FiscalItem {
+  readonly name: string;
+  readonly flow: FiscalFlow;
+  readonly amount: number;
+}
+
+/** Projected public finances in a year */
+export interface FiscalYear {
+  readonly year: number;
+  readonly revenue: number;
+  readonly expenditure: number;
+  readonly balance: number;        // revenue - expenditure
+  readonly coverageRatio: number;  // revenue / expenditure
+  readonly items: FiscalItem[];
+}
+
 /** Full population data for a single year */
 export interface YearPopulationSnapshot {
   readonly year: number;
@@ -204,6 +583,56 @@ export interface InputDataStats {
   readonly yearsProjected: number;
 }
 
+/** Leslie matrix and stable population for one sex */
+export interface StablePopulationResult {
+  readonly gender: Gender;
+  readonly leslieFertility: number[];  // first row by age
+  readonly leslieSurvival: number[];   // survival to next age, last entry = open group
+  readonly lambda?: number;            // annual growth ratio (absent without reproduction)
+  readonly intrinsicGrowthRate?: number;
+  readonly netReproductionRate?: number;
+  readonly stableAgeDistribution: number[];
+  readonly reproductiveValues: number[];
+}
+
+/** Median and prediction intervals of a simulated quantity */
+export interface Quantiles {
+  readonly median: number;
+  readonly lower80: number;
+  readonly upper80: number;
+  readonly lower95: number;
+  readonly upper95: number;
+}
+
+/** Distribution of simulated outcomes for one year */
+export interface StochasticYear {
+  readonly year: number;
+  readonly totalPopulation: Quantiles;
+  readonly births: Quantiles;
+  readonly deaths: Quantiles;
+  readonly netMigration: Quantiles;
+  readonly tfr: Quantiles;
+  readonly e0Male: Quantiles;
+  readonly e0Female: Quantiles;
+}
+
+/** Lee-Carter parameters for one sex */
+export interface LeeCarterParameters {
+  readonly ax: number[];  // mean log central death rate by age
+  readonly bx: number[];  // age response to the index, sums to 1
+  readonly kt: number[];  // mortality index by fitted year, sums to 0
+  readonly drift: number; // average yearly change of kt
+}
+
+/** Lee-Carter model fitted to the mortality history */
+export interface LeeCarterFit {
+  readonly regionId: string;
+  readonly ages: number[];
+  readonly years: number[];
+  readonly male: LeeCarterParameters;
+  readonly female: LeeCarterParameters;
+}
+
 /** Run projection response payload */
 export interface ProjectionRunResponse {
   readonly workspaceId: string;
@@ -214,6 +643,26 @@ export interface ProjectionRunResponse {
   readonly inputStats?: InputDataStats;
   /** Full population snapshots by year (age/sex breakdown) */
   readonly populationByYear?: YearPopulationSnapshot[];
+  /** Stable population analysis by sex (when requested) */
+  readonly stablePopulation?: StablePopulationResult[];
+  /** Period life tables by year and sex (when requested) */
+  readonly lifeTables?: LifeTableResult[];
+  /** Median and 80%/95% prediction intervals by year (when requested) */
+  readonly stochastic?: StochasticYear[];
+  /** Fitted Lee-Carter parameters (when mortality history is given) */
+  readonly leeCarter?: LeeCarterFit;
+  /** Populations estimated before the base year, ascending (when requested) */
+  readonly backcast?: BackcastYearResult[];
+  /** Results by state of the extra dimension (when one is given) */
+  readonly states?: StateYearResult[];
+  /** Households for every population snapshot (when headship rates are given) */
+  readonly households?: HouseholdYear[];
+  /** Labor force for every population snapshot (when participation rates are given) */
+  readonly laborForce?: LaborForceYear[];
+  /** Demand by profile and region for every population snapshot (when demand profiles are given) */
+  readonly serviceDemand?: ServiceDemandYear[];
+  /** Revenue, expenditure and balance for every population snapshot (when fiscal profiles are given) */
+  readonly fiscal?: FiscalYear[];
 }
 
 /** Projection progress update */
@@ -35,6 +35,10 @@ export interface AbsoluteModifier {
 
 export interface FunctionModifier {
   type: 'function';
+  /**
+   * Expression of `base`, `year`, `age` and `t` (years since the shock
+   * started), e.g. `base * (1 + 0.5 * exp(-t / 2))`
+   */
   expression: string;
 }
 
@@ -176,7 +180,7 @@ export const SHOCK_TEMPLATES: ShockTemplate[] = [
       targetRegions: 'all',
       targetGenders: 'all',
       targetAges: { min: 20, max: 40 },
-      modifier: -50000, // 50,000 emigrants per year
+      modifier: { type: 'absolute', value: -50000 }, // 50,000 emigrants per year
     },
   },
   {
@@ -190,7 +194,7 @@ export const SHOCK_TEMPLATES: ShockTemplate[] = [
       targetRegions: 'all',
       targetGenders: 'all',
       targetAges: 'all',
-      modifier: 100000, // 100,000 immigrants per year
+      modifier: { type: 'absolute', value: 100000 }, // 100,000 immigrants per year
     },
   },
 ];
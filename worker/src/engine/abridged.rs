@@ -0,0 +1,428 @@
+//! Abridged Cohort-Component Method
+//!
+//! Projection with five-year age groups and five-year steps, for inputs
+//! published in abridged form (UN WPP, Eurostat, older censuses).
+//!
+//! For each step t → t+5:
+//! 1. **Migration**: Net migrants of the step by age group at the start;
+//!    the [`MigrationTiming`] start share is exposed to survival
+//! 2. **Survival**: Each group is multiplied by its five-year survival ratio
+//!    (5Lx+5 / 5Lx) and moved up one group; the open group keeps its survivors
+//! 3. **Fertility**: Births over the step are `5 × Σ ASFR × ` the mean of
+//!    women in the group at the start and at the end of the step
+//! 4. **Births cohort**: Births survive into the 0-4 group with the birth
+//!    survival ratio (5L0 / 5·l0)
+//!
+//! Ages are group start ages (0, 5, 10, ...). Rate tables use the same
+//! ages; fertility rates are annual, migration is the total for the step.
+
+use std::collections::HashMap;
+
+use super::ccm::apply_net_migration;
+use super::series::{Interpolation, TableSeries};
+use super::state::PopulationState;
+use super::types::*;
+
+/// Width of age groups and length of a projection step (years)
+pub const AGE_STEP: u32 = 5;
+
+/// Default first age of the open-ended group (100+)
+const DEFAULT_OPEN_AGE: u32 = 100;
+
+/// Lowest allowed open age group (all fertile groups stay separate)
+const MIN_OPEN_AGE: u32 = 50;
+
+/// First and last fertile age groups (15-19 to 45-49)
+const FERTILITY_MIN_AGE: u32 = 15;
+const FERTILITY_MAX_AGE: u32 = 45;
+
+/// Five-year cohort-component model
+pub struct AbridgedModel {
+    /// Population by age group index (age / 5), gender and region index
+    population: PopulationState,
+
+    /// Index of the open-ended group
+    open_group: u32,
+
+    /// Survival ratio tables by region, anchored by year
+    survival_tables: HashMap<String, TableSeries<SurvivalTable>>,
+
+    /// Fertility tables by region, anchored by year
+    fertility_tables: HashMap<String, TableSeries<FertilityTable>>,
+
+    /// Migration tables by region, anchored by year
+    migration_tables: HashMap<String, TableSeries<MigrationTable>>,
+
+    /// How rates are derived between anchor years
+    interpolation: Interpolation,
+
+    /// When net migrants arrive within the step
+    migration_timing: MigrationTiming,
+}
+
+impl AbridgedModel {
+    /// Create a new empty abridged model
+    pub fn new() -> Self {
+        Self {
+            population: PopulationState::new(DEFAULT_OPEN_AGE / AGE_STEP),
+            open_group: DEFAULT_OPEN_AGE / AGE_STEP,
+            survival_tables: HashMap::new(),
+            fertility_tables: HashMap::new(),
+            migration_tables: HashMap::new(),
+            interpolation: Interpolation::default(),
+            migration_timing: MigrationTiming::default(),
+        }
+    }
+
+    /// Set the first age of the open-ended group (default: 100)
+    ///
+    /// The age is rounded down to a group start and limited to at least 50.
+    /// Population already loaded above it is folded into the open group.
+    pub fn set_open_age_group(&mut self, age: u32) {
+        let cohorts = self.get_cohorts();
+        self.open_group = age.max(MIN_OPEN_AGE) / AGE_STEP;
+        self.population = PopulationState::new(self.open_group);
+        self.load_population(&cohorts);
+    }
+
+    /// Load initial population from cohorts by five-year group
+    ///
+    /// A cohort's age may be any age in its group; ages above the open
+    /// group are added to it.
+    pub fn load_population(&mut self, cohorts: &[Cohort]) {
+        self.population.clear();
+        for cohort in cohorts {
+            let region = self.population.add_region(&cohort.region_id);
+            self.population.add(cohort.age / AGE_STEP, cohort.gender, region, cohort.count);
+        }
+    }
+
+    /// Load survival ratios for a region and year
+    pub fn load_survival_table(&mut self, table: SurvivalTable) {
+        self.survival_tables.entry(table.region_id.clone()).or_default().insert(table);
+    }
+
+    /// Load annual fertility rates by five-year group for a region and year
+    pub fn load_fertility_table(&mut self, table: FertilityTable) {
+        self.fertility_tables.entry(table.region_id.clone()).or_default().insert(table);
+    }
+
+    /// Load net migrants per step by five-year group for a region and year
+    pub fn load_migration_table(&mut self, table: MigrationTable) {
+        self.migration_tables.entry(table.region_id.clone()).or_default().insert(table);
+    }
+
+    /// Set how rates are derived between anchor years (default: linear)
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Set when net migrants arrive within the step (default: start)
+    pub fn set_migration_timing(&mut self, timing: MigrationTiming) {
+        self.migration_timing = timing;
+    }
+
+    /// Get total population across all groups
+    pub fn total_population(&self) -> f64 {
+        self.population.total()
+    }
+
+    /// Project population over one five-year step starting in `year`
+    ///
+    /// Rates in effect for `year` are used for the whole step. The returned
+    /// [`ProjectionYear`] holds the step totals and the population at its end.
+    pub fn project_step(&mut self, year: u32, regions: &[String]) -> ProjectionYear {
+        let initial_population = self.total_population();
+        let mut total_births = 0.0;
+        let mut total_deaths = 0.0;
+        let mut total_migration = 0.0;
+        let mut unmet_emigration = 0.0;
+
+        let region_indices: Vec<usize> = regions.iter()
+            .map(|region_id| self.population.add_region(region_id))
+            .collect();
+        let mut new_population = self.population.empty_like();
+
+        let start_share = self.migration_timing.start_share();
+        // Women by group after start-of-step migration
+        let mut women = vec![0.0; self.open_group as usize + 1];
+        let mut region_years = Vec::with_capacity(regions.len());
+
+        for (region_id, &region) in regions.iter().zip(&region_indices) {
+            let start_population = self.region_counts(&self.population, region).iter().sum::<f64>();
+            let totals_before = (total_deaths, total_migration);
+            let survival = self.survival_tables.get(region_id)
+                .and_then(|series| series.table_for(year, self.interpolation));
+            let fertility = self.fertility_tables.get(region_id)
+                .and_then(|series| series.table_for(year, self.interpolation));
+            let migration = self.migration_tables.get(region_id)
+                .and_then(|series| series.table_for(year, self.interpolation));
+
+            // Steps 1-2: Migration, survival and moving up one group
+            for group in 0..=self.open_group {
+                let age = group * AGE_STEP;
+                for gender in [Gender::Male, Gender::Female] {
+                    let mut count = self.population.get(group, gender, region);
+
+                    let migrants = migration.as_ref()
+                        .map(|table| table.get_rate(age, gender))
+                        .unwrap_or(0.0);
+                    let start_migrants = migrants * start_share;
+                    let applied = apply_net_migration(count, start_migrants);
+                    count += applied;
+                    total_migration += applied;
+                    unmet_emigration += applied - start_migrants;
+
+                    if gender == Gender::Female {
+                        women[group as usize] = count;
+                    }
+
+                    let ratio = survival.as_ref()
+                        .map(|table| table.get_ratio(age, gender).clamp(0.0, 1.0))
+                        .unwrap_or(0.0);
+                    let mut survivors = count * ratio;
+                    total_deaths += count - survivors;
+
+                    let end_migrants = migrants - start_migrants;
+                    let applied = apply_net_migration(survivors, end_migrants);
+                    survivors += applied;
+                    total_migration += applied;
+                    unmet_emigration += applied - end_migrants;
+
+                    if survivors > 0.0 {
+                        let new_group = (group + 1).min(self.open_group);
+                        new_population.add(new_group, gender, region, survivors);
+                    }
+                }
+            }
+
+            // Step 3: Births over the step from mean women at start and end
+            let mut births = 0.0;
+            if let Some(fertility) = &fertility {
+                for age in (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE).step_by(AGE_STEP as usize) {
+                    let group = age / AGE_STEP;
+                    let end_of_step = new_population.get(group, Gender::Female, region);
+                    let exposure = (women[group as usize] + end_of_step) / 2.0;
+                    births += AGE_STEP as f64 * fertility.get_rate(age) * exposure;
+                }
+            }
+            total_births += births;
+
+            // Step 4: Births surviving to the end of the step form the 0-4 group
+            let sex_ratio = fertility.as_ref().map(|table| table.sex_ratio_at_birth).unwrap_or(105.0);
+            let male_proportion = sex_ratio / (sex_ratio + 100.0);
+            for (gender, born) in [
+                (Gender::Male, births * male_proportion),
+                (Gender::Female, births * (1.0 - male_proportion)),
+            ] {
+                let ratio = survival.as_ref()
+                    .map(|table| table.birth_survival(gender).clamp(0.0, 1.0))
+                    .unwrap_or(0.0);
+                let survivors = born * ratio;
+                total_deaths += born - survivors;
+                new_population.add(0, gender, region, survivors);
+            }
+
+            // Step 5: Region components and indicators
+            let counts = self.region_counts(&new_population, region);
+            let deaths = total_deaths - totals_before.0;
+            let tfr = fertility.as_ref()
+                .map(|table| {
+                    (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE).step_by(AGE_STEP as usize)
+                        .map(|age| AGE_STEP as f64 * table.get_rate(age))
+                        .sum()
+                })
+                .unwrap_or(0.0);
+            region_years.push(RegionYear {
+                region_id: region_id.clone(),
+                total_population: counts.iter().sum(),
+                births,
+                deaths,
+                net_migration: total_migration - totals_before.1,
+                internal_in: 0.0,
+                internal_out: 0.0,
+                indicators: Indicators::compute(&counts, AGE_STEP, start_population, births, deaths, tfr),
+                components: None,
+                audit: None,
+            });
+        }
+
+        self.population = new_population;
+
+        let final_population = self.total_population();
+        let natural_change = total_births - total_deaths;
+        let growth_rate = if initial_population > 0.0 {
+            ((final_population - initial_population) / initial_population) * 100.0
+        } else if final_population > 0.0 {
+            100.0
+        } else {
+            0.0
+        };
+
+        ProjectionYear {
+            year,
+            total_population: final_population,
+            births: total_births,
+            deaths: total_deaths,
+            net_migration: total_migration,
+            internal_migration: 0.0,
+            unmet_emigration,
+            natural_change,
+            growth_rate,
+            regions: region_years,
+        }
+    }
+
+    /// Both sexes by age group for one region of a population
+    fn region_counts(&self, population: &PopulationState, region: usize) -> Vec<f64> {
+        (0..=self.open_group)
+            .map(|group| population.get(group, Gender::Male, region) + population.get(group, Gender::Female, region))
+            .collect()
+    }
+
+    /// Get population as cohorts aged at the group start (0, 5, 10, ...)
+    pub fn get_cohorts(&self) -> Vec<Cohort> {
+        self.population
+            .to_cohorts()
+            .into_iter()
+            .map(|cohort| Cohort { age: cohort.age * AGE_STEP, ..cohort })
+            .collect()
+    }
+}
+
+impl Default for AbridgedModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn survival(ratio: f64, birth: f64) -> SurvivalTable {
+        SurvivalTable {
+            region_id: "TEST".to_string(),
+            year: 2020,
+            ratios: (0..=100).step_by(5).map(|age| SurvivalRatio { age, male: ratio, female: ratio }).collect(),
+            birth_male: birth,
+            birth_female: birth,
+        }
+    }
+
+    fn fertility(age: u32, rate: f64) -> FertilityTable {
+        FertilityTable {
+            region_id: "TEST".to_string(),
+            year: 2020,
+            rates: vec![FertilityRate { age, rate }],
+            sex_ratio_at_birth: 100.0,
+        }
+    }
+
+    fn cohort(age: u32, gender: Gender, count: f64) -> Cohort {
+        Cohort { age, gender, region_id: "TEST".to_string(), count }
+    }
+
+    fn regions() -> Vec<String> {
+        vec!["TEST".to_string()]
+    }
+
+    /// Count in the group starting at `age`
+    fn count(model: &AbridgedModel, age: u32, gender: Gender) -> f64 {
+        model.get_cohorts()
+            .iter()
+            .filter(|c| c.age == age && c.gender == gender)
+            .map(|c| c.count)
+            .sum()
+    }
+
+    #[test]
+    fn test_groups_move_up_five_years() {
+        let mut model = AbridgedModel::new();
+        model.load_population(&[cohort(20, Gender::Male, 1000.0)]);
+        model.load_survival_table(survival(0.9, 1.0));
+
+        let result = model.project_step(2020, &regions());
+
+        assert!((count(&model, 25, Gender::Male) - 900.0).abs() < 1e-9);
+        assert_eq!(count(&model, 20, Gender::Male), 0.0);
+        assert!((result.deaths - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_population_by_single_age_is_grouped() {
+        let mut model = AbridgedModel::new();
+        model.load_population(&[cohort(21, Gender::Female, 10.0), cohort(24, Gender::Female, 5.0)]);
+
+        assert_eq!(count(&model, 20, Gender::Female), 15.0);
+        assert_eq!(model.get_cohorts()[0].age, 20);
+    }
+
+    #[test]
+    fn test_open_group_keeps_survivors() {
+        let mut model = AbridgedModel::new();
+        model.set_open_age_group(85);
+        model.load_population(&[cohort(80, Gender::Female, 100.0), cohort(85, Gender::Female, 100.0)]);
+        model.load_survival_table(survival(0.5, 1.0));
+
+        model.project_step(2020, &regions());
+
+        // 80-84 survivors join the 85+ survivors
+        assert!((count(&model, 85, Gender::Female) - 100.0).abs() < 1e-9);
+        assert_eq!(count(&model, 90, Gender::Female), 0.0);
+    }
+
+    #[test]
+    fn test_births_use_mean_exposure_over_step() {
+        // Women 25-29 at the start move to 30-34; ASFR 0.1 in both groups
+        let mut model = AbridgedModel::new();
+        model.load_population(&[cohort(25, Gender::Female, 1000.0)]);
+        model.load_survival_table(survival(1.0, 1.0));
+        model.load_fertility_table(FertilityTable {
+            region_id: "TEST".to_string(),
+            year: 2020,
+            rates: vec![FertilityRate { age: 25, rate: 0.1 }, FertilityRate { age: 30, rate: 0.1 }],
+            sex_ratio_at_birth: 100.0,
+        });
+
+        let result = model.project_step(2020, &regions());
+
+        // 5 × 0.1 × (500 + 500)
+        assert!((result.births - 500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_births_cohort_uses_birth_survival() {
+        let mut model = AbridgedModel::new();
+        model.load_population(&[cohort(30, Gender::Female, 1000.0)]);
+        model.load_survival_table(survival(0.0, 0.9));
+        model.load_fertility_table(fertility(30, 0.2));
+
+        let result = model.project_step(2020, &regions());
+
+        // Women die before the end, so exposure is 500: births = 5 × 0.2 × 500
+        assert!((result.births - 500.0).abs() < 1e-9);
+        assert!((count(&model, 0, Gender::Male) - 225.0).abs() < 1e-9);
+        assert!((count(&model, 0, Gender::Female) - 225.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_step_balances() {
+        let mut model = AbridgedModel::new();
+        model.load_population(&[cohort(25, Gender::Female, 1000.0), cohort(60, Gender::Male, 800.0)]);
+        model.load_survival_table(survival(0.95, 0.98));
+        model.load_fertility_table(fertility(25, 0.08));
+        model.load_migration_table(MigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2020,
+            rates: vec![MigrationRate { age: 25, male: 50.0, female: -2000.0 }],
+        });
+        model.set_migration_timing(MigrationTiming::MidPeriod);
+        let initial = model.total_population();
+
+        let result = model.project_step(2020, &regions());
+
+        let expected = initial + result.births - result.deaths + result.net_migration;
+        assert!((result.total_population - expected).abs() < 1e-9);
+    }
+}
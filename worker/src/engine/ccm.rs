@@ -6,216 +6,585 @@
 //! ## Algorithm
 //!
 //! For each year t → t+1:
-//! 1. **Fertility**: Calculate births from women of reproductive age (15-49)
-//! 2. **Migration**: Add/subtract net migrants by age/gender
-//! 3. **Mortality**: Apply survival rates to reduce cohort sizes
-//! 4. **Aging**: Move survivors up one year of age
+//! 1. **Migration**: Move interregional migrants between regions, then
+//!    add/subtract net (external) migrants by age/gender and remove
+//!    emigrants given as rates of the cohort. Depending on
+//!    [`MigrationTiming`], migration happens at the start of the year,
+//!    half at the start and half at the end, or at the end. Net emigration
+//!    exceeding a cohort is reported as unmet.
+//! 2. **Mortality**: Apply survival rates to reduce cohort sizes. The open
+//!    age group survives with the life-table ratio T(x+1)/T(x), and ages above
+//!    the last tabulated mortality rate keep that rate.
+//! 3. **Aging**: Move survivors up one year of age
+//! 4. **Fertility**: Births from women of reproductive age (15-49) present
+//!    after start-of-period migration plus half of the end-of-period
+//!    migrants, or the mean of the women present after start-of-period
+//!    migration and those of the same age at the end of the year
+//!    ([`FertilityExposure`]); newborns survive to the end of the year with
+//!    the age-0 rate and the infant separation factor
+//!
+//! Rate tables are loaded per region and year. Each projected year uses the
+//! table for that year, interpolated between anchor years (see [`TableSeries`]).
+//!
+//! Scenario shocks modify the rates in effect for the cohorts they target:
+//! mortality and fertility rates and net migration counts. Emigration rates
+//! are not shocked, so a migration multiplier cannot push them past one.
+//! Absolute migration shocks add their net migrants to the start-of-year
+//! population's targeted cohorts in proportion to their size.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+use super::infant::{self, DEFAULT_SEPARATION_FACTOR};
+use super::leslie::LeslieMatrix;
+use super::life_table::LifeTable;
+use super::series::{Interpolation, TableSeries};
+use super::state::{gender_index, PopulationState};
 use super::types::*;
 
-/// Key for storing population by age, gender, and region
-fn cohort_key(age: u32, gender: Gender, region_id: &str) -> String {
-    let gender_str = match gender {
-        Gender::Male => "M",
-        Gender::Female => "F",
-    };
-    format!("{}:{}:{}", age, gender_str, region_id)
-}
+/// Default first age of the open-ended interval (120+)
+const DEFAULT_OPEN_AGE: u32 = 120;
 
-/// Parse a cohort key back to components
-fn parse_cohort_key(key: &str) -> Option<(u32, Gender, String)> {
-    let parts: Vec<&str> = key.splitn(3, ':').collect();
-    if parts.len() != 3 {
-        return None;
-    }
-    
-    let age: u32 = parts[0].parse().ok()?;
-    let gender = match parts[1] {
-        "M" => Gender::Male,
-        "F" => Gender::Female,
-        _ => return None,
-    };
-    let region_id = parts[2].to_string();
-    
-    Some((age, gender, region_id))
-}
+/// Minimum and maximum age for fertility
+pub(super) const FERTILITY_MIN_AGE: u32 = 15;
+pub(super) const FERTILITY_MAX_AGE: u32 = 49;
 
-/// Maximum age in the model (open-ended interval: 120+)
-const MAX_AGE: u32 = 120;
+/// Lowest allowed open age group (all fertile ages stay single-year)
+pub const MIN_OPEN_AGE: u32 = FERTILITY_MAX_AGE + 1;
 
-/// Minimum and maximum age for fertility
-const FERTILITY_MIN_AGE: u32 = 15;
-const FERTILITY_MAX_AGE: u32 = 49;
+/// Highest death probability a shock can raise a rate to, so that shocked
+/// cohorts keep survivors
+const MAX_SHOCKED_MORTALITY: f64 = 0.999;
 
 /// Cohort-Component Model for demographic projections
+#[derive(Clone)]
 pub struct CohortComponentModel {
-    /// Population counts by "age:gender:region" key
-    population: HashMap<String, f64>,
+    /// Population counts by age, gender and region index
+    population: PopulationState,
+
+    /// First age of the open-ended interval
+    max_age: u32,
     
-    /// Mortality tables by region
-    mortality_tables: HashMap<String, MortalityTable>,
+    /// Mortality tables by region, anchored by year
+    mortality_tables: HashMap<String, TableSeries<MortalityTable>>,
     
-    /// Fertility tables by region
-    fertility_tables: HashMap<String, FertilityTable>,
+    /// Fertility tables by region, anchored by year
+    fertility_tables: HashMap<String, TableSeries<FertilityTable>>,
     
-    /// Migration tables by region
-    migration_tables: HashMap<String, MigrationTable>,
+    /// Migration tables by region, anchored by year
+    migration_tables: HashMap<String, TableSeries<MigrationTable>>,
+
+    /// Emigration rate tables by region, anchored by year
+    emigration_tables: HashMap<String, TableSeries<EmigrationTable>>,
+
+    /// How rates are derived between anchor years
+    interpolation: Interpolation,
+
+    /// When net migrants arrive within the projection step
+    migration_timing: MigrationTiming,
+
+    /// Share of infant deaths occurring in the calendar year of birth
+    infant_separation_factor: f64,
+
+    /// Female population used as exposure for fertility rates
+    fertility_exposure: FertilityExposure,
+
+    /// Origin → destination migration matrices, anchored by year (multiregional mode)
+    interregional_tables: TableSeries<InterregionalMigrationTable>,
+
+    /// Multipliers on the rates in effect, by region
+    adjustments: HashMap<String, RateAdjustment>,
+
+    /// Whether projected years carry components by age
+    component_detail: bool,
+
+    /// Whether projected years carry a balancing-equation audit
+    audit: bool,
+
+    /// Scenario shocks modifying the rates
+    shocks: Vec<Shock>,
 }
 
 impl CohortComponentModel {
     /// Create a new empty CCM model
     pub fn new() -> Self {
         Self {
-            population: HashMap::new(),
+            population: PopulationState::new(DEFAULT_OPEN_AGE),
+            max_age: DEFAULT_OPEN_AGE,
             mortality_tables: HashMap::new(),
             fertility_tables: HashMap::new(),
             migration_tables: HashMap::new(),
+            emigration_tables: HashMap::new(),
+            interpolation: Interpolation::default(),
+            migration_timing: MigrationTiming::default(),
+            infant_separation_factor: DEFAULT_SEPARATION_FACTOR,
+            fertility_exposure: FertilityExposure::default(),
+            interregional_tables: TableSeries::new(),
+            adjustments: HashMap::new(),
+            component_detail: false,
+            audit: false,
+            shocks: Vec::new(),
         }
     }
 
     /// Load initial population from cohorts
+    ///
+    /// Ages above the open age group are added to it.
     pub fn load_population(&mut self, cohorts: &[Cohort]) {
         self.population.clear();
         for cohort in cohorts {
-            let key = cohort_key(cohort.age, cohort.gender, &cohort.region_id);
-            *self.population.entry(key).or_insert(0.0) += cohort.count;
+            let region = self.population.add_region(&cohort.region_id);
+            self.population.add(cohort.age, cohort.gender, region, cohort.count);
         }
     }
 
-    /// Load a mortality table for a region
+    /// Load a mortality table for a region and year
+    ///
+    /// Tables for other years of the same region are kept as anchors;
+    /// a table for an already loaded year replaces it.
     pub fn load_mortality_table(&mut self, table: MortalityTable) {
-        self.mortality_tables.insert(table.region_id.clone(), table);
+        self.mortality_tables.entry(table.region_id.clone()).or_default().insert(table);
     }
 
-    /// Load a fertility table for a region
+    /// Load a fertility table for a region and year
     pub fn load_fertility_table(&mut self, table: FertilityTable) {
-        self.fertility_tables.insert(table.region_id.clone(), table);
+        self.fertility_tables.entry(table.region_id.clone()).or_default().insert(table);
     }
 
-    /// Load a migration table for a region
+    /// Load a migration table for a region and year
     pub fn load_migration_table(&mut self, table: MigrationTable) {
-        self.migration_tables.insert(table.region_id.clone(), table);
+        self.migration_tables.entry(table.region_id.clone()).or_default().insert(table);
+    }
+
+    /// Load an emigration rate table for a region and year
+    ///
+    /// Emigrants are the rate times the cohort, in addition to the net
+    /// migrants of the migration table (which then usually holds immigration).
+    pub fn load_emigration_table(&mut self, table: EmigrationTable) {
+        self.emigration_tables.entry(table.region_id.clone()).or_default().insert(table);
+    }
+
+    /// Set the first age of the open-ended interval (default: 120)
+    ///
+    /// Ages are limited to at least [`MIN_OPEN_AGE`]. Population already
+    /// loaded above the new open age is folded into it.
+    pub fn set_open_age_group(&mut self, age: u32) {
+        let cohorts = self.population.to_cohorts();
+        self.max_age = age.max(MIN_OPEN_AGE);
+        self.population = PopulationState::new(self.max_age);
+        self.load_population(&cohorts);
+    }
+
+    /// Set how rates are derived between anchor years (default: linear)
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Set when net migrants arrive within the projection step (default: start)
+    pub fn set_migration_timing(&mut self, timing: MigrationTiming) {
+        self.migration_timing = timing;
+    }
+
+    /// Set the share of infant deaths occurring in the year of birth (0-1)
+    pub fn set_infant_separation_factor(&mut self, factor: f64) {
+        self.infant_separation_factor = factor.clamp(0.0, 1.0);
+    }
+
+    /// Set the female exposure used for births (default: start of year)
+    pub fn set_fertility_exposure(&mut self, exposure: FertilityExposure) {
+        self.fertility_exposure = exposure;
+    }
+
+    /// Record deaths, births and migration by age for each region (default: off)
+    pub fn set_component_detail(&mut self, enabled: bool) {
+        self.component_detail = enabled;
+    }
+
+    /// Check the balancing equation for every region and cohort each year
+    ///
+    /// Projected regions then carry a [`RegionAudit`].
+    pub fn set_audit(&mut self, enabled: bool) {
+        self.audit = enabled;
+    }
+
+    /// Load an interregional migration matrix for a year, enabling multiregional mode
+    ///
+    /// Migrants are moved between the projected regions in the same step,
+    /// so internal migration always sums to zero nationally. Matrices for
+    /// other years are kept as anchors.
+    pub fn load_interregional_migration_table(&mut self, table: InterregionalMigrationTable) {
+        self.interregional_tables.insert(table);
+    }
+
+    /// Multiply a region's rates in effect until replaced (default: unadjusted)
+    ///
+    /// Mortality multipliers are applied to death probabilities, capped at 1.
+    pub fn set_rate_adjustment(&mut self, region_id: &str, adjustment: RateAdjustment) {
+        self.adjustments.insert(region_id.to_string(), adjustment);
+    }
+
+    /// Add a scenario shock applied to the rates of the years it covers
+    pub fn add_shock(&mut self, shock: Shock) {
+        self.shocks.push(shock);
+    }
+
+    /// First age of the open-ended interval
+    pub fn open_age_group(&self) -> u32 {
+        self.max_age
+    }
+
+    /// How rates are derived between anchor years
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Unadjusted mortality table in effect for a region and year
+    pub fn mortality_table_for(&self, region_id: &str, year: u32) -> Option<MortalityTable> {
+        self.mortality_tables.get(region_id)
+            .and_then(|series| series.table_for(year, self.interpolation))
+            .map(Cow::into_owned)
+    }
+
+    /// Unadjusted fertility table in effect for a region and year
+    pub fn fertility_table_for(&self, region_id: &str, year: u32) -> Option<FertilityTable> {
+        self.fertility_tables.get(region_id)
+            .and_then(|series| series.table_for(year, self.interpolation))
+            .map(Cow::into_owned)
     }
 
     /// Get population count for a specific cohort
     pub fn get_count(&self, age: u32, gender: Gender, region_id: &str) -> f64 {
-        let key = cohort_key(age, gender, region_id);
-        self.population.get(&key).copied().unwrap_or(0.0)
+        self.population
+            .region_index(region_id)
+            .map(|region| self.population.get(age, gender, region))
+            .unwrap_or(0.0)
+    }
+
+    /// Add persons to a cohort (negative counts remove them)
+    pub fn add_count(&mut self, age: u32, gender: Gender, region_id: &str, count: f64) {
+        let region = self.population.add_region(region_id);
+        self.population.add(age, gender, region, count);
     }
 
     /// Get total population across all cohorts
     pub fn total_population(&self) -> f64 {
-        self.population.values().sum()
+        self.population.total()
     }
 
-    /// Get mortality rate for a cohort, defaulting to 100% if not found
-    fn get_mortality_rate(&self, age: u32, gender: Gender, region_id: &str) -> f64 {
-        self.mortality_tables
-            .get(region_id)
-            .map(|table| table.get_rate(age, gender))
-            .unwrap_or(1.0) // Default: 100% mortality (everyone dies)
+    /// Rate tables in effect for a region in a given year
+    fn rates_for<'a>(&'a self, region_id: &'a str, year: u32) -> RegionRates<'a> {
+        RegionRates {
+            mortality: self.mortality_tables.get(region_id)
+                .and_then(|series| series.table_for(year, self.interpolation)),
+            fertility: self.fertility_tables.get(region_id)
+                .and_then(|series| series.table_for(year, self.interpolation)),
+            migration: self.migration_tables.get(region_id)
+                .and_then(|series| series.table_for(year, self.interpolation)),
+            emigration: self.emigration_tables.get(region_id)
+                .and_then(|series| series.table_for(year, self.interpolation)),
+            adjustment: self.adjustments.get(region_id).copied().unwrap_or_default(),
+            shocks: &self.shocks,
+            region_id,
+            year,
+        }
     }
 
-    /// Get fertility rate for a woman's age, defaulting to 0
-    fn get_fertility_rate(&self, age: u32, region_id: &str) -> f64 {
-        self.fertility_tables
-            .get(region_id)
-            .map(|table| table.get_rate(age))
-            .unwrap_or(0.0)
+    /// Net migrants added by absolute migration shocks, by cohort
+    ///
+    /// Each shock's yearly total is spread over the targeted cohorts of the
+    /// projected regions in proportion to their population, or evenly when
+    /// they are empty.
+    fn calculate_shock_migration(&self, year: u32, regions: &[String]) -> PopulationState {
+        let mut changes = self.population.empty_like();
+        let absolute = self.shocks.iter().filter(|shock| {
+            shock.shock_type == ShockType::Migration
+                && matches!(shock.modifier, ShockModifier::Absolute { .. })
+                && (shock.start_year..=shock.end_year).contains(&year)
+        });
+        for shock in absolute {
+            let mut targets = Vec::new();
+            for region_id in regions {
+                let Some(region) = self.population.region_index(region_id) else {
+                    continue;
+                };
+                for age in 0..=self.max_age {
+                    for gender in [Gender::Male, Gender::Female] {
+                        if shock.applies(year, age, gender, region_id) {
+                            targets.push((age, gender, region, self.population.get(age, gender, region).max(0.0)));
+                        }
+                    }
+                }
+            }
+            let total: f64 = targets.iter().map(|&(_, _, _, count)| count).sum();
+            let migrants = shock.modify(0.0, year, 0);
+            for &(age, gender, region, count) in &targets {
+                let share = if total > 0.0 { count / total } else { 1.0 / targets.len() as f64 };
+                changes.add(age, gender, region, migrants * share);
+            }
+        }
+        changes
     }
 
-    /// Get sex ratio at birth for a region, defaulting to 105
-    fn get_sex_ratio_at_birth(&self, region_id: &str) -> f64 {
-        self.fertility_tables
-            .get(region_id)
-            .map(|table| table.sex_ratio_at_birth)
-            .unwrap_or(105.0)
-    }
+    /// Calculate interregional moves from the start-of-year population with
+    /// the matrix in effect for the year
+    ///
+    /// Flows whose origin or destination is not projected are ignored. If the
+    /// combined out-migration rates of a cohort exceed 1, they are scaled down
+    /// proportionally so nobody leaves twice.
+    ///
+    fn calculate_interregional_moves(&self, year: u32) -> InterregionalMoves {
+        let regions = self.population.region_count();
+        let mut moves = InterregionalMoves {
+            changes: self.population.empty_like(),
+            total: 0.0,
+            inflow: vec![0.0; regions],
+            outflow: vec![0.0; regions],
+        };
+        let table = match self.interregional_tables.table_for(year, self.interpolation) {
+            Some(table) => table,
+            None => return moves,
+        };
 
-    /// Get net migration for a cohort, defaulting to 0
-    fn get_migration_rate(&self, age: u32, gender: Gender, region_id: &str) -> f64 {
-        self.migration_tables
-            .get(region_id)
-            .map(|table| table.get_rate(age, gender))
-            .unwrap_or(0.0)
+        // Resolve region indices once; flows outside the projection are dropped
+        let flows: Vec<(usize, usize, &InterregionalFlow)> = table.flows.iter()
+            .filter(|flow| flow.origin_region_id != flow.destination_region_id)
+            .filter_map(|flow| {
+                let origin = self.population.region_index(&flow.origin_region_id)?;
+                let destination = self.population.region_index(&flow.destination_region_id)?;
+                Some((origin, destination, flow))
+            })
+            .collect();
+
+        // Total out-migration rate per origin cohort
+        let mut out_rates = self.population.empty_like();
+        for &(origin, _, flow) in &flows {
+            for gender in [Gender::Male, Gender::Female] {
+                out_rates.add(flow.age, gender, origin, flow.get_rate(gender).max(0.0));
+            }
+        }
+
+        for &(origin, destination, flow) in &flows {
+            for gender in [Gender::Male, Gender::Female] {
+                let rate = flow.get_rate(gender).max(0.0);
+                if rate == 0.0 {
+                    continue;
+                }
+
+                let count = self.population.get(flow.age, gender, origin);
+                if count <= 0.0 {
+                    continue;
+                }
+
+                let total_rate = out_rates.get(flow.age, gender, origin);
+                let scale = if total_rate > 1.0 { 1.0 / total_rate } else { 1.0 };
+                let movers = count * rate * scale;
+
+                moves.changes.add(flow.age, gender, origin, -movers);
+                moves.changes.add(flow.age, gender, destination, movers);
+                moves.total += movers;
+                moves.outflow[origin] += movers;
+                moves.inflow[destination] += movers;
+            }
+        }
+
+        moves
     }
 
     /// Project population for one year using CCM
     ///
     /// Steps:
-    /// 1. Calculate births from fertile women (before any changes)
-    /// 2. Apply migration (add immigrants, remove emigrants)
-    /// 3. Apply mortality to post-migration population
-    /// 4. Age survivors up one year
-    /// 5. Add newborns at age 0
-    /// 6. Return year summary
+    /// 1. Apply start-of-period migration (interregional moves, then the
+    ///    start share of net migration)
+    /// 2. Apply mortality, then the end-of-period share of net migration
+    /// 3. Age survivors up one year
+    /// 4. Add newborns surviving to the end of the year at age 0, born to
+    ///    women present after step 1 and half of the end-of-period female
+    ///    migrants (or the mean of the women after step 1 and those at the
+    ///    end of the year, see [`FertilityExposure`])
+    /// 5. Return year summary with components and indicators by region
     pub fn project_one_year(&mut self, year: u32, regions: &[String]) -> ProjectionYear {
         let initial_population = self.total_population();
         let mut total_births = 0.0;
         let mut total_deaths = 0.0;
         let mut total_migration = 0.0;
-        let mut new_population: HashMap<String, f64> = HashMap::new();
-
-        for region_id in regions {
-            // Step 1: Calculate births from fertile women (before they age/die/migrate)
-            let (births, male_births, female_births) = self.calculate_births(region_id);
-            total_births += births;
-
-            // Add newborns at age 0
-            if male_births > 0.0 {
-                let key = cohort_key(0, Gender::Male, region_id);
-                *new_population.entry(key).or_insert(0.0) += male_births;
-            }
-            if female_births > 0.0 {
-                let key = cohort_key(0, Gender::Female, region_id);
-                *new_population.entry(key).or_insert(0.0) += female_births;
-            }
-
-            // Step 2 & 3 & 4: Process each cohort - migration, mortality, aging
-            for age in 0..=MAX_AGE {
+        let mut unmet_emigration = 0.0;
+
+        // Regions outside `regions` are not carried into the new population
+        let region_indices: Vec<usize> = regions.iter()
+            .map(|region_id| self.population.add_region(region_id))
+            .collect();
+        let mut new_population = self.population.empty_like();
+        let internal_moves = self.calculate_interregional_moves(year);
+        let shock_migration = self.calculate_shock_migration(year, regions);
+
+        let start_share = self.migration_timing.start_share();
+        // Women by age after start-of-period migration, plus half of the
+        // end-of-period migrants (fertility exposure)
+        let mut women = vec![0.0; self.max_age as usize + 1];
+        let mut region_years = Vec::with_capacity(regions.len());
+
+        for (region_id, &region) in regions.iter().zip(&region_indices) {
+            let rates = self.rates_for(region_id, year);
+            let start_population = self.region_counts(&self.population, region).iter().sum::<f64>();
+            let totals_before = (total_births, total_deaths, total_migration);
+            let unmet_before = unmet_emigration;
+            let open_survival = [Gender::Male, Gender::Female]
+                .map(|gender| rates.open_age_survival(self.max_age, gender));
+            let mut detail = self.component_detail
+                .then(|| ComponentDetail::new(self.max_age, FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE));
+            let mut audit = self.audit.then(RegionAudit::default);
+            // Each cohort's balance (start + moves - deaths + migration),
+            // by the age and sex it should reach next year
+            let mut expected = vec![[0.0; 2]; if self.audit { self.max_age as usize + 1 } else { 0 }];
+
+            // Steps 1-3: Process each cohort - migration, mortality, aging
+            for age in 0..=self.max_age {
                 for gender in [Gender::Male, Gender::Female] {
-                    let key = cohort_key(age, gender, region_id);
-                    let mut count = self.population.get(&key).copied().unwrap_or(0.0);
+                    let mut count = self.population.get(age, gender, region);
+                    let start_count = count;
+                    let migration_before = total_migration;
                     
-                    // Step 2: Apply migration
-                    let migration = self.get_migration_rate(age, gender, region_id);
-                    
-                    if migration != 0.0 {
-                        if migration > 0.0 {
-                            // Immigration: add migrants
-                            count += migration;
-                            total_migration += migration;
-                        } else {
-                            // Emigration: remove migrants (but can't go negative)
-                            let emigrants = (-migration).min(count);
-                            count -= emigrants;
-                            total_migration -= emigrants;
-                        }
+                    // Step 1: Apply start-of-period migration (interregional moves first)
+                    let internal = internal_moves.changes.get(age, gender, region);
+                    if let Some(audit) = audit.as_mut() {
+                        audit.internal_migration += internal;
+                        audit.clamped_population += (-(count + internal)).max(0.0);
                     }
-                    
-                    if count <= 0.0 {
-                        continue;
+                    count = (count + internal).max(0.0);
+                    let migration = rates.migration(age, gender) + shock_migration.get(age, gender, region);
+                    let emigration_rate = rates.emigration_rate(age, gender);
+                    if let Some(audit) = audit.as_mut() {
+                        let mortality_rate = rates.mortality_rate(age, gender);
+                        audit.clamped_rates += [mortality_rate, emigration_rate].iter()
+                            .filter(|rate| !(0.0..=1.0).contains(*rate))
+                            .count() as u32;
+                    }
+                    let emigration_rate = emigration_rate.clamp(0.0, 1.0);
+                    let start_migration = migration * start_share;
+                    let applied = apply_net_migration(count, start_migration);
+                    count += applied;
+                    total_migration += applied;
+                    unmet_emigration += applied - start_migration;
+                    let emigrants = count * emigration_rate * start_share;
+                    count -= emigrants;
+                    total_migration -= emigrants;
+
+                    if gender == Gender::Female {
+                        women[age as usize] = count;
                     }
 
-                    // Step 3: Apply mortality
-                    let mortality_rate = self.get_mortality_rate(age, gender, region_id);
-                    // Clamp mortality rate to [0, 1]
-                    let mortality_rate = mortality_rate.clamp(0.0, 1.0);
-                    
+                    // Step 2: Apply mortality
+                    let mortality_rate = self.step_mortality(&rates, age, gender, open_survival[gender_index(gender)]);
                     let deaths = count * mortality_rate;
-                    let survivors = count - deaths;
+                    let mut survivors = count - deaths;
                     total_deaths += deaths;
 
-                    // Step 4: Age survivors (or keep at MAX_AGE for open-ended interval)
+                    // End-of-period migrants arrive after mortality
+                    let end_migration = migration - start_migration;
+                    let applied = apply_net_migration(survivors, end_migration);
+                    survivors += applied;
+                    total_migration += applied;
+                    unmet_emigration += applied - end_migration;
+                    let emigrants = survivors * emigration_rate * (1.0 - start_share);
+                    survivors -= emigrants;
+                    total_migration -= emigrants;
+                    // End-of-period migrants are exposed for half the year on
+                    // average; averaged exposure counts them at the end of the year
+                    if gender == Gender::Female && self.fertility_exposure == FertilityExposure::Start {
+                        women[age as usize] += (applied - emigrants) / 2.0;
+                    }
+
+                    if let Some(detail) = detail.as_mut() {
+                        detail.deaths[age as usize].add(gender, deaths);
+                        detail.migration[age as usize].add(gender, total_migration - migration_before);
+                    }
+
+                    // Step 3: Age survivors (or keep at max age for open-ended interval)
+                    let new_age = if age >= self.max_age { self.max_age } else { age + 1 };
                     if survivors > 0.0 {
-                        let new_age = if age >= MAX_AGE { MAX_AGE } else { age + 1 };
-                        let new_key = cohort_key(new_age, gender, region_id);
-                        *new_population.entry(new_key).or_insert(0.0) += survivors;
+                        new_population.add(new_age, gender, region, survivors);
+                    }
+                    if self.audit {
+                        expected[new_age as usize][gender_index(gender)] +=
+                            start_count + internal - deaths + (total_migration - migration_before);
                     }
                 }
             }
+
+            // Cohort balances against the population carried forward,
+            // before newborns join age 0
+            if let Some(audit) = audit.as_mut() {
+                for (age, cohort) in (0..).zip(&expected) {
+                    for gender in [Gender::Male, Gender::Female] {
+                        let gap = cohort[gender_index(gender)] - new_population.get(age, gender, region);
+                        audit.max_cohort_discrepancy = audit.max_cohort_discrepancy.max(gap.abs());
+                        if age == self.max_age {
+                            audit.open_age_loss += gap;
+                        }
+                    }
+                }
+            }
+
+            // Step 4: Births from women exposed during the year; newborns
+            // surviving to the end of the year are added at age 0
+            if self.fertility_exposure == FertilityExposure::Average {
+                for age in FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE {
+                    let end_of_year = new_population.get(age, Gender::Female, region);
+                    women[age as usize] = (women[age as usize] + end_of_year) / 2.0;
+                }
+            }
+            let (births, male_births, female_births) = calculate_births(&women, &rates);
+            total_births += births;
+            if let Some(audit) = audit.as_mut() {
+                audit.clamped_rates += (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE)
+                    .filter(|&age| rates.unclamped_fertility_rate(age) < 0.0)
+                    .count() as u32;
+            }
+            for (gender, born) in [(Gender::Male, male_births), (Gender::Female, female_births)] {
+                let q0 = rates.mortality_rate(0, gender);
+                if let Some(audit) = audit.as_mut() {
+                    audit.clamped_rates += u32::from(born > 0.0 && !(0.0..=1.0).contains(&q0));
+                }
+                let survivors = born * infant::birth_year_survival(q0, self.infant_separation_factor);
+                total_deaths += born - survivors;
+                new_population.add(0, gender, region, survivors);
+                if let Some(detail) = detail.as_mut() {
+                    match gender {
+                        Gender::Male => detail.newborn_deaths_male += born - survivors,
+                        Gender::Female => detail.newborn_deaths_female += born - survivors,
+                    }
+                }
+            }
+            if let Some(detail) = detail.as_mut() {
+                for row in &mut detail.births_by_mother_age {
+                    row.births = women[row.age as usize].max(0.0) * rates.fertility_rate(row.age);
+                }
+            }
+
+            // Step 5: Region components and indicators
+            let counts = self.region_counts(&new_population, region);
+            let births = total_births - totals_before.0;
+            let deaths = total_deaths - totals_before.1;
+            let net_migration = total_migration - totals_before.2;
+            let end_population: f64 = counts.iter().sum();
+            let tfr = (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE).map(|age| rates.fertility_rate(age)).sum();
+            if let Some(audit) = audit.as_mut() {
+                let balance = start_population + audit.internal_migration + births - deaths + net_migration;
+                audit.discrepancy = end_population - balance;
+                audit.clamped_emigration = unmet_emigration - unmet_before;
+            }
+            region_years.push(RegionYear {
+                region_id: region_id.clone(),
+                total_population: end_population,
+                births,
+                deaths,
+                net_migration,
+                internal_in: internal_moves.inflow[region],
+                internal_out: internal_moves.outflow[region],
+                indicators: Indicators::compute(&counts, 1, start_population, births, deaths, tfr),
+                components: detail,
+                audit,
+            });
         }
 
         // Update population
@@ -238,57 +607,386 @@ impl CohortComponentModel {
             births: total_births,
             deaths: total_deaths,
             net_migration: total_migration,
+            internal_migration: internal_moves.total,
+            unmet_emigration,
             natural_change,
             growth_rate,
+            regions: region_years,
         }
     }
 
-    /// Calculate births for a region
+    /// Estimate the population at the start of `year` by reverse survival
+    ///
+    /// The current population is taken as the start of `year + 1` and each
+    /// cohort is carried back through the year's migration, mortality and
+    /// aging with the rates of `year`, undoing the steps of
+    /// [`project_one_year`](Self::project_one_year) in reverse order.
     ///
-    /// Returns (total_births, male_births, female_births)
-    fn calculate_births(&self, region_id: &str) -> (f64, f64, f64) {
+    /// - The open age group is taken as stationary: the share given by its
+    ///   own survival was already in it, the rest entered from the age below
+    /// - Interregional moves are not reversed
+    /// - Cohorts left negative by migration are set to zero
+    /// - Newborns implied by the estimated women are compared with the
+    ///   observed age 0, as a check on the base data
+    pub fn backcast_one_year(&mut self, year: u32, regions: &[String]) -> BackcastYear {
         let mut total_births = 0.0;
+        let mut total_deaths = 0.0;
+        let mut total_migration = 0.0;
+        let mut newborn_discrepancy = 0.0;
+        let mut clamped_population = 0.0;
+
+        let region_indices: Vec<usize> = regions.iter()
+            .map(|region_id| self.population.add_region(region_id))
+            .collect();
+        let mut previous = self.population.empty_like();
+        let shock_migration = self.calculate_shock_migration(year, regions);
+        let start_share = self.migration_timing.start_share();
+        let mut women = vec![0.0; self.max_age as usize + 1];
+
+        for (region_id, &region) in regions.iter().zip(&region_indices) {
+            let rates = self.rates_for(region_id, year);
+            for gender in [Gender::Male, Gender::Female] {
+                let open_survival = rates.open_age_survival(self.max_age, gender);
+                let open = self.population.get(self.max_age, gender, region);
+
+                for age in 0..=self.max_age {
+                    // Persons at the end of the year who were aged `age` at its start
+                    let end_count = if age == self.max_age {
+                        open * open_survival
+                    } else if age + 1 == self.max_age {
+                        open * (1.0 - open_survival)
+                    } else {
+                        self.population.get(age + 1, gender, region)
+                    };
+
+                    // Undo end-of-period emigration and net migration
+                    let migration = rates.migration(age, gender) + shock_migration.get(age, gender, region);
+                    let emigration_rate = rates.emigration_rate(age, gender).clamp(0.0, 1.0);
+                    let end_retained = 1.0 - emigration_rate * (1.0 - start_share);
+                    let before_emigration = if end_retained > 0.0 { end_count / end_retained } else { 0.0 };
+                    let survivors = before_emigration - migration * (1.0 - start_share);
+                    clamped_population += (-survivors).max(0.0);
+                    let survivors = survivors.max(0.0);
+
+                    // Undo mortality
+                    let mortality_rate = self.step_mortality(&rates, age, gender, open_survival);
+                    let count = if mortality_rate < 1.0 { survivors / (1.0 - mortality_rate) } else { 0.0 };
+                    if gender == Gender::Female {
+                        women[age as usize] = match self.fertility_exposure {
+                            FertilityExposure::Start => count + (end_count - survivors) / 2.0,
+                            FertilityExposure::Average => count,
+                        };
+                    }
+
+                    // Undo start-of-period emigration and net migration
+                    let start_retained = 1.0 - emigration_rate * start_share;
+                    let before_emigration = if start_retained > 0.0 { count / start_retained } else { 0.0 };
+                    let start_count = before_emigration - migration * start_share;
+                    clamped_population += (-start_count).max(0.0);
+                    let start_count = start_count.max(0.0);
 
-        // Sum births from all fertile women (ages 15-49)
-        for age in FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE {
-            let women = self.get_count(age, Gender::Female, region_id);
-            if women <= 0.0 {
-                continue;
+                    total_deaths += count - survivors;
+                    total_migration += (count - start_count) + (end_count - survivors);
+                    previous.add(age, gender, region, start_count);
+                }
             }
 
-            let fertility_rate = self.get_fertility_rate(age, region_id);
-            total_births += women * fertility_rate;
+            // Births to the estimated women against the observed age 0
+            if self.fertility_exposure == FertilityExposure::Average {
+                for age in FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE {
+                    let end_of_year = self.population.get(age, Gender::Female, region);
+                    women[age as usize] = (women[age as usize] + end_of_year) / 2.0;
+                }
+            }
+            let (births, male_births, female_births) = calculate_births(&women, &rates);
+            total_births += births;
+            for (gender, born) in [(Gender::Male, male_births), (Gender::Female, female_births)] {
+                let q0 = rates.mortality_rate(0, gender);
+                let survivors = born * infant::birth_year_survival(q0, self.infant_separation_factor);
+                total_deaths += born - survivors;
+                newborn_discrepancy += self.population.get(0, gender, region) - survivors;
+            }
         }
 
-        if total_births <= 0.0 {
-            return (0.0, 0.0, 0.0);
+        self.population = previous;
+
+        BackcastYear {
+            year,
+            total_population: self.total_population(),
+            births: total_births,
+            deaths: total_deaths,
+            net_migration: total_migration,
+            newborn_discrepancy,
+            clamped_population,
+        }
+    }
+
+    /// Death probability applied to a cohort during one step
+    ///
+    /// Age 0 has already lived part of infancy and the open age group uses
+    /// life-table person-years; other rates are clamped to [0, 1].
+    fn step_mortality(&self, rates: &RegionRates, age: u32, gender: Gender, open_survival: f64) -> f64 {
+        let mortality_rate = rates.mortality_rate(age, gender);
+        if age == 0 {
+            infant::remaining_infant_mortality(mortality_rate, self.infant_separation_factor)
+        } else if age == self.max_age {
+            1.0 - open_survival
+        } else {
+            mortality_rate.clamp(0.0, 1.0)
         }
+    }
+
+    /// Both sexes by single year of age for one region of a population
+    fn region_counts(&self, population: &PopulationState, region: usize) -> Vec<f64> {
+        (0..=self.max_age)
+            .map(|age| population.get(age, Gender::Male, region) + population.get(age, Gender::Female, region))
+            .collect()
+    }
+
+    /// Leslie matrix for one sex of a region from the rates in effect in `year`
+    ///
+    /// Entries follow the projection step: age 0 faces the remaining infant
+    /// mortality, the open group its person-year survival, and F_x counts
+    /// births of that sex surviving to the end of the year. With
+    /// [`FertilityExposure::Average`], women aged x also give birth after
+    /// surviving to x+1. Migration is not included. The male matrix pairs
+    /// male survival with the female fertility schedule (one-sex model).
+    pub fn leslie_matrix(&self, region_id: &str, year: u32, gender: Gender) -> LeslieMatrix {
+        let rates = self.rates_for(region_id, year);
+
+        let survival: Vec<f64> = (0..=self.max_age)
+            .map(|age| {
+                let rate = rates.mortality_rate(age, gender);
+                if age == self.max_age {
+                    rates.open_age_survival(self.max_age, gender)
+                } else if age == 0 {
+                    1.0 - infant::remaining_infant_mortality(rate, self.infant_separation_factor)
+                } else {
+                    1.0 - rate.clamp(0.0, 1.0)
+                }
+            })
+            .collect();
 
-        // Split births by sex using sex ratio at birth
-        let sex_ratio = self.get_sex_ratio_at_birth(region_id);
-        // sex_ratio = males per 100 females
-        // male_proportion = sex_ratio / (sex_ratio + 100)
+        let sex_ratio = rates.sex_ratio_at_birth();
         let male_proportion = sex_ratio / (sex_ratio + 100.0);
-        
-        let male_births = total_births * male_proportion;
-        let female_births = total_births * (1.0 - male_proportion);
+        let share = match gender {
+            Gender::Male => male_proportion,
+            Gender::Female => 1.0 - male_proportion,
+        };
+        let newborn_survival = infant::birth_year_survival(
+            rates.mortality_rate(0, gender),
+            self.infant_separation_factor,
+        );
+
+        // Births counted by the projection: only ages 15-49 at start or end of year
+        let rate = |age: u32| {
+            if (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE).contains(&age) {
+                rates.fertility_rate(age)
+            } else {
+                0.0
+            }
+        };
+        let fertility = (0..=self.max_age)
+            .map(|age| {
+                let births = match self.fertility_exposure {
+                    FertilityExposure::Start => rate(age),
+                    FertilityExposure::Average => {
+                        (rate(age) + survival[age as usize] * rate(age + 1)) / 2.0
+                    }
+                };
+                births * share * newborn_survival
+            })
+            .collect();
 
-        (total_births, male_births, female_births)
+        LeslieMatrix { fertility, survival }
+    }
+
+    /// Period life table for one sex of a region from the rates in effect in `year`
+    ///
+    /// Starts at age 0 and runs to the open age group or the last tabulated
+    /// age, whichever is higher; the last rate is held above it. Returns
+    /// `None` without a mortality table or with a zero rate at the last age.
+    pub fn life_table(&self, region_id: &str, year: u32, gender: Gender) -> Option<LifeTable> {
+        let rates = self.rates_for(region_id, year);
+        let last_age = rates.mortality.as_ref()?.last_age().unwrap_or(self.max_age).max(self.max_age);
+        let qx: Vec<f64> = (0..=last_age).map(|age| rates.mortality_rate(age, gender)).collect();
+        LifeTable::from_probabilities(0, &qx, gender)
     }
 
     /// Get population as cohorts (for output)
     pub fn get_cohorts(&self) -> Vec<Cohort> {
-        self.population
-            .iter()
-            .filter_map(|(key, &count)| {
-                parse_cohort_key(key).map(|(age, gender, region_id)| Cohort {
-                    age,
-                    gender,
-                    region_id,
-                    count,
-                })
+        self.population.to_cohorts()
+    }
+}
+
+/// Apply net migration to a cohort count
+///
+/// Returns the migration actually applied: immigration in full, emigration
+/// capped at the available population so counts never go negative. The
+/// unmet emigration is the applied minus the requested migration.
+pub(super) fn apply_net_migration(count: f64, migration: f64) -> f64 {
+    if migration >= 0.0 {
+        migration
+    } else {
+        -(-migration).min(count.max(0.0))
+    }
+}
+
+/// Calculate births from women by single year of age
+///
+/// Returns (total_births, male_births, female_births)
+fn calculate_births(women: &[f64], rates: &RegionRates) -> (f64, f64, f64) {
+    let mut total_births = 0.0;
+
+    // Sum births from all fertile women (ages 15-49)
+    for age in FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE {
+        let count = women[age as usize];
+        if count <= 0.0 {
+            continue;
+        }
+
+        let fertility_rate = rates.fertility_rate(age);
+        total_births += count * fertility_rate;
+    }
+
+    if total_births <= 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    // Split births by sex using sex ratio at birth
+    let sex_ratio = rates.sex_ratio_at_birth();
+    // sex_ratio = males per 100 females
+    // male_proportion = sex_ratio / (sex_ratio + 100)
+    let male_proportion = sex_ratio / (sex_ratio + 100.0);
+    
+    let male_births = total_births * male_proportion;
+    let female_births = total_births * (1.0 - male_proportion);
+
+    (total_births, male_births, female_births)
+}
+
+/// Rate tables in effect for one region in one projected year
+/// Interregional moves of one year, by the start-of-year population
+struct InterregionalMoves {
+    /// Net change of each cohort, shaped like the population state
+    changes: PopulationState,
+    /// Persons moved between regions
+    total: f64,
+    /// Persons moving into / out of each region, by region index
+    inflow: Vec<f64>,
+    outflow: Vec<f64>,
+}
+
+struct RegionRates<'a> {
+    mortality: Option<Cow<'a, MortalityTable>>,
+    fertility: Option<Cow<'a, FertilityTable>>,
+    migration: Option<Cow<'a, MigrationTable>>,
+    emigration: Option<Cow<'a, EmigrationTable>>,
+    adjustment: RateAdjustment,
+    shocks: &'a [Shock],
+    region_id: &'a str,
+    year: u32,
+}
+
+impl RegionRates<'_> {
+    /// Apply the shocks of a type targeting a cohort to a rate
+    ///
+    /// Absolute migration shocks add counts rather than modify rates and
+    /// are skipped here. Shocked death probabilities stay between 0 and
+    /// [`MAX_SHOCKED_MORTALITY`] (or the unshocked rate, if higher), shocked
+    /// fertility rates non-negative. Rates without shocks are returned as
+    /// given, so the audit still sees invalid inputs.
+    fn shocked(&self, shock_type: ShockType, value: f64, age: u32, gender: Gender) -> f64 {
+        let mut shocks = self.shocks.iter()
+            .filter(|shock| shock.shock_type == shock_type)
+            .filter(|shock| shock_type != ShockType::Migration || !matches!(shock.modifier, ShockModifier::Absolute { .. }))
+            .filter(|shock| shock.applies(self.year, age, gender, self.region_id))
+            .peekable();
+        if shocks.peek().is_none() {
+            return value;
+        }
+        let shocked = shocks.fold(value, |value, shock| shock.modify(value, self.year, age));
+        match shock_type {
+            ShockType::Mortality => shocked.clamp(0.0, MAX_SHOCKED_MORTALITY.max(value)),
+            ShockType::Fertility => shocked.max(0.0),
+            ShockType::Migration => shocked,
+        }
+    }
+
+    /// Get mortality rate for a cohort, defaulting to 100% if not found
+    ///
+    /// Ages above the last tabulated age keep the last rate.
+    fn mortality_rate(&self, age: u32, gender: Gender) -> f64 {
+        self.mortality
+            .as_ref()
+            .map(|table| {
+                let rate = table.get_rate_or_last(age, gender) * self.adjustment.mortality(gender);
+                self.shocked(ShockType::Mortality, rate, age, gender)
             })
-            .collect()
+            .unwrap_or(1.0) // Default: 100% mortality (everyone dies)
+    }
+
+    /// Survival of the open age group starting at `open_age` (T(x+1)/T(x))
+    ///
+    /// Built from the rates at and above the open age, so a table extending
+    /// past the open age still contributes its older ages.
+    fn open_age_survival(&self, open_age: u32, gender: Gender) -> f64 {
+        let Some(table) = self.mortality.as_ref() else {
+            return 0.0;
+        };
+        let last_age = table.last_age().unwrap_or(open_age).max(open_age);
+        let qx: Vec<f64> = (open_age..=last_age)
+            .map(|age| {
+                let rate = table.get_rate_or_last(age, gender) * self.adjustment.mortality(gender);
+                self.shocked(ShockType::Mortality, rate, age, gender).clamp(0.0, 1.0)
+            })
+            .collect();
+        // No table without deaths at the last age: the group never dies out
+        LifeTable::from_probabilities(open_age, &qx, gender)
+            .map(|life_table| life_table.open_interval_survival(open_age))
+            .unwrap_or(1.0)
+    }
+
+    /// Get fertility rate for a woman's age, defaulting to 0
+    fn fertility_rate(&self, age: u32) -> f64 {
+        self.unclamped_fertility_rate(age).max(0.0)
+    }
+
+    /// Fertility rate after adjustments and shocks, which may be negative
+    fn unclamped_fertility_rate(&self, age: u32) -> f64 {
+        self.fertility
+            .as_ref()
+            .map(|table| {
+                let rate = table.get_rate(age) * self.adjustment.fertility;
+                self.shocked(ShockType::Fertility, rate, age, Gender::Female)
+            })
+            .unwrap_or(0.0)
+    }
+
+    /// Get sex ratio at birth, defaulting to 105
+    fn sex_ratio_at_birth(&self) -> f64 {
+        self.fertility
+            .as_ref()
+            .map(|table| table.sex_ratio_at_birth)
+            .unwrap_or(105.0)
+    }
+
+    /// Get net migration for a cohort, defaulting to 0
+    fn migration(&self, age: u32, gender: Gender) -> f64 {
+        self.migration
+            .as_ref()
+            .map(|table| self.shocked(ShockType::Migration, table.get_rate(age, gender) * self.adjustment.migration, age, gender))
+            .unwrap_or(0.0)
+    }
+
+    /// Get the emigration rate for a cohort, defaulting to 0
+    ///
+    /// Migration shocks apply to the net migration counts only.
+    fn emigration_rate(&self, age: u32, gender: Gender) -> f64 {
+        self.emigration
+            .as_ref()
+            .map(|table| table.get_rate(age, gender) * self.adjustment.migration)
+            .unwrap_or(0.0)
     }
 }
 
@@ -302,21 +1000,6 @@ impl Default for CohortComponentModel {
 mod unit_tests {
     use super::*;
 
-    #[test]
-    fn test_cohort_key_roundtrip() {
-        let key = cohort_key(25, Gender::Male, "CZ");
-        let parsed = parse_cohort_key(&key);
-        assert_eq!(parsed, Some((25, Gender::Male, "CZ".to_string())));
-    }
-
-    #[test]
-    fn test_cohort_key_female() {
-        let key = cohort_key(30, Gender::Female, "TEST");
-        assert!(key.contains("F"));
-        let parsed = parse_cohort_key(&key);
-        assert_eq!(parsed, Some((30, Gender::Female, "TEST".to_string())));
-    }
-
     #[test]
     fn test_new_model_is_empty() {
         let ccm = CohortComponentModel::new();
@@ -7,6 +7,7 @@
 
 use super::types::*;
 use super::ccm::CohortComponentModel;
+use super::stochastic::{run_stochastic, StochasticSettings, StochasticYear};
 
 // ============================================================
 // TEST FIXTURES - Minimal datasets for testing
@@ -169,6 +170,19 @@ mod fixtures {
             }).collect(),
         }
     }
+    /// Interregional migration matrix with a single origin → destination flow
+    pub fn single_flow(origin: &str, destination: &str, age: u32, male: f64, female: f64) -> InterregionalMigrationTable {
+        InterregionalMigrationTable {
+            year: 2024,
+            flows: vec![InterregionalFlow {
+                origin_region_id: origin.to_string(),
+                destination_region_id: destination.to_string(),
+                age,
+                male,
+                female,
+            }],
+        }
+    }
 }
 
 // ============================================================
@@ -618,12 +632,14 @@ mod migration_tests {
         ccm.load_migration_table(migration);
 
         // When: Project one year
-        ccm.project_one_year(2024, &["TEST".to_string()]);
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
 
         // Then: Population should be 0, not negative
         assert!(ccm.total_population() >= 0.0);
         // All 10 emigrated (capped at available population)
         assert_eq!(ccm.get_count(26, Gender::Male, "TEST"), 0.0);
+        // The other 90 are reported as unmet
+        assert!((result.unmet_emigration - 90.0).abs() < 1e-9);
     }
 
     #[test]
@@ -758,3 +774,1494 @@ mod migration_tests {
     }
 }
 
+
+// ============================================================
+// INTERREGIONAL MIGRATION TESTS
+// ============================================================
+
+mod interregional_migration_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn two_regions() -> Vec<String> {
+        vec!["A".to_string(), "B".to_string()]
+    }
+
+    fn load_two_regions(ccm: &mut CohortComponentModel) {
+        for region in ["A", "B"] {
+            ccm.load_mortality_table(zero_mortality(region));
+            ccm.load_fertility_table(zero_fertility(region));
+        }
+    }
+
+    #[test]
+    fn test_flow_moves_people_between_regions() {
+        // Given: 100 males age 25 in A, 10% move to B
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 25, gender: Gender::Male, region_id: "A".to_string(), count: 100.0 },
+        ]);
+        load_two_regions(&mut ccm);
+        ccm.load_interregional_migration_table(single_flow("A", "B", 25, 0.1, 0.0));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &two_regions());
+
+        // Then: 10 movers arrive in B and age with the destination population
+        assert!((ccm.get_count(26, Gender::Male, "A") - 90.0).abs() < 1e-9);
+        assert!((ccm.get_count(26, Gender::Male, "B") - 10.0).abs() < 1e-9);
+        assert!((result.internal_migration - 10.0).abs() < 1e-9);
+        assert_eq!(result.net_migration, 0.0);
+        let (a, b) = (&result.regions[0], &result.regions[1]);
+        assert!((a.internal_out - 10.0).abs() < 1e-9 && a.internal_in == 0.0);
+        assert!((b.internal_in - 10.0).abs() < 1e-9 && b.internal_out == 0.0);
+    }
+
+    #[test]
+    fn test_internal_migration_sums_to_zero_nationally() {
+        // Given: Flows in both directions with different rates
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 30, gender: Gender::Male, region_id: "A".to_string(), count: 500.0 },
+            Cohort { age: 30, gender: Gender::Female, region_id: "A".to_string(), count: 400.0 },
+            Cohort { age: 30, gender: Gender::Male, region_id: "B".to_string(), count: 200.0 },
+            Cohort { age: 30, gender: Gender::Female, region_id: "B".to_string(), count: 300.0 },
+        ]);
+        load_two_regions(&mut ccm);
+        ccm.load_interregional_migration_table(InterregionalMigrationTable {
+            year: 2024,
+            flows: vec![
+                InterregionalFlow { origin_region_id: "A".to_string(), destination_region_id: "B".to_string(), age: 30, male: 0.05, female: 0.02 },
+                InterregionalFlow { origin_region_id: "B".to_string(), destination_region_id: "A".to_string(), age: 30, male: 0.1, female: 0.2 },
+            ],
+        });
+
+        let initial_pop = ccm.total_population();
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &two_regions());
+
+        // Then: National total unchanged, but regions changed
+        assert!((ccm.total_population() - initial_pop).abs() < 1e-9);
+        assert!(result.internal_migration > 0.0);
+        // A: 500 - 25 + 20 = 495 males
+        assert!((ccm.get_count(31, Gender::Male, "A") - 495.0).abs() < 1e-9);
+        // B: 300 - 60 + 8 = 248 females
+        assert!((ccm.get_count(31, Gender::Female, "B") - 248.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_out_rates_above_one_are_scaled() {
+        // Given: Out-migration rates from A summing to 1.5
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 40, gender: Gender::Female, region_id: "A".to_string(), count: 90.0 },
+        ]);
+        for region in ["A", "B", "C"] {
+            ccm.load_mortality_table(zero_mortality(region));
+            ccm.load_fertility_table(zero_fertility(region));
+        }
+        ccm.load_interregional_migration_table(InterregionalMigrationTable {
+            year: 2024,
+            flows: vec![
+                InterregionalFlow { origin_region_id: "A".to_string(), destination_region_id: "B".to_string(), age: 40, male: 0.0, female: 1.0 },
+                InterregionalFlow { origin_region_id: "A".to_string(), destination_region_id: "C".to_string(), age: 40, male: 0.0, female: 0.5 },
+            ],
+        });
+
+        // When: Project one year
+        ccm.project_one_year(2024, &["A".to_string(), "B".to_string(), "C".to_string()]);
+
+        // Then: Everyone leaves A, split 2:1 between B and C, nobody is created
+        assert_eq!(ccm.get_count(41, Gender::Female, "A"), 0.0);
+        assert!((ccm.get_count(41, Gender::Female, "B") - 60.0).abs() < 1e-9);
+        assert!((ccm.get_count(41, Gender::Female, "C") - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_flows_to_unprojected_regions_are_ignored() {
+        // Given: Flow to a region that is not part of the projection
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 25, gender: Gender::Male, region_id: "A".to_string(), count: 100.0 },
+        ]);
+        load_two_regions(&mut ccm);
+        ccm.load_interregional_migration_table(single_flow("A", "Z", 25, 0.5, 0.5));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &two_regions());
+
+        // Then: Nobody moves
+        assert_eq!(result.internal_migration, 0.0);
+        assert_eq!(ccm.get_count(26, Gender::Male, "A"), 100.0);
+    }
+
+    #[test]
+    fn test_movers_face_destination_mortality() {
+        // Given: Zero mortality in A, 10% mortality in B
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 25, gender: Gender::Male, region_id: "A".to_string(), count: 100.0 },
+        ]);
+        ccm.load_mortality_table(zero_mortality("A"));
+        ccm.load_mortality_table(MortalityTable {
+            region_id: "B".to_string(),
+            year: 2024,
+            rates: vec![MortalityRate { age: 25, male: 0.1, female: 0.1 }],
+        });
+        ccm.load_interregional_migration_table(single_flow("A", "B", 25, 0.5, 0.0));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &two_regions());
+
+        // Then: 50 movers, 5 of them die in B
+        assert!((result.deaths - 5.0).abs() < 1e-9);
+        assert!((ccm.get_count(26, Gender::Male, "B") - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_matrices_vary_by_year() {
+        // Given: 10% move from A to B in 2024, 30% in 2034
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 25, gender: Gender::Male, region_id: "A".to_string(), count: 100.0 },
+        ]);
+        load_two_regions(&mut ccm);
+        ccm.load_interregional_migration_table(single_flow("A", "B", 25, 0.1, 0.0));
+        let mut later = single_flow("A", "B", 25, 0.3, 0.0);
+        later.year = 2034;
+        ccm.load_interregional_migration_table(later);
+
+        // When: Project 2029, halfway between the anchors
+        let result = ccm.project_one_year(2029, &two_regions());
+
+        // Then: The interpolated rate of 20% applies
+        assert!((result.internal_migration - 20.0).abs() < 1e-9);
+        assert!((ccm.get_count(26, Gender::Male, "B") - 20.0).abs() < 1e-9);
+    }
+}
+
+// ============================================================
+// TIME-VARYING RATE TESTS
+// ============================================================
+
+mod time_varying_rate_tests {
+    use super::*;
+    use super::fixtures::*;
+    use crate::engine::Interpolation;
+
+    fn mortality_at(year: u32, rate: f64) -> MortalityTable {
+        MortalityTable {
+            region_id: "TEST".to_string(),
+            year,
+            rates: (0..=120).map(|age| MortalityRate { age, male: rate, female: rate }).collect(),
+        }
+    }
+
+    fn project_deaths(ccm: &mut CohortComponentModel, year: u32) -> f64 {
+        ccm.load_population(&[
+            Cohort { age: 50, gender: Gender::Male, region_id: "TEST".to_string(), count: 1000.0 },
+        ]);
+        ccm.project_one_year(year, &["TEST".to_string()]).deaths
+    }
+
+    #[test]
+    fn test_anchor_table_used_in_its_own_year() {
+        // Given: Anchors for 2024 and 2030, loaded out of order
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_mortality_table(mortality_at(2030, 0.04));
+        ccm.load_mortality_table(mortality_at(2024, 0.01));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+
+        // Then: Each anchor year uses its own table (the last loaded no longer wins)
+        assert!((project_deaths(&mut ccm, 2024) - 10.0).abs() < 1e-9);
+        assert!((project_deaths(&mut ccm, 2030) - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_linear_interpolation_between_anchor_years() {
+        // Given: Mortality declining from 4% in 2024 to 1% in 2030
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_mortality_table(mortality_at(2024, 0.04));
+        ccm.load_mortality_table(mortality_at(2030, 0.01));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+
+        // Then: 2026 is a third of the way: 4% - 1% = 3%
+        assert!((project_deaths(&mut ccm, 2026) - 30.0).abs() < 1e-9);
+        // After the last anchor the rate is held
+        assert!((project_deaths(&mut ccm, 2050) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_constant_interpolation_holds_previous_anchor() {
+        // Given: Same anchors, hold constant between them
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_mortality_table(mortality_at(2024, 0.04));
+        ccm.load_mortality_table(mortality_at(2030, 0.01));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+        ccm.set_interpolation(Interpolation::Constant);
+
+        // Then: 2029 still uses the 2024 table
+        assert!((project_deaths(&mut ccm, 2029) - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fertility_path_over_projection() {
+        // Given: Fertility at age 30 rising from 0.1 (2024) to 0.2 (2034)
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_mortality_table(zero_mortality("TEST"));
+        ccm.load_fertility_table(simple_fertility("TEST"));
+        ccm.load_fertility_table(FertilityTable {
+            year: 2034,
+            rates: vec![FertilityRate { age: 30, rate: 0.2 }],
+            ..simple_fertility("TEST")
+        });
+
+        // When: Project 2029 with 100 women aged 30
+        ccm.load_population(&[
+            Cohort { age: 30, gender: Gender::Female, region_id: "TEST".to_string(), count: 100.0 },
+        ]);
+        let result = ccm.project_one_year(2029, &["TEST".to_string()]);
+
+        // Then: Halfway rate 0.15 gives 15 births
+        assert!((result.births - 15.0).abs() < 1e-9);
+    }
+}
+
+// ============================================================
+// MIGRATION TIMING TESTS
+// ============================================================
+
+mod migration_timing_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    /// 10% mortality at age 30, 10% fertility at age 30, 100 immigrants of each sex at 30
+    fn project_with_timing(timing: MigrationTiming) -> (ProjectionYear, CohortComponentModel) {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 30, gender: Gender::Female, region_id: "TEST".to_string(), count: 1000.0 },
+        ]);
+        ccm.load_mortality_table(MortalityTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: (0..=120).map(|age| MortalityRate {
+                age,
+                male: if age == 30 { 0.1 } else { 0.0 },
+                female: if age == 30 { 0.1 } else { 0.0 },
+            }).collect(),
+        });
+        ccm.load_fertility_table(simple_fertility("TEST"));
+        ccm.load_migration_table(MigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![MigrationRate { age: 30, male: 100.0, female: 100.0 }],
+        });
+        ccm.set_migration_timing(timing);
+
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+        (result, ccm)
+    }
+
+    #[test]
+    fn test_start_timing_migrants_die_and_give_birth() {
+        let (result, ccm) = project_with_timing(MigrationTiming::Start);
+
+        // Deaths: (1000 + 100 women + 100 men) × 10% = 120
+        assert!((result.deaths - 120.0).abs() < 1e-9);
+        // Births: (1000 + 100) women × 10% = 110
+        assert!((result.births - 110.0).abs() < 1e-9);
+        assert!((ccm.get_count(31, Gender::Female, "TEST") - 990.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mid_period_timing_halves_migrant_exposure() {
+        let (result, ccm) = project_with_timing(MigrationTiming::MidPeriod);
+
+        // Deaths: (1000 + 50 + 50) × 10% = 110
+        assert!((result.deaths - 110.0).abs() < 1e-9);
+        // Births: (1000 + 50 + 50 / 2) × 10% = 107.5
+        assert!((result.births - 107.5).abs() < 1e-9);
+        // Women: (1050 × 0.9) + 50 = 995
+        assert!((ccm.get_count(31, Gender::Female, "TEST") - 995.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_end_timing_migrants_do_not_die_and_count_half_for_births() {
+        let (result, ccm) = project_with_timing(MigrationTiming::End);
+
+        // Only the resident women die; migrant women give birth for half a year
+        assert!((result.deaths - 100.0).abs() < 1e-9);
+        assert!((result.births - 105.0).abs() < 1e-9);
+        // Women: 900 survivors + 100 arriving at the end
+        assert!((ccm.get_count(31, Gender::Female, "TEST") - 1000.0).abs() < 1e-9);
+        assert!((ccm.get_count(31, Gender::Male, "TEST") - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_net_migration_is_same_for_all_timings() {
+        for timing in [MigrationTiming::Start, MigrationTiming::MidPeriod, MigrationTiming::End] {
+            let (result, _) = project_with_timing(timing);
+            assert!((result.net_migration - 200.0).abs() < 1e-9, "{:?}", timing);
+        }
+    }
+
+    #[test]
+    fn test_births_by_timing_follow_migrant_exposure() {
+        // Migrant women count fully from the start, half from the end
+        let expected = [
+            (MigrationTiming::Start, 110.0),
+            (MigrationTiming::MidPeriod, 107.5),
+            (MigrationTiming::End, 105.0),
+        ];
+        for (timing, births) in expected {
+            let (result, _) = project_with_timing(timing);
+            assert!((result.births - births).abs() < 1e-9, "{:?}", timing);
+        }
+    }
+
+    #[test]
+    fn test_later_arrival_means_fewer_deaths_and_births() {
+        let (start, _) = project_with_timing(MigrationTiming::Start);
+        let (mid, _) = project_with_timing(MigrationTiming::MidPeriod);
+        let (end, _) = project_with_timing(MigrationTiming::End);
+
+        assert!(start.deaths > mid.deaths && mid.deaths > end.deaths);
+        assert!(start.births > mid.births && mid.births > end.births);
+    }
+
+    #[test]
+    fn test_end_timing_emigration_capped_at_survivors() {
+        // Given: 10 men, 50% mortality, 100 emigrants requested at the end
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 25, gender: Gender::Male, region_id: "TEST".to_string(), count: 10.0 },
+        ]);
+        ccm.load_mortality_table(MortalityTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![MortalityRate { age: 25, male: 0.5, female: 0.5 }],
+        });
+        ccm.load_fertility_table(zero_fertility("TEST"));
+        ccm.load_migration_table(MigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![MigrationRate { age: 25, male: -100.0, female: 0.0 }],
+        });
+        ccm.set_migration_timing(MigrationTiming::End);
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: 5 die, the 5 survivors emigrate
+        assert!((result.deaths - 5.0).abs() < 1e-9);
+        assert!((result.net_migration + 5.0).abs() < 1e-9);
+        assert_eq!(ccm.total_population(), 0.0);
+    }
+}
+
+// ============================================================
+// OPEN AGE GROUP TESTS
+// ============================================================
+
+mod open_age_group_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn mortality_to(last_age: u32, rate: impl Fn(u32) -> f64) -> MortalityTable {
+        MortalityTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: (0..=last_age).map(|age| MortalityRate { age, male: rate(age), female: rate(age) }).collect(),
+        }
+    }
+
+    #[test]
+    fn test_population_above_open_age_is_folded() {
+        // Given: Open age group 85+ and people aged 85 and 97
+        let mut ccm = CohortComponentModel::new();
+        ccm.set_open_age_group(85);
+        ccm.load_population(&[
+            Cohort { age: 85, gender: Gender::Male, region_id: "TEST".to_string(), count: 10.0 },
+            Cohort { age: 97, gender: Gender::Male, region_id: "TEST".to_string(), count: 5.0 },
+        ]);
+
+        // Then: Both are counted in 85+
+        assert_eq!(ccm.get_count(85, Gender::Male, "TEST"), 15.0);
+        assert_eq!(ccm.get_count(97, Gender::Male, "TEST"), 0.0);
+    }
+
+    #[test]
+    fn test_setting_open_age_folds_loaded_population() {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 110, gender: Gender::Female, region_id: "TEST".to_string(), count: 4.0 },
+        ]);
+
+        ccm.set_open_age_group(100);
+
+        assert_eq!(ccm.get_count(100, Gender::Female, "TEST"), 4.0);
+        assert_eq!(ccm.total_population(), 4.0);
+    }
+
+    #[test]
+    fn test_ages_above_table_keep_last_rate() {
+        // Given: Rates only up to age 85 (20% at 85), open age group 100+
+        let mut ccm = CohortComponentModel::new();
+        ccm.set_open_age_group(100);
+        ccm.load_population(&[
+            Cohort { age: 90, gender: Gender::Female, region_id: "TEST".to_string(), count: 100.0 },
+        ]);
+        ccm.load_mortality_table(mortality_to(85, |age| if age == 85 { 0.2 } else { 0.01 }));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+
+        // When: Project one year
+        ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: 90-year-olds face 20%, not 100%
+        assert!((ccm.get_count(91, Gender::Female, "TEST") - 80.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_open_group_matching_table_end_uses_last_rate() {
+        // Given: Default 120+ group and 50% mortality at 120
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 120, gender: Gender::Male, region_id: "TEST".to_string(), count: 100.0 },
+        ]);
+        ccm.load_mortality_table(mortality_to(120, |_| 0.5));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+
+        // When: Project one year
+        ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: T(121)/T(120) reduces to 1 - q
+        assert!((ccm.get_count(120, Gender::Male, "TEST") - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_open_group_survival_uses_person_years_above_open_age() {
+        // Given: 85+ group, table to 87 with rising mortality
+        let mut ccm = CohortComponentModel::new();
+        ccm.set_open_age_group(85);
+        ccm.load_population(&[
+            Cohort { age: 85, gender: Gender::Female, region_id: "TEST".to_string(), count: 1000.0 },
+        ]);
+        ccm.load_mortality_table(mortality_to(87, |age| match age {
+            85 => 0.1,
+            86 => 0.2,
+            87 => 0.5,
+            _ => 0.0,
+        }));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Survival is T(86)/T(85), below 1 - q85 but above 1 - q87
+        let t86 = 0.9 * 0.9 + 0.72 * 0.75 / 0.5;
+        let t85 = 0.95 + t86;
+        let expected = 1000.0 * t86 / t85;
+        let survivors = ccm.get_count(85, Gender::Female, "TEST");
+        assert!((survivors - expected).abs() < 1e-9);
+        assert!(survivors < 900.0 && survivors > 500.0);
+        assert!((result.deaths - (1000.0 - expected)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_open_age_group_is_not_below_fertile_ages() {
+        let mut ccm = CohortComponentModel::new();
+        ccm.set_open_age_group(30);
+        ccm.load_population(&[
+            Cohort { age: 49, gender: Gender::Female, region_id: "TEST".to_string(), count: 1.0 },
+        ]);
+
+        assert_eq!(ccm.get_count(49, Gender::Female, "TEST"), 1.0);
+    }
+}
+
+// ============================================================
+// FERTILITY EXPOSURE TESTS
+// ============================================================
+
+mod fertility_exposure_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn project_births(women: &[(u32, f64)], exposure: FertilityExposure) -> f64 {
+        let mut ccm = CohortComponentModel::new();
+        let cohorts: Vec<Cohort> = women.iter().map(|&(age, count)| Cohort {
+            age,
+            gender: Gender::Female,
+            region_id: "TEST".to_string(),
+            count,
+        }).collect();
+        ccm.load_population(&cohorts);
+        ccm.load_mortality_table(zero_mortality("TEST"));
+        ccm.load_fertility_table(simple_fertility("TEST")); // 10% at age 30 only
+        ccm.set_fertility_exposure(exposure);
+        ccm.project_one_year(2024, &["TEST".to_string()]).births
+    }
+
+    #[test]
+    fn test_start_exposure_uses_start_of_year_women() {
+        // Women turning 30 during the year contribute nothing
+        assert_eq!(project_births(&[(29, 1000.0)], FertilityExposure::Start), 0.0);
+        assert!((project_births(&[(30, 1000.0)], FertilityExposure::Start) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_average_exposure_splits_cohort_between_ages() {
+        // Half a year of exposure at age 30 for each cohort
+        assert!((project_births(&[(29, 1000.0)], FertilityExposure::Average) - 50.0).abs() < 1e-9);
+        assert!((project_births(&[(30, 1000.0)], FertilityExposure::Average) - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_average_matches_start_for_stationary_population() {
+        // Equal cohorts around age 30 and no deaths: exposure is unchanged
+        let women = [(29, 500.0), (30, 500.0)];
+        let start = project_births(&women, FertilityExposure::Start);
+        let average = project_births(&women, FertilityExposure::Average);
+        assert!((start - average).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_average_exposure_accounts_for_mortality() {
+        // Given: 1000 women each at 29 and 30, 20% mortality at 29
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 29, gender: Gender::Female, region_id: "TEST".to_string(), count: 1000.0 },
+            Cohort { age: 30, gender: Gender::Female, region_id: "TEST".to_string(), count: 1000.0 },
+        ]);
+        ccm.load_mortality_table(MortalityTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: (0..=120).map(|age| MortalityRate {
+                age,
+                male: 0.0,
+                female: if age == 29 { 0.2 } else { 0.0 },
+            }).collect(),
+        });
+        ccm.load_fertility_table(simple_fertility("TEST"));
+        ccm.set_fertility_exposure(FertilityExposure::Average);
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Women aged 30: 1000 at start, 800 at end → mean 900 × 10%
+        assert!((result.births - 90.0).abs() < 1e-9);
+    }
+}
+
+// ============================================================
+// INFANT MORTALITY TESTS
+// ============================================================
+
+mod infant_mortality_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    /// 1000 women aged 30 with 10% fertility, infant mortality q0 and no other deaths
+    fn model_with_infant_mortality(q0: f64) -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 30, gender: Gender::Female, region_id: "TEST".to_string(), count: 1000.0 },
+        ]);
+        ccm.load_mortality_table(MortalityTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: (0..=120).map(|age| {
+                let rate = if age == 0 { q0 } else { 0.0 };
+                MortalityRate { age, male: rate, female: rate }
+            }).collect(),
+        });
+        ccm.load_fertility_table(FertilityTable {
+            sex_ratio_at_birth: 100.0,
+            ..simple_fertility("TEST")
+        });
+        ccm
+    }
+
+    fn newborns(ccm: &CohortComponentModel) -> f64 {
+        ccm.get_count(0, Gender::Male, "TEST") + ccm.get_count(0, Gender::Female, "TEST")
+    }
+
+    #[test]
+    fn test_newborns_survive_with_separation_factor() {
+        // Given: q0 = 2%, 75% of infant deaths in the birth year
+        let mut ccm = model_with_infant_mortality(0.02);
+        ccm.set_infant_separation_factor(0.75);
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: 100 births, 100 × 0.75 × 0.02 = 1.5 infant deaths
+        assert!((result.births - 100.0).abs() < 1e-9);
+        assert!((result.deaths - 1.5).abs() < 1e-9);
+        assert!((newborns(&ccm) - 98.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_default_separation_factor_reduces_newborns() {
+        let mut ccm = model_with_infant_mortality(0.02);
+
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        assert!(newborns(&ccm) < result.births);
+        assert!((result.natural_change - (result.births - result.deaths)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_birth_cohort_loses_exactly_q0_by_age_one() {
+        // Given: q0 = 2%
+        let mut ccm = model_with_infant_mortality(0.02);
+        ccm.set_infant_separation_factor(0.6);
+
+        // When: Births in 2024 reach age 1 in 2025
+        ccm.project_one_year(2024, &["TEST".to_string()]);
+        ccm.project_one_year(2025, &["TEST".to_string()]);
+
+        // Then: Infant deaths across both years equal q0 × births
+        let age_one = ccm.get_count(1, Gender::Male, "TEST") + ccm.get_count(1, Gender::Female, "TEST");
+        assert!((age_one - 98.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_separation_factor_zero_keeps_all_newborns() {
+        let mut ccm = model_with_infant_mortality(0.02);
+        ccm.set_infant_separation_factor(0.0);
+
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        assert_eq!(result.deaths, 0.0);
+        assert!((newborns(&ccm) - 100.0).abs() < 1e-9);
+    }
+}
+
+// ============================================================
+// LESLIE MATRIX TESTS
+// ============================================================
+
+mod leslie_matrix_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    /// Women aged 0-60, 1000 each
+    fn women_model(exposure: FertilityExposure) -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&(0..=60).map(|age| Cohort {
+            age,
+            gender: Gender::Female,
+            region_id: "TEST".to_string(),
+            count: 1000.0,
+        }).collect::<Vec<_>>());
+        ccm.load_mortality_table(simple_mortality("TEST"));
+        ccm.load_fertility_table(high_fertility("TEST"));
+        ccm.set_fertility_exposure(exposure);
+        ccm
+    }
+
+    fn women_by_age(ccm: &CohortComponentModel) -> Vec<f64> {
+        (0..=120).map(|age| ccm.get_count(age, Gender::Female, "TEST")).collect()
+    }
+
+    #[test]
+    fn test_matrix_reproduces_projection_step() {
+        for exposure in [FertilityExposure::Start, FertilityExposure::Average] {
+            // Given: Female matrix for the loaded rates
+            let mut ccm = women_model(exposure);
+            let matrix = ccm.leslie_matrix("TEST", 2024, Gender::Female);
+            let expected = matrix.apply(&women_by_age(&ccm));
+
+            // When: Project one year without migration
+            ccm.project_one_year(2024, &["TEST".to_string()]);
+
+            // Then: Women by age match the matrix product
+            for (age, (actual, expected)) in women_by_age(&ccm).iter().zip(&expected).enumerate() {
+                assert!((actual - expected).abs() < 1e-9, "age {}: {} vs {}", age, actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stable_population_grows_by_lambda() {
+        // Given: Women in the stable age distribution
+        let mut ccm = women_model(FertilityExposure::Start);
+        let stable = ccm.leslie_matrix("TEST", 2024, Gender::Female).stable_population().unwrap();
+        ccm.load_population(&stable.age_distribution.iter().enumerate().map(|(age, share)| Cohort {
+            age: age as u32,
+            gender: Gender::Female,
+            region_id: "TEST".to_string(),
+            count: share * 1_000_000.0,
+        }).collect::<Vec<_>>());
+
+        // When: Project one year
+        ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Women grow by λ, keeping the same shares
+        let women = women_by_age(&ccm);
+        let total: f64 = women.iter().sum();
+        assert!((total / 1_000_000.0 - stable.lambda).abs() < 1e-9);
+        assert!((women[30] / total - stable.age_distribution[30]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_below_replacement_has_negative_intrinsic_growth() {
+        // 35 years × 5% with half of births female, less deaths: R0 below 0.875
+        let ccm = women_model(FertilityExposure::Start);
+        let stable = ccm.leslie_matrix("TEST", 2024, Gender::Female).stable_population().unwrap();
+
+        assert!(stable.net_reproduction_rate > 0.8 && stable.net_reproduction_rate < 0.875);
+        assert!(stable.intrinsic_growth_rate < 0.0);
+        assert!((stable.reproductive_values[0] - 1.0).abs() < 1e-12);
+        assert_eq!(stable.reproductive_values[60], 0.0);
+    }
+
+    #[test]
+    fn test_male_matrix_uses_male_share_of_births() {
+        let ccm = women_model(FertilityExposure::Start);
+        let female = ccm.leslie_matrix("TEST", 2024, Gender::Female);
+        let male = ccm.leslie_matrix("TEST", 2024, Gender::Male);
+
+        // Sex ratio 100: equal shares, survival differs by sex
+        assert_eq!(male.size(), 121);
+        assert!(male.fertility[30] > 0.0);
+        assert!(male.survival[30] < female.survival[30]);
+    }
+}
+
+// ============================================================
+// STOCHASTIC PROJECTION TESTS
+// ============================================================
+
+mod stochastic_tests {
+    use super::*;
+    use super::fixtures::*;
+    use super::super::stochastic::{ErrorModel, ErrorProcess};
+
+    fn model() -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&(0..=80).flat_map(|age| [Gender::Male, Gender::Female].map(|gender| Cohort {
+            age,
+            gender,
+            region_id: "TEST".to_string(),
+            count: 1000.0,
+        })).collect::<Vec<_>>());
+        ccm.load_mortality_table(simple_mortality("TEST"));
+        ccm.load_fertility_table(high_fertility("TEST"));
+        ccm.load_migration_table(simple_immigration("TEST"));
+        ccm
+    }
+
+    fn settings(simulations: usize, sd: f64) -> StochasticSettings {
+        let error = ErrorModel { process: ErrorProcess::RandomWalk, sd };
+        StochasticSettings {
+            simulations,
+            seed: 42,
+            tfr: ErrorModel { sd: sd / 10.0, ..error },
+            e0: error,
+            migration: ErrorModel { process: ErrorProcess::Autoregressive { phi: 0.5 }, sd: sd / 5.0 },
+        }
+    }
+
+    fn regions() -> Vec<String> {
+        vec!["TEST".to_string()]
+    }
+
+    #[test]
+    fn test_zero_errors_reproduce_deterministic_projection() {
+        // Given: Error models without variance
+        let base = model();
+        let result = run_stochastic(&base, &regions(), 2024, 2028, &settings(3, 0.0));
+
+        // When: Projecting the deterministic model
+        let mut deterministic = base.clone();
+        let years: Vec<ProjectionYear> = (2024..=2028)
+            .map(|year| deterministic.project_one_year(year, &regions()))
+            .collect();
+
+        // Then: Every quantile equals the deterministic path
+        for (stochastic, year) in result.iter().zip(&years) {
+            assert!((stochastic.total_population.lower95 - year.total_population).abs() < 1e-6);
+            assert!((stochastic.total_population.upper95 - year.total_population).abs() < 1e-6);
+            assert!((stochastic.births.median - year.births).abs() < 1e-6);
+            assert!((stochastic.tfr.median - 1.75).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_result() {
+        let base = model();
+
+        let first = run_stochastic(&base, &regions(), 2024, 2030, &settings(50, 1.0));
+        let second = run_stochastic(&base, &regions(), 2024, 2030, &settings(50, 1.0));
+
+        assert_eq!(first.last().unwrap().total_population, second.last().unwrap().total_population);
+    }
+
+    #[test]
+    fn test_different_seeds_give_different_medians() {
+        // Given: Two runs whose seeds differ by one
+        let base = model();
+        let mut other = settings(50, 1.0);
+        other.seed += 1;
+
+        // When: Projecting both
+        let first = run_stochastic(&base, &regions(), 2024, 2030, &settings(50, 1.0));
+        let second = run_stochastic(&base, &regions(), 2024, 2030, &other);
+
+        // Then: The simulated medians differ
+        let median = |result: &[StochasticYear]| result.last().unwrap().total_population.median;
+        assert!((median(&first) - median(&second)).abs() > 1e-6);
+    }
+
+    #[test]
+    fn test_intervals_are_ordered_and_widen_over_time() {
+        let result = run_stochastic(&model(), &regions(), 2024, 2043, &settings(100, 1.0));
+
+        for year in &result {
+            let q = year.total_population;
+            assert!(q.lower95 <= q.lower80 && q.lower80 <= q.median);
+            assert!(q.median <= q.upper80 && q.upper80 <= q.upper95);
+        }
+        let width = |year: &StochasticYear| year.e0_female.upper80 - year.e0_female.lower80;
+        assert!(width(&result[19]) > width(&result[0]));
+        assert!(result[0].e0_female.lower95 < result[0].e0_female.upper95);
+    }
+
+    #[test]
+    fn test_base_model_is_not_modified() {
+        let base = model();
+        let before = base.total_population();
+
+        run_stochastic(&base, &regions(), 2024, 2026, &settings(5, 1.0));
+
+        assert_eq!(base.total_population(), before);
+    }
+}
+
+// ============================================================
+// EMIGRATION RATE TESTS
+// ============================================================
+
+mod emigration_rate_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn emigration_rates(region: &str, rate: f64) -> EmigrationTable {
+        EmigrationTable {
+            region_id: region.to_string(),
+            year: 2024,
+            rates: vec![EmigrationRate { age: 25, male: rate, female: rate / 2.0 }],
+        }
+    }
+
+    fn model(count: f64) -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 25, gender: Gender::Male, region_id: "TEST".to_string(), count },
+            Cohort { age: 25, gender: Gender::Female, region_id: "TEST".to_string(), count },
+        ]);
+        ccm.load_mortality_table(zero_mortality("TEST"));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+        ccm
+    }
+
+    #[test]
+    fn test_emigration_is_share_of_population() {
+        // Given: 10% of men and 5% of women aged 25 emigrate
+        let mut ccm = model(200.0);
+        ccm.load_emigration_table(emigration_rates("TEST", 0.1));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: 20 men and 10 women leave
+        assert!((ccm.get_count(26, Gender::Male, "TEST") - 180.0).abs() < 1e-9);
+        assert!((ccm.get_count(26, Gender::Female, "TEST") - 190.0).abs() < 1e-9);
+        assert!((result.net_migration + 30.0).abs() < 1e-9);
+        assert_eq!(result.unmet_emigration, 0.0);
+    }
+
+    #[test]
+    fn test_emigration_shrinks_with_region() {
+        // Given: The same rate in a region ten times smaller
+        let mut large = model(1000.0);
+        let mut small = model(100.0);
+        large.load_emigration_table(emigration_rates("TEST", 0.2));
+        small.load_emigration_table(emigration_rates("TEST", 0.2));
+
+        // When: Project one year
+        let large_result = large.project_one_year(2024, &["TEST".to_string()]);
+        let small_result = small.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Emigration scales with the population, nothing is unmet
+        assert!((large_result.net_migration - 10.0 * small_result.net_migration).abs() < 1e-9);
+        assert_eq!(small_result.unmet_emigration, 0.0);
+    }
+
+    #[test]
+    fn test_immigration_counts_combine_with_emigration_rates() {
+        // Given: 100 immigrants at 25 and a 10% emigration rate, split over the year
+        let mut ccm = model(100.0);
+        ccm.load_migration_table(simple_immigration("TEST"));
+        ccm.load_emigration_table(emigration_rates("TEST", 0.1));
+        ccm.set_migration_timing(MigrationTiming::MidPeriod);
+
+        // When: Project one year
+        ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Men: (100 + 25) × 0.95 = 118.75, + 25 = 143.75, then × 0.95 again
+        let expected = ((100.0 + 25.0) * 0.95 + 25.0) * 0.95;
+        assert!((ccm.get_count(26, Gender::Male, "TEST") - expected).abs() < 1e-9);
+    }
+}
+
+// ============================================================
+// LIFE TABLE TESTS
+// ============================================================
+
+mod life_table_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn constant_mortality(q: f64) -> MortalityTable {
+        MortalityTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: (0..=100).map(|age| MortalityRate { age, male: q, female: q / 2.0 }).collect(),
+        }
+    }
+
+    #[test]
+    fn test_life_table_from_rates_in_effect() {
+        // Given: Constant death probabilities of 10% (men) and 5% (women)
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_mortality_table(constant_mortality(0.1));
+
+        // When: Building life tables for the year
+        let male = ccm.life_table("TEST", 2024, Gender::Male).unwrap();
+        let female = ccm.life_table("TEST", 2024, Gender::Female).unwrap();
+
+        // Then: e_x = (1 - q/2) / q above infancy, up to the open age group;
+        // infant deaths at a0 give e0 = e_1 + q·(a0 - 1/2)
+        assert_eq!(male.lx.len(), 121);
+        assert!((male.life_expectancy(1) - 9.5).abs() < 1e-9);
+        assert!((male.life_expectancy(65) - 9.5).abs() < 1e-9);
+        assert!((male.life_expectancy(0) - (9.5 + 0.1 * (0.33 - 0.5))).abs() < 1e-9);
+        assert!((female.life_expectancy(0) - (19.5 + 0.05 * (0.2 - 0.5))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_life_table_follows_rate_adjustment() {
+        // Given: Male mortality doubled by an adjustment
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_mortality_table(constant_mortality(0.1));
+        ccm.set_rate_adjustment("TEST", RateAdjustment { male_mortality: 2.0, ..Default::default() });
+
+        // When: Building the male life table
+        let male = ccm.life_table("TEST", 2024, Gender::Male).unwrap();
+
+        // Then: q = 0.2 gives e_1 = 0.9 / 0.2, and e0 with a0 = 0.33
+        assert!((male.life_expectancy(1) - 4.5).abs() < 1e-9);
+        assert!((male.life_expectancy(0) - (4.5 + 0.2 * (0.33 - 0.5))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_life_table_without_mortality() {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_fertility_table(zero_fertility("TEST"));
+
+        assert!(ccm.life_table("TEST", 2024, Gender::Female).is_none());
+    }
+}
+
+// ============================================================
+// REGION INDICATOR TESTS
+// ============================================================
+
+mod region_indicator_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    #[test]
+    fn test_components_are_reported_by_region() {
+        // Given: Two regions, births only in A, immigration only in B
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            minimal_population("A"),
+            minimal_population("B"),
+        ].concat());
+        ccm.load_mortality_table(simple_mortality("A"));
+        ccm.load_mortality_table(simple_mortality("B"));
+        ccm.load_fertility_table(simple_fertility("A"));
+        ccm.load_fertility_table(zero_fertility("B"));
+        ccm.load_migration_table(simple_immigration("B"));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["A".to_string(), "B".to_string()]);
+
+        // Then: Region components add up to the national totals
+        let [a, b] = [&result.regions[0], &result.regions[1]];
+        assert_eq!((a.region_id.as_str(), b.region_id.as_str()), ("A", "B"));
+        assert!((a.births + b.births - result.births).abs() < 1e-9);
+        assert!((a.deaths + b.deaths - result.deaths).abs() < 1e-9);
+        assert!((a.total_population + b.total_population - result.total_population).abs() < 1e-9);
+        assert_eq!(b.births, 0.0);
+        assert!((b.net_migration - 100.0).abs() < 1e-9);
+        assert!((a.indicators.tfr - 0.1).abs() < 1e-12);
+        assert_eq!(b.indicators.crude_birth_rate, 0.0);
+    }
+
+    #[test]
+    fn test_structure_indicators_use_end_of_year_population() {
+        // Given: 100 persons aged 64 who do not die
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&[
+            Cohort { age: 64, gender: Gender::Female, region_id: "TEST".to_string(), count: 100.0 },
+        ]);
+        ccm.load_mortality_table(zero_mortality("TEST"));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Everyone is 65 at the end of the year
+        let indicators = result.regions[0].indicators;
+        assert_eq!(indicators.share_65_plus, 100.0);
+        assert!((indicators.mean_age - 65.5).abs() < 1e-9);
+        assert_eq!(indicators.old_age_dependency_ratio, 0.0);
+    }
+}
+
+// ============================================================
+// COMPONENT DETAIL TESTS
+// ============================================================
+
+mod component_detail_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn model() -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&minimal_population("TEST"));
+        ccm.load_mortality_table(simple_mortality("TEST"));
+        ccm.load_fertility_table(simple_fertility("TEST"));
+        ccm.load_migration_table(simple_immigration("TEST"));
+        ccm
+    }
+
+    #[test]
+    fn test_detail_adds_up_to_year_totals() {
+        // Given: Mortality, fertility and immigration with detail enabled
+        let mut ccm = model();
+        ccm.set_component_detail(true);
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Detail tables sum to the scalar components
+        let detail = result.regions[0].components.as_ref().unwrap();
+        let deaths: f64 = detail.deaths.iter().map(|row| row.male + row.female).sum::<f64>()
+            + detail.newborn_deaths_male + detail.newborn_deaths_female;
+        let births: f64 = detail.births_by_mother_age.iter().map(|row| row.births).sum();
+        let migration: f64 = detail.migration.iter().map(|row| row.male + row.female).sum();
+        assert!((deaths - result.deaths).abs() < 1e-9);
+        assert!((births - result.births).abs() < 1e-9);
+        assert!((migration - result.net_migration).abs() < 1e-9);
+        assert_eq!(detail.births_by_mother_age.iter().find(|row| row.age == 30).unwrap().births, births);
+        assert!(detail.deaths[99].female > 0.0);
+    }
+
+    #[test]
+    fn test_detail_is_off_by_default() {
+        let mut ccm = model();
+
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        assert!(result.regions[0].components.is_none());
+    }
+}
+
+// ============================================================
+// AUDIT TESTS
+// ============================================================
+
+mod audit_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn model(regions: &[&str]) -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        for region in regions {
+            ccm.load_population(&minimal_population(region));
+            ccm.load_mortality_table(simple_mortality(region));
+            ccm.load_fertility_table(simple_fertility(region));
+        }
+        ccm.set_audit(true);
+        ccm
+    }
+
+    #[test]
+    fn test_balance_holds_across_regions() {
+        // Given: Two regions with all components, interregional moves and
+        // an open age group reached by the 99-year-olds
+        let mut ccm = model(&["A", "B"]);
+        ccm.load_migration_table(simple_immigration("A"));
+        ccm.load_interregional_migration_table(single_flow("A", "B", 30, 0.1, 0.0));
+        ccm.set_open_age_group(100);
+        let regions = vec!["A".to_string(), "B".to_string()];
+
+        // When: Project two years
+        for year in 2024..=2025 {
+            let result = ccm.project_one_year(year, &regions);
+
+            // Then: Every region and cohort balances
+            for region in &result.regions {
+                let audit = region.audit.unwrap();
+                assert!(audit.discrepancy.abs() < 1e-9, "{} {}: {}", region.region_id, year, audit.discrepancy);
+                assert!(audit.max_cohort_discrepancy < 1e-9);
+                assert!(audit.open_age_loss.abs() < 1e-9);
+                assert_eq!(audit.clamped_rates, 0);
+            }
+            let moves: f64 = result.regions.iter().map(|region| region.audit.unwrap().internal_migration).sum();
+            assert!(moves.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_clamping_is_reported() {
+        // Given: More emigrants at 30 than men present, and an emigration
+        // rate above one at age 1
+        let mut ccm = model(&["TEST"]);
+        ccm.load_migration_table(MigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![MigrationRate { age: 30, male: -150.0, female: 0.0 }],
+        });
+        ccm.load_emigration_table(EmigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![EmigrationRate { age: 1, male: 1.5, female: 0.0 }],
+        });
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: 50 emigrants are unmet, one rate is clamped, the year still balances
+        let audit = result.regions[0].audit.unwrap();
+        assert!((audit.clamped_emigration - 50.0).abs() < 1e-9);
+        assert_eq!(audit.clamped_rates, 1);
+        assert!(audit.discrepancy.abs() < 1e-9);
+        assert_eq!(ccm.get_count(2, Gender::Male, "TEST"), 0.0);
+    }
+
+    #[test]
+    fn test_cohort_gaps_are_flagged() {
+        // Given: A negative male cohort at 30 and a negative fertility rate at 25
+        let mut ccm = model(&["TEST"]);
+        let mut population = minimal_population("TEST");
+        population.push(Cohort { age: 30, gender: Gender::Male, region_id: "TEST".to_string(), count: -110.0 });
+        ccm.load_population(&population);
+        let mut fertility = simple_fertility("TEST");
+        fertility.rates.push(FertilityRate { age: 25, rate: -0.1 });
+        ccm.load_fertility_table(fertility);
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: The zeroed cohort misses its balance at 31 and both clamps are counted
+        let audit = result.regions[0].audit.unwrap();
+        assert!((audit.clamped_population - 10.0).abs() < 1e-9);
+        assert!((audit.max_cohort_discrepancy - 10.0).abs() < 1e-9);
+        assert!((audit.discrepancy - 10.0).abs() < 1e-9);
+        assert_eq!(audit.clamped_rates, 1);
+    }
+
+    #[test]
+    fn test_audit_is_off_by_default() {
+        let mut ccm = model(&["TEST"]);
+        ccm.set_audit(false);
+
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        assert!(result.regions[0].audit.is_none());
+    }
+}
+
+// ============================================================
+// BACKCAST TESTS
+// ============================================================
+
+mod backcast_tests {
+    use super::*;
+    use super::fixtures::*;
+
+    fn model() -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&minimal_population("TEST"));
+        ccm.load_mortality_table(simple_mortality("TEST"));
+        ccm.load_fertility_table(simple_fertility("TEST"));
+        ccm.load_migration_table(simple_immigration("TEST"));
+        ccm.load_emigration_table(EmigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![EmigrationRate { age: 30, male: 0.1, female: 0.05 }],
+        });
+        ccm.set_migration_timing(MigrationTiming::MidPeriod);
+        ccm
+    }
+
+    #[test]
+    fn test_backcast_reverses_projection() {
+        // Given: A population projected one year ahead
+        let mut ccm = model();
+        let base = ccm.get_cohorts();
+        let projected = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // When: Carry it back over the same year
+        let backcast = ccm.backcast_one_year(2024, &["TEST".to_string()]);
+
+        // Then: The base population and its components are recovered
+        for cohort in &base {
+            let estimated = ccm.get_count(cohort.age, cohort.gender, "TEST");
+            assert!((estimated - cohort.count).abs() < 1e-9, "age {}: {}", cohort.age, estimated);
+        }
+        assert!(ccm.get_count(25, Gender::Male, "TEST").abs() < 1e-9);
+        assert!((backcast.total_population - 700.0).abs() < 1e-9);
+        assert!((backcast.births - projected.births).abs() < 1e-9);
+        assert!((backcast.deaths - projected.deaths).abs() < 1e-9);
+        assert!((backcast.net_migration - projected.net_migration).abs() < 1e-9);
+        assert!(backcast.newborn_discrepancy.abs() < 1e-9);
+        assert!(backcast.clamped_population < 1e-9);
+    }
+
+    #[test]
+    fn test_backcast_flags_inconsistent_base_data() {
+        // Given: Children aged 0 without any births, and more immigrants
+        // at 26 than persons present
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&minimal_population("TEST"));
+        ccm.load_mortality_table(zero_mortality("TEST"));
+        ccm.load_fertility_table(zero_fertility("TEST"));
+        ccm.load_migration_table(simple_immigration("TEST"));
+
+        // When: Carry the population back one year
+        let backcast = ccm.backcast_one_year(2023, &["TEST".to_string()]);
+
+        // Then: The 200 children and the 100 missing persons at 25 are reported
+        assert!((backcast.newborn_discrepancy - 200.0).abs() < 1e-9);
+        assert!((backcast.clamped_population - 100.0).abs() < 1e-9);
+        assert_eq!(ccm.get_count(25, Gender::Male, "TEST"), 0.0);
+        assert!((ccm.get_count(0, Gender::Female, "TEST") - 100.0).abs() < 1e-9);
+    }
+}
+
+// ============================================================
+// SHOCK TESTS
+// ============================================================
+
+mod shock_tests {
+    use super::*;
+    use super::fixtures::*;
+    use crate::types::{pandemic_shock, war_shock, AgeGroup, AgeTarget, Target};
+
+    fn model() -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_population(&minimal_population("TEST"));
+        ccm.load_mortality_table(simple_mortality("TEST"));
+        ccm.load_fertility_table(simple_fertility("TEST"));
+        ccm
+    }
+
+    fn migration_shock(modifier: ShockModifier, ages: AgeGroup) -> Shock {
+        Shock {
+            id: "exodus".to_string(),
+            name: "Mass Emigration".to_string(),
+            description: String::new(),
+            shock_type: ShockType::Migration,
+            start_year: 2024,
+            end_year: 2024,
+            target_regions: Target::All,
+            target_genders: Target::All,
+            target_ages: AgeTarget::Range(ages),
+            modifier,
+            intensity_curve: None,
+        }
+    }
+
+    #[test]
+    fn test_pandemic_and_war_templates_raise_deaths() {
+        // Given: Pandemic doubling mortality at 65+ and a war tripling it for men 18-45
+        let mut baseline = model();
+        let mut shocked = model();
+        shocked.add_shock(pandemic_shock("pandemic", "Pandemic", 2024, 2024, 2.0, Some(65)));
+        shocked.add_shock(war_shock("war", "War", 2024, 2024, 3.0, None, None));
+
+        // When: Project the shock year
+        let base = baseline.project_one_year(2024, &["TEST".to_string()]);
+        let result = shocked.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Only the targeted cohorts lose more persons
+        let survivors = |ccm: &CohortComponentModel, age, gender| ccm.get_count(age, gender, "TEST");
+        let q99 = 0.001 + 19.0 * 0.005;
+        assert!((survivors(&shocked, 100, Gender::Female) - 50.0 * (1.0 - 2.0 * q99)).abs() < 1e-9);
+        assert!((survivors(&shocked, 31, Gender::Male) - 100.0 * (1.0 - 3.0 * 0.0011)).abs() < 1e-9);
+        assert_eq!(survivors(&shocked, 31, Gender::Female), survivors(&baseline, 31, Gender::Female));
+        assert!(result.deaths > base.deaths);
+        // Life tables carry the shock in its years only
+        let e0 = |ccm: &CohortComponentModel, year| ccm.life_table("TEST", year, Gender::Male).unwrap().life_expectancy(0);
+        assert!(e0(&shocked, 2024) < e0(&baseline, 2024));
+        assert_eq!(e0(&shocked, 2025), e0(&baseline, 2025));
+    }
+
+    #[test]
+    fn test_fertility_shock_scales_births() {
+        // Given: A baby boom raising fertility by 40%
+        let mut ccm = model();
+        let mut shock = pandemic_shock("boom", "Baby Boom", 2024, 2024, 1.4, None);
+        shock.shock_type = ShockType::Fertility;
+        ccm.add_shock(shock);
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: 100 women aged 30 have 14 children
+        assert!((result.births - 14.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_absolute_migration_shock_spreads_total_by_population() {
+        // Given: 60 emigrants at ages 0-1 and a doubling of tabled migration at 30
+        let mut ccm = model();
+        ccm.load_fertility_table(zero_fertility("TEST"));
+        ccm.load_mortality_table(zero_mortality("TEST"));
+        ccm.load_migration_table(MigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![MigrationRate { age: 30, male: 10.0, female: 10.0 }],
+        });
+        ccm.add_shock(migration_shock(ShockModifier::absolute(-60.0), AgeGroup::range(0, 1)));
+        ccm.add_shock(migration_shock(ShockModifier::multiplier(2.0), AgeGroup::range(30, 30)));
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Each of the four equal cohorts loses 15; age 30 gains 20 per sex
+        assert!((ccm.get_count(1, Gender::Male, "TEST") - 85.0).abs() < 1e-9);
+        assert!((ccm.get_count(2, Gender::Female, "TEST") - 85.0).abs() < 1e-9);
+        assert!((ccm.get_count(31, Gender::Female, "TEST") - 120.0).abs() < 1e-9);
+        assert!((result.net_migration - (-60.0 + 40.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_additive_expression_cannot_empty_a_cohort() {
+        // Given: A death probability of 0.9 at 30 raised by 0.2, and fertility lowered by 0.5
+        let mut ccm = model();
+        ccm.load_mortality_table(MortalityTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: (0..=120).map(|age| {
+                let rate = if age == 30 { 0.9 } else { 0.001 };
+                MortalityRate { age, male: rate, female: rate }
+            }).collect(),
+        });
+        let mut shock = migration_shock(ShockModifier::function("base + 0.2"), AgeGroup::range(30, 30));
+        shock.shock_type = ShockType::Mortality;
+        ccm.add_shock(shock.clone());
+        shock.shock_type = ShockType::Fertility;
+        shock.modifier = ShockModifier::function("base - 0.5");
+        ccm.add_shock(shock);
+        ccm.set_audit(true);
+
+        // When: Project one year
+        let result = ccm.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Survivors are left, no births are negative, nothing needs clamping
+        let expected = 100.0 * (1.0 - 0.999);
+        assert!((ccm.get_count(31, Gender::Male, "TEST") - expected).abs() < 1e-9);
+        assert_eq!(result.births, 0.0);
+        assert_eq!(result.regions[0].audit.unwrap().clamped_rates, 0);
+    }
+
+    #[test]
+    fn test_migration_shock_leaves_emigration_rates() {
+        // Given: Half of the 30-year-olds emigrate, and a tripling migration shock at 30
+        let emigration = EmigrationTable {
+            region_id: "TEST".to_string(),
+            year: 2024,
+            rates: vec![EmigrationRate { age: 30, male: 0.5, female: 0.5 }],
+        };
+        let mut baseline = model();
+        baseline.load_emigration_table(emigration.clone());
+        let mut shocked = model();
+        shocked.load_emigration_table(emigration);
+        shocked.add_shock(migration_shock(ShockModifier::multiplier(3.0), AgeGroup::range(30, 30)));
+
+        // When: Project one year
+        baseline.project_one_year(2024, &["TEST".to_string()]);
+        shocked.project_one_year(2024, &["TEST".to_string()]);
+
+        // Then: Emigration does not grow to 150% of the cohort
+        let survivors = |ccm: &CohortComponentModel| ccm.get_count(31, Gender::Male, "TEST");
+        assert!(survivors(&shocked) > 0.0);
+        assert_eq!(survivors(&shocked), survivors(&baseline));
+    }
+}
+
+// ============================================================
+// BENCHMARKS - run with: cargo test --release -- --ignored --nocapture
+// ============================================================
+
+mod benchmarks {
+    use super::*;
+    use super::fixtures::*;
+    use std::time::Instant;
+
+    /// Roughly the number of Czech municipalities
+    const MUNICIPALITIES: usize = 6_250;
+    const YEARS: u32 = 10;
+
+    #[test]
+    #[ignore]
+    fn bench_many_regions_projection() {
+        // Given: Full single-year age structure in every municipality
+        let regions: Vec<String> = (0..MUNICIPALITIES).map(|i| format!("OB{:05}", i)).collect();
+        let mut ccm = CohortComponentModel::new();
+        let cohorts: Vec<Cohort> = regions.iter().flat_map(|region| {
+            (0..=100u32).flat_map(move |age| {
+                [Gender::Male, Gender::Female].into_iter().map(move |gender| Cohort {
+                    age,
+                    gender,
+                    region_id: region.clone(),
+                    count: 10.0,
+                })
+            })
+        }).collect();
+        ccm.load_population(&cohorts);
+        for region in &regions {
+            ccm.load_mortality_table(simple_mortality(region));
+            ccm.load_fertility_table(high_fertility(region));
+            ccm.load_migration_table(labor_migration_profile(region, 1.0));
+        }
+
+        // When: Project ten years
+        let start = Instant::now();
+        for year in 2024..2024 + YEARS {
+            ccm.project_one_year(year, &regions);
+        }
+        let elapsed = start.elapsed();
+
+        // Then: Report throughput
+        println!(
+            "{} regions x {} years: {:.1} ms total, {:.1} ms/year, final population {:.0}",
+            MUNICIPALITIES,
+            YEARS,
+            elapsed.as_secs_f64() * 1000.0,
+            elapsed.as_secs_f64() * 1000.0 / YEARS as f64,
+            ccm.total_population()
+        );
+        assert!(ccm.total_population() > 0.0);
+    }
+}
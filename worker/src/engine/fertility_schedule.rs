@@ -0,0 +1,280 @@
+//! Fertility Assumption Generator
+//!
+//! Turns target paths of the total fertility rate (TFR) and the mean age at
+//! childbearing (MAC) into yearly ASFR schedules for ages 15–49, keeping
+//! the shape of the base-year schedule.
+//!
+//! - **Shift**: the observed schedule is moved along the age axis by the
+//!   change in MAC (linear interpolation between ages) and rescaled to TFR
+//! - **Gamma**: a gamma density from age 15 fitted to the base-year mean and
+//!   variance; the variance is held while MAC moves
+//!
+//! Ages are taken at mid-year (x + 0.5) for MAC and variance. Targets are
+//! interpolated linearly from the base year and held after the last one.
+
+use serde::{Deserialize, Serialize};
+
+use super::ccm::{FERTILITY_MAX_AGE, FERTILITY_MIN_AGE};
+use super::profile::path_value;
+use super::types::*;
+
+/// Corrections of the schedule location towards the target mean age
+const MEAN_ITERATIONS: usize = 50;
+
+/// Accepted gap between achieved and target mean age (years)
+const MEAN_TOLERANCE: f64 = 1e-10;
+
+/// Parametric family used to reshape the schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduleModel {
+    /// Shift the observed base-year schedule
+    #[default]
+    Shift,
+    /// Gamma density fitted to the base-year mean and variance
+    Gamma,
+}
+
+/// Fertility assumption for one year; missing values follow the path
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FertilityTarget {
+    pub year: u32,
+    /// Total fertility rate (children per woman)
+    #[serde(default)]
+    pub tfr: Option<f64>,
+    /// Mean age at childbearing
+    #[serde(default)]
+    pub mean_age: Option<f64>,
+}
+
+/// Schedule generator fitted to a base-year fertility table
+#[derive(Debug, Clone)]
+pub struct FertilityGenerator {
+    region_id: String,
+    base_year: u32,
+    sex_ratio_at_birth: f64,
+    model: ScheduleModel,
+    /// Base-year rates for ages 15–49
+    base: Vec<f64>,
+    tfr: f64,
+    mean_age: f64,
+    variance: f64,
+}
+
+impl FertilityGenerator {
+    /// Fit to a base-year table; `None` if it has no births at ages 15–49
+    pub fn from_table(table: &FertilityTable, model: ScheduleModel) -> Option<Self> {
+        let base: Vec<f64> = (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE)
+            .map(|age| table.get_rate(age).max(0.0))
+            .collect();
+        let (tfr, mean_age, variance) = moments(&base);
+        if tfr <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            region_id: table.region_id.clone(),
+            base_year: table.year,
+            sex_ratio_at_birth: table.sex_ratio_at_birth,
+            model,
+            base,
+            tfr,
+            mean_age,
+            variance,
+        })
+    }
+
+    /// Schedule for ages 15–49 with the given TFR and mean age
+    ///
+    /// The location parameter is corrected until truncation at ages 15 and
+    /// 49 no longer pulls the mean away from the target.
+    fn schedule(&self, tfr: f64, mean_age: f64) -> Vec<f64> {
+        let mut location = mean_age;
+        let mut shape = self.shape(location);
+        for _ in 0..MEAN_ITERATIONS {
+            let (total, achieved, _) = moments(&shape);
+            if total <= 0.0 || (achieved - mean_age).abs() < MEAN_TOLERANCE {
+                break;
+            }
+            location += mean_age - achieved;
+            shape = self.shape(location);
+        }
+        let total: f64 = shape.iter().sum();
+        if total <= 0.0 {
+            return vec![0.0; self.base.len()];
+        }
+        shape.iter().map(|weight| weight / total * tfr).collect()
+    }
+
+    /// Unscaled schedule with its location at `mean_age`
+    fn shape(&self, mean_age: f64) -> Vec<f64> {
+        match self.model {
+            ScheduleModel::Shift => {
+                let shift = mean_age - self.mean_age;
+                (0..self.base.len())
+                    .map(|index| interpolate(&self.base, index as f64 - shift))
+                    .collect()
+            }
+            ScheduleModel::Gamma => {
+                // Gamma from the lowest fertile age: shape k = μ²/σ², scale θ = σ²/μ
+                let mean = mean_age - FERTILITY_MIN_AGE as f64;
+                if mean <= 0.0 || self.variance <= 0.0 {
+                    return vec![0.0; self.base.len()];
+                }
+                let shape = mean * mean / self.variance;
+                let scale = self.variance / mean;
+                (0..self.base.len())
+                    .map(|index| {
+                        let x = index as f64 + 0.5;
+                        ((shape - 1.0) * x.ln() - x / scale).exp()
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Fertility table for one year with the given TFR and mean age
+    pub fn table(&self, year: u32, tfr: f64, mean_age: f64) -> FertilityTable {
+        FertilityTable {
+            region_id: self.region_id.clone(),
+            year,
+            rates: (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE)
+                .zip(self.schedule(tfr, mean_age))
+                .map(|(age, rate)| FertilityRate { age, rate })
+                .collect(),
+            sex_ratio_at_birth: self.sex_ratio_at_birth,
+        }
+    }
+
+    /// Yearly tables after the base year up to `end_year` following the targets
+    pub fn series(&self, targets: &[FertilityTarget], end_year: u32) -> Vec<FertilityTable> {
+        let mut tfr_path = vec![(self.base_year, self.tfr)];
+        let mut mean_age_path = vec![(self.base_year, self.mean_age)];
+        let mut targets: Vec<&FertilityTarget> = targets.iter()
+            .filter(|target| target.year > self.base_year)
+            .collect();
+        targets.sort_by_key(|target| target.year);
+        for target in targets {
+            if let Some(tfr) = target.tfr {
+                tfr_path.push((target.year, tfr));
+            }
+            if let Some(mean_age) = target.mean_age {
+                mean_age_path.push((target.year, mean_age));
+            }
+        }
+
+        (self.base_year + 1..=end_year)
+            .map(|year| {
+                let tfr = path_value(&tfr_path, year).unwrap_or(self.tfr);
+                let mean_age = path_value(&mean_age_path, year).unwrap_or(self.mean_age);
+                self.table(year, tfr, mean_age)
+            })
+            .collect()
+    }
+}
+
+/// TFR, mean age and variance of age at childbearing of rates for ages 15–49
+fn moments(rates: &[f64]) -> (f64, f64, f64) {
+    let tfr: f64 = rates.iter().sum();
+    if tfr <= 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+    let age = |index: usize| FERTILITY_MIN_AGE as f64 + index as f64 + 0.5;
+    let mean = rates.iter().enumerate().map(|(index, rate)| age(index) * rate).sum::<f64>() / tfr;
+    let variance = rates.iter().enumerate()
+        .map(|(index, rate)| (age(index) - mean).powi(2) * rate)
+        .sum::<f64>() / tfr;
+    (tfr, mean, variance)
+}
+
+/// Value at a fractional index, zero outside the schedule
+fn interpolate(values: &[f64], position: f64) -> f64 {
+    let lower = position.floor();
+    let fraction = position - lower;
+    let at = |index: f64| {
+        if index < 0.0 || index >= values.len() as f64 {
+            0.0
+        } else {
+            values[index as usize]
+        }
+    };
+    at(lower) * (1.0 - fraction) + at(lower + 1.0) * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Triangular schedule peaking at 30, TFR 1.5
+    fn base_table() -> FertilityTable {
+        let weights: Vec<(u32, f64)> = (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE)
+            .map(|age| (age, (15.0 - (age as f64 - 30.0).abs()).max(0.0)))
+            .collect();
+        let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        FertilityTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: weights.into_iter()
+                .map(|(age, weight)| FertilityRate { age, rate: weight / total * 1.5 })
+                .collect(),
+            sex_ratio_at_birth: 105.0,
+        }
+    }
+
+    fn table_moments(table: &FertilityTable) -> (f64, f64, f64) {
+        let rates: Vec<f64> = (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE).map(|age| table.get_rate(age)).collect();
+        moments(&rates)
+    }
+
+    #[test]
+    fn test_shift_keeps_shape_and_hits_targets() {
+        let generator = FertilityGenerator::from_table(&base_table(), ScheduleModel::Shift).unwrap();
+
+        let table = generator.table(2030, 1.6, generator.mean_age + 2.0);
+
+        let (tfr, mean_age, variance) = table_moments(&table);
+        assert!((tfr - 1.6).abs() < 1e-12);
+        assert!((mean_age - (generator.mean_age + 2.0)).abs() < 1e-9);
+        assert!((variance - generator.variance).abs() < 1e-9);
+        assert!((table.get_rate(32) / 1.6 - base_table().get_rate(30) / 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gamma_matches_mean_and_tfr() {
+        let generator = FertilityGenerator::from_table(&base_table(), ScheduleModel::Gamma).unwrap();
+
+        let table = generator.table(2030, 1.45, 32.0);
+
+        let (tfr, mean_age, _) = table_moments(&table);
+        assert!((tfr - 1.45).abs() < 1e-12);
+        assert!((mean_age - 32.0).abs() < 1e-9);
+        assert_eq!(table.rates.len(), 35);
+    }
+
+    #[test]
+    fn test_series_interpolates_and_holds_targets() {
+        let generator = FertilityGenerator::from_table(&base_table(), ScheduleModel::Shift).unwrap();
+        let targets = [
+            FertilityTarget { year: 2034, tfr: Some(1.6), mean_age: None },
+            FertilityTarget { year: 2029, tfr: None, mean_age: Some(generator.mean_age + 1.0) },
+        ];
+
+        let series = generator.series(&targets, 2040);
+
+        assert_eq!(series.first().unwrap().year, 2025);
+        assert_eq!(series.last().unwrap().year, 2040);
+        let (tfr, _, _) = table_moments(&series[4]); // 2029: halfway to 2034
+        assert!((tfr - 1.55).abs() < 1e-12);
+        let (tfr, mean_age, _) = table_moments(&series[15]); // 2040: held
+        assert!((tfr - 1.6).abs() < 1e-12);
+        assert!((mean_age - (generator.mean_age + 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_births_cannot_be_fitted() {
+        let mut table = base_table();
+        table.rates.iter_mut().for_each(|rate| rate.rate = 0.0);
+
+        assert!(FertilityGenerator::from_table(&table, ScheduleModel::Gamma).is_none());
+    }
+}
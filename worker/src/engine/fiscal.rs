@@ -0,0 +1,215 @@
+//! Fiscal Projections
+//!
+//! Applies National Transfer Accounts style age profiles of public revenues
+//! (taxes, contributions) and expenditures (pensions, health, education) to
+//! projected populations. An item's amount in year t is the sum over ages
+//! and sexes of P(x, s, t) times its per-capita value, grown by productivity:
+//!
+//!   amount(t) = Σ P(x, s, t) · v(x, s) · (1 + g)^(t - base year)
+//!
+//! - Profiles are per-capita values of the base year; the shape by age is
+//!   held, so changes in the balance come from the age structure and growth
+//! - A profile row applies from its age up to the next row
+//! - Each profile may override the common growth rate (e.g. pensions
+//!   indexed to prices rather than wages)
+
+use serde::{Deserialize, Serialize};
+
+use super::profile::{step_value, ProfileValue};
+use super::types::*;
+
+/// Direction of a fiscal flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FiscalFlow {
+    Revenue,
+    Expenditure,
+}
+
+/// Per-capita age profile of one revenue or expenditure item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalProfile {
+    pub name: String,
+    pub flow: FiscalFlow,
+    /// Yearly growth of the values (default: the common productivity growth)
+    #[serde(default)]
+    pub growth: Option<f64>,
+    pub values: Vec<ProfileValue>,
+}
+
+/// Profiles and growth for a fiscal projection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalSettings {
+    pub profiles: Vec<FiscalProfile>,
+    /// Yearly productivity growth of per-capita values, e.g. 0.015
+    #[serde(default)]
+    pub productivity_growth: f64,
+}
+
+impl FiscalSettings {
+    /// Check profile names, values and growth rates
+    pub fn validate(&self) -> Result<(), String> {
+        let valid_growth = |growth: f64| growth.is_finite() && growth > -1.0;
+        if !valid_growth(self.productivity_growth) {
+            return Err("Productivity growth must be above -1".to_string());
+        }
+        for (index, profile) in self.profiles.iter().enumerate() {
+            if profile.name.is_empty() {
+                return Err("Fiscal profile name is required".to_string());
+            }
+            if self.profiles[..index].iter().any(|other| other.name == profile.name) {
+                return Err(format!("Duplicate fiscal profile {}", profile.name));
+            }
+            if profile.growth.is_some_and(|growth| !valid_growth(growth)) {
+                return Err(format!("Growth of fiscal profile {} must be above -1", profile.name));
+            }
+            if profile.values.iter().any(|value| !value.male.is_finite() || !value.female.is_finite()) {
+                return Err(format!("Fiscal profile {} has invalid values", profile.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Amount of one item in a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalItem {
+    pub name: String,
+    pub flow: FiscalFlow,
+    pub amount: f64,
+}
+
+/// Projected public finances in a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalYear {
+    pub year: u32,
+    pub revenue: f64,
+    pub expenditure: f64,
+    /// Revenue minus expenditure
+    pub balance: f64,
+    /// Revenue as a share of expenditure (0 without expenditure)
+    pub coverage_ratio: f64,
+    /// Items in profile order
+    pub items: Vec<FiscalItem>,
+}
+
+/// Fiscal profiles applied to populations from a base year
+#[derive(Debug, Clone)]
+pub struct FiscalModel {
+    base_year: u32,
+    settings: FiscalSettings,
+}
+
+impl FiscalModel {
+    /// Create a model whose profile values hold in `base_year`
+    pub fn new(base_year: u32, settings: FiscalSettings) -> Self {
+        Self { base_year, settings }
+    }
+
+    /// Revenues, expenditures and balance for the cohorts of a year
+    pub fn project(&self, cohorts: &[Cohort], year: u32) -> FiscalYear {
+        let elapsed = year as i32 - self.base_year as i32;
+        let items: Vec<FiscalItem> = self.settings.profiles.iter().map(|profile| {
+            let growth = profile.growth.unwrap_or(self.settings.productivity_growth);
+            let base_amount: f64 = cohorts.iter()
+                .map(|cohort| cohort.count * step_value(&profile.values, cohort.age, cohort.gender))
+                .sum();
+            FiscalItem {
+                name: profile.name.clone(),
+                flow: profile.flow,
+                amount: base_amount * (1.0 + growth).powi(elapsed),
+            }
+        }).collect();
+
+        let total = |flow| items.iter().filter(|item| item.flow == flow).map(|item| item.amount).sum::<f64>();
+        let revenue = total(FiscalFlow::Revenue);
+        let expenditure = total(FiscalFlow::Expenditure);
+        FiscalYear {
+            year,
+            revenue,
+            expenditure,
+            balance: revenue - expenditure,
+            coverage_ratio: if expenditure > 0.0 { revenue / expenditure } else { 0.0 },
+            items,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cohort(age: u32, gender: Gender, count: f64) -> Cohort {
+        Cohort { age, gender, region_id: "CZ".to_string(), count }
+    }
+
+    fn profile(name: &str, flow: FiscalFlow, values: Vec<ProfileValue>) -> FiscalProfile {
+        FiscalProfile { name: name.to_string(), flow, growth: None, values }
+    }
+
+    /// Taxes paid at 20-64, pensions from 65
+    fn settings() -> FiscalSettings {
+        FiscalSettings {
+            profiles: vec![
+                profile("taxes", FiscalFlow::Revenue, vec![
+                    ProfileValue { age: 20, male: 10.0, female: 8.0 },
+                    ProfileValue { age: 65, male: 1.0, female: 1.0 },
+                ]),
+                profile("pensions", FiscalFlow::Expenditure, vec![
+                    ProfileValue { age: 65, male: 12.0, female: 10.0 },
+                ]),
+            ],
+            productivity_growth: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_balance_from_age_structure() {
+        let model = FiscalModel::new(2024, settings());
+        let cohorts = vec![
+            cohort(10, Gender::Male, 100.0),
+            cohort(40, Gender::Male, 100.0),
+            cohort(40, Gender::Female, 100.0),
+            cohort(70, Gender::Female, 100.0),
+        ];
+
+        let result = model.project(&cohorts, 2024);
+
+        assert!((result.revenue - (1000.0 + 800.0 + 100.0)).abs() < 1e-9);
+        assert!((result.expenditure - 1000.0).abs() < 1e-9);
+        assert!((result.balance - 900.0).abs() < 1e-9);
+        assert!((result.coverage_ratio - 1.9).abs() < 1e-12);
+        assert_eq!(result.items[1].name, "pensions");
+    }
+
+    #[test]
+    fn test_productivity_growth_and_override() {
+        let mut settings = settings();
+        settings.productivity_growth = 0.02;
+        settings.profiles[1].growth = Some(0.0);
+        let model = FiscalModel::new(2024, settings);
+        let cohorts = vec![cohort(40, Gender::Male, 100.0), cohort(70, Gender::Male, 100.0)];
+
+        let result = model.project(&cohorts, 2034);
+
+        let taxes = (1000.0 + 100.0) * 1.02_f64.powi(10);
+        assert!((result.revenue - taxes).abs() < 1e-9);
+        assert!((result.expenditure - 1200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        let mut settings = settings();
+        assert!(settings.validate().is_ok());
+
+        settings.productivity_growth = -1.0;
+        assert!(settings.validate().unwrap_err().contains("above -1"));
+        settings.productivity_growth = 0.0;
+        settings.profiles[1].name = "taxes".to_string();
+        assert!(settings.validate().unwrap_err().contains("Duplicate"));
+    }
+}
@@ -49,16 +49,16 @@ mod tests {
     
     #[test]
     fn test_compute_area_for_simple_polygon() {
-        // Create a small polygon in WGS84 coordinates (approx 1km x 1km near equator)
+        // Create a 1km x 1km polygon in S-JTSK coordinates (meters)
         let mut fc = FeatureCollection {
             bbox: None,
             features: vec![Feature {
                 bbox: None,
                 geometry: Some(GeoJsonGeometry::new(GeoValue::Polygon(vec![vec![
                     vec![0.0, 0.0],
-                    vec![0.01, 0.0],
-                    vec![0.01, 0.01],
-                    vec![0.0, 0.01],
+                    vec![1000.0, 0.0],
+                    vec![1000.0, 1000.0],
+                    vec![0.0, 1000.0],
                     vec![0.0, 0.0],
                 ]]))),
                 id: None,
@@ -68,14 +68,13 @@ mod tests {
             foreign_members: None,
         };
         
-        let result = compute_feature_areas(&mut fc);
+        let result = compute_feature_areas_s_jtsk(&mut fc);
         assert!(result.is_ok());
         
         // Check that area was added to properties
         let props = fc.features[0].properties.as_ref().unwrap();
         let area = props.get("areaSqKm").unwrap().as_f64().unwrap();
         
-        // Area should be roughly 1.2 km² (at equator, 1 degree ≈ 111 km)
-        assert!(area > 0.5 && area < 2.0, "Area should be roughly 1 km²");
+        assert!((area - 1.0).abs() < 1e-9, "Area should be 1 km²");
     }
 }
@@ -0,0 +1,182 @@
+//! Household Projections
+//!
+//! Converts projected populations into households with the headship-rate
+//! method: households of a type headed by persons of age x and sex s are
+//! the population P(x, s) times the headship rate h(x, s) of that type.
+//!
+//! - Rates are given per household type, region and year; years between
+//!   anchors are interpolated (see [`TableSeries`])
+//! - A rate row applies from its age up to the next row, so rates by
+//!   five-year group work with single-year populations
+//! - Regions without rates for a type have no households of it
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::series::{Interpolation, TableSeries};
+use super::types::*;
+
+/// Households of one type in a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HouseholdTypeYear {
+    pub household_type: String,
+    pub households: f64,
+    /// Households by age and sex of the reference person
+    pub by_age_of_head: Vec<AgeSexCount>,
+}
+
+/// Projected households in a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HouseholdYear {
+    pub year: u32,
+    pub total_households: f64,
+    /// Persons per household (0 without households)
+    pub average_size: f64,
+    /// Household types in alphabetical order
+    pub types: Vec<HouseholdTypeYear>,
+}
+
+/// Headship rates by household type and region
+#[derive(Debug, Clone, Default)]
+pub struct HouseholdModel {
+    /// Rate tables by region and household type, anchored by year
+    headship_tables: HashMap<(String, String), TableSeries<HeadshipTable>>,
+
+    /// How rates are derived between anchor years
+    interpolation: Interpolation,
+}
+
+impl HouseholdModel {
+    /// Create a model without headship rates
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load headship rates of a household type for a region and year
+    ///
+    /// Tables for other years of the same type are kept as anchors.
+    pub fn load_headship_table(&mut self, table: HeadshipTable) {
+        self.headship_tables
+            .entry((table.region_id.clone(), table.household_type.clone()))
+            .or_default()
+            .insert(table);
+    }
+
+    /// Set how rates are derived between anchor years (default: linear)
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Households of a population with the headship rates of its year
+    pub fn project(&self, population: &Population) -> HouseholdYear {
+        // Heads by age for every type, including types without rates this year
+        let mut by_type: BTreeMap<&str, BTreeMap<u32, AgeSexCount>> = self.headship_tables.keys()
+            .map(|(_, household_type)| (household_type.as_str(), BTreeMap::new()))
+            .collect();
+        for ((region_id, household_type), series) in &self.headship_tables {
+            let Some(table) = series.table_for(population.year, self.interpolation) else {
+                continue;
+            };
+            let by_age = by_type.entry(household_type.as_str()).or_default();
+            for cohort in population.cohorts.iter().filter(|cohort| &cohort.region_id == region_id) {
+                let heads = cohort.count * table.get_rate(cohort.age, cohort.gender);
+                if heads > 0.0 {
+                    by_age.entry(cohort.age)
+                        .or_insert(AgeSexCount { age: cohort.age, male: 0.0, female: 0.0 })
+                        .add(cohort.gender, heads);
+                }
+            }
+        }
+
+        let types: Vec<HouseholdTypeYear> = by_type.into_iter().map(|(household_type, by_age)| HouseholdTypeYear {
+            household_type: household_type.to_string(),
+            households: by_age.values().map(|row| row.male + row.female).sum(),
+            by_age_of_head: by_age.into_values().collect(),
+        }).collect();
+
+        let total_households: f64 = types.iter().map(|household_type| household_type.households).sum();
+        let persons: f64 = population.cohorts.iter().map(|cohort| cohort.count).sum();
+        HouseholdYear {
+            year: population.year,
+            total_households,
+            average_size: if total_households > 0.0 { persons / total_households } else { 0.0 },
+            types,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn population(year: u32) -> Population {
+        let cohorts = vec![
+            Cohort { age: 25, gender: Gender::Male, region_id: "CZ".to_string(), count: 100.0 },
+            Cohort { age: 27, gender: Gender::Female, region_id: "CZ".to_string(), count: 200.0 },
+            Cohort { age: 70, gender: Gender::Female, region_id: "CZ".to_string(), count: 50.0 },
+        ];
+        Population {
+            scenario_id: "test".to_string(),
+            year,
+            metadata: Population::calculate_metadata(&cohorts),
+            cohorts,
+        }
+    }
+
+    fn headship(household_type: &str, year: u32, rates: Vec<HeadshipRate>) -> HeadshipTable {
+        HeadshipTable {
+            region_id: "CZ".to_string(),
+            year,
+            household_type: household_type.to_string(),
+            rates,
+        }
+    }
+
+    #[test]
+    fn test_households_by_type_and_age_of_head() {
+        let mut model = HouseholdModel::new();
+        // Rates by five-year group: 25-29 and 65+
+        model.load_headship_table(headship("single", 2024, vec![
+            HeadshipRate { age: 25, male: 0.2, female: 0.1 },
+            HeadshipRate { age: 65, male: 0.3, female: 0.4 },
+        ]));
+        model.load_headship_table(headship("couple", 2024, vec![
+            HeadshipRate { age: 20, male: 0.3, female: 0.05 },
+            HeadshipRate { age: 65, male: 0.0, female: 0.0 },
+        ]));
+
+        let result = model.project(&population(2024));
+
+        let names: Vec<&str> = result.types.iter().map(|t| t.household_type.as_str()).collect();
+        assert_eq!(names, vec!["couple", "single"]);
+        let single = &result.types[1];
+        assert!((single.households - (20.0 + 20.0 + 20.0)).abs() < 1e-9);
+        assert_eq!(single.by_age_of_head.iter().map(|row| row.age).collect::<Vec<_>>(), vec![25, 27, 70]);
+        assert!((result.types[0].households - 40.0).abs() < 1e-9);
+        assert!((result.total_households - 100.0).abs() < 1e-9);
+        assert!((result.average_size - 3.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rates_vary_over_time() {
+        let mut model = HouseholdModel::new();
+        let rates = |rate| vec![HeadshipRate { age: 0, male: rate, female: rate }];
+        model.load_headship_table(headship("single", 2020, rates(0.1)));
+        model.load_headship_table(headship("single", 2030, rates(0.3)));
+
+        assert!((model.project(&population(2025)).total_households - 70.0).abs() < 1e-9);
+        model.set_interpolation(Interpolation::Constant);
+        assert!((model.project(&population(2025)).total_households - 35.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_rates_no_households() {
+        let result = HouseholdModel::new().project(&population(2024));
+
+        assert!(result.types.is_empty());
+        assert_eq!(result.average_size, 0.0);
+    }
+}
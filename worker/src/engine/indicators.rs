@@ -0,0 +1,117 @@
+//! Demographic Indicators
+//!
+//! Summary measures of a region's population in one projection step,
+//! from counts by age group at the end of the step and the step's births
+//! and deaths.
+//!
+//! - Crude rates use the mid-period population (mean of start and end) and
+//!   are annualized for five-year steps
+//! - Mean age takes every group at its midpoint; the open group is treated
+//!   like the others
+//! - Ratios and shares are 0 when their denominator is empty
+
+use super::profile::WORKING_AGE;
+use super::types::*;
+
+/// Lower bounds of the older age groups
+const OLD_AGE: u32 = 65;
+const OLDEST_OLD_AGE: u32 = 80;
+
+impl Indicators {
+    /// Indicators from end-of-step counts by age group
+    ///
+    /// `counts[i]` holds both sexes in the group starting at age `i × width`;
+    /// the step also lasts `width` years.
+    pub fn compute(
+        counts: &[f64],
+        width: u32,
+        start_population: f64,
+        births: f64,
+        deaths: f64,
+        tfr: f64,
+    ) -> Self {
+        let sum_from = |from: u32, to: u32| -> f64 {
+            counts.iter().enumerate()
+                .filter(|(group, _)| (from..to).contains(&(*group as u32 * width)))
+                .map(|(_, count)| count)
+                .sum()
+        };
+        let total: f64 = counts.iter().sum();
+        let children = sum_from(0, WORKING_AGE);
+        let working = sum_from(WORKING_AGE, OLD_AGE);
+        let old = sum_from(OLD_AGE, u32::MAX);
+        let oldest = sum_from(OLDEST_OLD_AGE, u32::MAX);
+
+        let person_years = (start_population + total) / 2.0 * width as f64;
+        let age_sum: f64 = counts.iter().enumerate()
+            .map(|(group, count)| (group as f64 + 0.5) * width as f64 * count)
+            .sum();
+
+        Self {
+            tfr,
+            crude_birth_rate: ratio(births, person_years) * 1000.0,
+            crude_death_rate: ratio(deaths, person_years) * 1000.0,
+            old_age_dependency_ratio: ratio(old, working) * 100.0,
+            total_dependency_ratio: ratio(children + old, working) * 100.0,
+            potential_support_ratio: ratio(working, old),
+            mean_age: ratio(age_sum, total),
+            share_under_15: ratio(children, total) * 100.0,
+            share_15_to_64: ratio(working, total) * 100.0,
+            share_65_plus: ratio(old, total) * 100.0,
+            share_80_plus: ratio(oldest, total) * 100.0,
+        }
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_year_indicators() {
+        // 10 persons at every age 0-99
+        let counts = vec![10.0; 100];
+
+        let indicators = Indicators::compute(&counts, 1, 1000.0, 20.0, 10.0, 1.5);
+
+        assert_eq!(indicators.tfr, 1.5);
+        assert!((indicators.crude_birth_rate - 20.0).abs() < 1e-12);
+        assert!((indicators.crude_death_rate - 10.0).abs() < 1e-12);
+        assert!((indicators.old_age_dependency_ratio - 70.0).abs() < 1e-12);
+        assert!((indicators.total_dependency_ratio - 100.0).abs() < 1e-12);
+        assert!((indicators.potential_support_ratio - 50.0 / 35.0).abs() < 1e-12);
+        assert!((indicators.mean_age - 50.0).abs() < 1e-12);
+        assert!((indicators.share_under_15 - 15.0).abs() < 1e-12);
+        assert!((indicators.share_15_to_64 - 50.0).abs() < 1e-12);
+        assert!((indicators.share_65_plus - 35.0).abs() < 1e-12);
+        assert!((indicators.share_80_plus - 20.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_five_year_groups_are_annualized() {
+        // 50 persons in each group 0-4 ... 95-99
+        let counts = vec![50.0; 20];
+
+        let indicators = Indicators::compute(&counts, 5, 1000.0, 100.0, 50.0, 1.5);
+
+        assert!((indicators.crude_birth_rate - 20.0).abs() < 1e-12);
+        assert!((indicators.crude_death_rate - 10.0).abs() < 1e-12);
+        assert!((indicators.mean_age - 50.0).abs() < 1e-12);
+        assert!((indicators.share_80_plus - 20.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_empty_population() {
+        let indicators = Indicators::compute(&[0.0; 10], 1, 0.0, 0.0, 0.0, 0.0);
+
+        assert_eq!(indicators, Indicators::default());
+    }
+}
@@ -0,0 +1,90 @@
+//! Infant Mortality Separation
+//!
+//! Infant deaths (q0) fall in two Lexis triangles: deaths in the calendar
+//! year of birth, and deaths in the following year before the first birthday.
+//! The separation factor is the share falling in the year of birth.
+//!
+//! - Births survive to the end of their birth year with `1 - f·q0`
+//! - The age-0 population at the start of a year has already lived through
+//!   the first part, so it faces only the remaining `(1-f)·q0`, conditional
+//!   on having survived the first part
+//!
+//! Life tables place infant deaths at the Coale-Demeny average age at death
+//! (a0) instead of mid-year, as deaths cluster in the first weeks.
+
+use super::types::Gender;
+
+/// Share of infant deaths occurring in the calendar year of birth
+/// (typical for low-mortality populations)
+pub const DEFAULT_SEPARATION_FACTOR: f64 = 0.85;
+
+/// Average years lived in the first year by infants who die (a0), from q0
+/// by the Coale-Demeny West formulas
+pub fn coale_demeny_a0(q0: f64, gender: Gender) -> f64 {
+    let q0 = q0.clamp(0.0, 1.0);
+    match gender {
+        Gender::Male if q0 >= 0.1 => 0.33,
+        Gender::Male => 0.0425 + 2.875 * q0,
+        Gender::Female if q0 >= 0.1 => 0.35,
+        Gender::Female => 0.05 + 3.0 * q0,
+    }
+}
+
+/// Probability that a child born during the year is alive at its end
+pub fn birth_year_survival(q0: f64, separation_factor: f64) -> f64 {
+    1.0 - separation_factor.clamp(0.0, 1.0) * q0.clamp(0.0, 1.0)
+}
+
+/// Death probability for the age-0 population at the start of a year
+pub fn remaining_infant_mortality(q0: f64, separation_factor: f64) -> f64 {
+    let q0 = q0.clamp(0.0, 1.0);
+    let separation_factor = separation_factor.clamp(0.0, 1.0);
+    let survived_first_part = 1.0 - separation_factor * q0;
+    if survived_first_part <= 0.0 {
+        return 1.0;
+    }
+    ((1.0 - separation_factor) * q0 / survived_first_part).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts_combine_to_full_infant_mortality() {
+        let q0 = 0.004;
+        let f = 0.85;
+
+        let survival = birth_year_survival(q0, f) * (1.0 - remaining_infant_mortality(q0, f));
+
+        assert!((survival - (1.0 - q0)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_separation_factor_one_puts_all_deaths_in_birth_year() {
+        assert!((birth_year_survival(0.01, 1.0) - 0.99).abs() < 1e-15);
+        assert_eq!(remaining_infant_mortality(0.01, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_separation_factor_zero_defers_all_deaths() {
+        assert_eq!(birth_year_survival(0.01, 0.0), 1.0);
+        assert!((remaining_infant_mortality(0.01, 0.0) - 0.01).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_coale_demeny_a0() {
+        assert!((coale_demeny_a0(0.004, Gender::Male) - 0.054).abs() < 1e-12);
+        assert!((coale_demeny_a0(0.004, Gender::Female) - 0.062).abs() < 1e-12);
+        // Continuous at q0 = 0.1, constant above
+        assert!((coale_demeny_a0(0.1, Gender::Male) - 0.33).abs() < 1e-12);
+        assert_eq!(coale_demeny_a0(0.3, Gender::Female), 0.35);
+    }
+
+    #[test]
+    fn test_total_infant_mortality() {
+        assert_eq!(birth_year_survival(1.0, 1.0), 0.0);
+        assert_eq!(remaining_infant_mortality(1.0, 1.0), 1.0);
+        assert_eq!(remaining_infant_mortality(1.0, 0.5), 1.0);
+    }
+}
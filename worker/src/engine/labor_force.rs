@@ -0,0 +1,242 @@
+//! Labor Force Projections
+//!
+//! Applies age/sex-specific labor force participation rates to projected
+//! populations: the labor force at age x and sex s is P(x, s) times the
+//! participation rate LFPR(x, s).
+//!
+//! - Rates are given per region and year; years between anchors are
+//!   interpolated (see [`TableSeries`]). A rate row applies from its age up
+//!   to the next row, so rates by five-year group can be used.
+//! - Targets move the rates of an age range linearly from the base year to
+//!   the target year and hold them afterwards (e.g. rising participation at
+//!   60-69 under a pension reform); they take precedence over later anchors
+//! - The active share is the labor force per 100 persons aged 15+, the
+//!   economic dependency ratio the persons outside the labor force per 100
+//!   in it
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::profile::{path_value, WORKING_AGE};
+use super::series::{Interpolation, TableSeries};
+use super::types::*;
+
+/// Participation target for an age range; missing sexes keep their rates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipationTarget {
+    pub year: u32,
+    pub min_age: u32,
+    pub max_age: u32,
+    #[serde(default)]
+    pub male: Option<f64>,
+    #[serde(default)]
+    pub female: Option<f64>,
+}
+
+/// Projected labor force in a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaborForceYear {
+    pub year: u32,
+    pub labor_force: f64,
+    pub male_labor_force: f64,
+    pub female_labor_force: f64,
+    /// Labor force per 100 persons aged 15+
+    pub active_share: f64,
+    /// Persons outside the labor force per 100 in it
+    pub economic_dependency_ratio: f64,
+    /// Labor force by age and sex
+    pub by_age: Vec<AgeSexCount>,
+}
+
+/// Participation rates by region with optional target paths
+#[derive(Debug, Clone)]
+pub struct LaborForceModel {
+    /// Year from which target paths start
+    base_year: u32,
+
+    /// Rate tables by region, anchored by year
+    participation_tables: HashMap<String, TableSeries<ParticipationTable>>,
+
+    /// Targets in ascending year order
+    targets: Vec<ParticipationTarget>,
+
+    /// How rates are derived between anchor years
+    interpolation: Interpolation,
+}
+
+impl LaborForceModel {
+    /// Create a model whose target paths start in `base_year`
+    pub fn new(base_year: u32) -> Self {
+        Self {
+            base_year,
+            participation_tables: HashMap::new(),
+            targets: Vec::new(),
+            interpolation: Interpolation::default(),
+        }
+    }
+
+    /// Load participation rates for a region and year
+    ///
+    /// Tables for other years of the same region are kept as anchors.
+    pub fn load_participation_table(&mut self, table: ParticipationTable) {
+        self.participation_tables.entry(table.region_id.clone()).or_default().insert(table);
+    }
+
+    /// Add a participation target applying to all regions
+    pub fn add_target(&mut self, target: ParticipationTarget) {
+        let index = self.targets.partition_point(|other| other.year <= target.year);
+        self.targets.insert(index, target);
+    }
+
+    /// Set how rates are derived between anchor years (default: linear)
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Participation rate of a cohort in a year, following the targets
+    ///
+    /// `current` and `base` are the region's tables for the year and the base year.
+    fn rate(
+        &self,
+        current: Option<&ParticipationTable>,
+        base: Option<&ParticipationTable>,
+        year: u32,
+        age: u32,
+        gender: Gender,
+    ) -> f64 {
+        let table_rate = |table: Option<&ParticipationTable>| table
+            .map(|table| table.get_rate(age, gender))
+            .unwrap_or(0.0);
+
+        let mut path = Vec::new();
+        for target in &self.targets {
+            let value = match gender {
+                Gender::Male => target.male,
+                Gender::Female => target.female,
+            };
+            if let Some(value) = value.filter(|_| target.year > self.base_year && (target.min_age..=target.max_age).contains(&age)) {
+                if path.is_empty() {
+                    path.push((self.base_year, table_rate(base)));
+                }
+                path.push((target.year, value));
+            }
+        }
+        match path_value(&path, year) {
+            Some(rate) if year > self.base_year => rate,
+            _ => table_rate(current),
+        }
+    }
+
+    /// Labor force of a population with the rates of its year
+    pub fn project(&self, population: &Population) -> LaborForceYear {
+        let mut by_age: BTreeMap<u32, AgeSexCount> = BTreeMap::new();
+        let mut adults = 0.0;
+        // Tables of each region for the year and the base year, resolved once
+        let mut tables: HashMap<&str, [Option<Cow<ParticipationTable>>; 2]> = HashMap::new();
+        for cohort in &population.cohorts {
+            if cohort.age >= WORKING_AGE {
+                adults += cohort.count;
+            }
+            let [current, base] = tables.entry(cohort.region_id.as_str()).or_insert_with(|| {
+                let series = self.participation_tables.get(&cohort.region_id);
+                [population.year, self.base_year]
+                    .map(|year| series.and_then(|series| series.table_for(year, self.interpolation)))
+            });
+            let rate = self.rate(current.as_deref(), base.as_deref(), population.year, cohort.age, cohort.gender);
+            let active = cohort.count * rate;
+            if active > 0.0 {
+                by_age.entry(cohort.age)
+                    .or_insert(AgeSexCount { age: cohort.age, male: 0.0, female: 0.0 })
+                    .add(cohort.gender, active);
+            }
+        }
+
+        let male_labor_force: f64 = by_age.values().map(|row| row.male).sum();
+        let female_labor_force: f64 = by_age.values().map(|row| row.female).sum();
+        let labor_force = male_labor_force + female_labor_force;
+        let persons: f64 = population.cohorts.iter().map(|cohort| cohort.count).sum();
+        let per_hundred = |numerator: f64, denominator: f64| {
+            if denominator > 0.0 { numerator / denominator * 100.0 } else { 0.0 }
+        };
+        LaborForceYear {
+            year: population.year,
+            labor_force,
+            male_labor_force,
+            female_labor_force,
+            active_share: per_hundred(labor_force, adults),
+            economic_dependency_ratio: per_hundred(persons - labor_force, labor_force),
+            by_age: by_age.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn population(year: u32) -> Population {
+        let cohorts = vec![
+            Cohort { age: 10, gender: Gender::Female, region_id: "CZ".to_string(), count: 100.0 },
+            Cohort { age: 40, gender: Gender::Male, region_id: "CZ".to_string(), count: 100.0 },
+            Cohort { age: 40, gender: Gender::Female, region_id: "CZ".to_string(), count: 100.0 },
+            Cohort { age: 62, gender: Gender::Male, region_id: "CZ".to_string(), count: 100.0 },
+        ];
+        Population {
+            scenario_id: "test".to_string(),
+            year,
+            metadata: Population::calculate_metadata(&cohorts),
+            cohorts,
+        }
+    }
+
+    /// Participation by broad group: 15-59 and 60+
+    fn model() -> LaborForceModel {
+        let mut model = LaborForceModel::new(2024);
+        model.load_participation_table(ParticipationTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: vec![
+                ParticipationRate { age: 15, male: 0.9, female: 0.8 },
+                ParticipationRate { age: 60, male: 0.3, female: 0.2 },
+            ],
+        });
+        model
+    }
+
+    #[test]
+    fn test_labor_force_and_ratios() {
+        let result = model().project(&population(2024));
+
+        // 90 + 80 aged 40, 30 aged 62
+        assert!((result.labor_force - 200.0).abs() < 1e-9);
+        assert!((result.male_labor_force - 120.0).abs() < 1e-9);
+        assert!((result.active_share - 200.0 / 300.0 * 100.0).abs() < 1e-9);
+        assert!((result.economic_dependency_ratio - 100.0).abs() < 1e-9);
+        assert_eq!(result.by_age.iter().map(|row| row.age).collect::<Vec<_>>(), vec![40, 62]);
+    }
+
+    #[test]
+    fn test_target_path_for_older_workers() {
+        let mut model = model();
+        model.add_target(ParticipationTarget { year: 2034, min_age: 60, max_age: 69, male: Some(0.6), female: None });
+
+        // Halfway in 2029, held after 2034; other ages unchanged
+        let midway = model.project(&population(2029));
+        assert!((midway.by_age[1].male - 45.0).abs() < 1e-9);
+        assert!((midway.by_age[0].male - 90.0).abs() < 1e-9);
+        assert!((model.project(&population(2040)).by_age[1].male - 60.0).abs() < 1e-9);
+        assert!((model.project(&population(2024)).by_age[1].male - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_rates_no_labor_force() {
+        let result = LaborForceModel::new(2024).project(&population(2024));
+
+        assert_eq!(result.labor_force, 0.0);
+        assert_eq!(result.economic_dependency_ratio, 0.0);
+    }
+}
@@ -0,0 +1,272 @@
+//! Lee-Carter Mortality Forecasting
+//!
+//! Fits `ln m(x,t) = a(x) + b(x)·k(t)` to a history of mortality tables of
+//! one region, separately by sex, and forecasts k(t) as a random walk with
+//! drift.
+//!
+//! - Death probabilities are converted to central rates with a constant
+//!   hazard within each year: `m = -ln(1 - q)`
+//! - a(x) is the mean log rate over the fitted years
+//! - b(x), k(t) are the leading singular vectors of the centred log rates,
+//!   normalized so that `Σ b(x) = 1` and `Σ k(t) = 0`
+//! - Forecasts start from the last observed rates (Lee-Miller jump-off):
+//!   `ln m(x,T+h) = ln m(x,T) + b(x)·(k(T+h) - k(T))`
+
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+
+/// Iterations of the rank-one power method
+const SVD_ITERATIONS: usize = 500;
+
+/// Bounds on death probabilities before taking logs
+const MIN_PROBABILITY: f64 = 1e-12;
+const MAX_PROBABILITY: f64 = 1.0 - 1e-12;
+
+/// Fitted Lee-Carter parameters for one sex
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeeCarterParameters {
+    /// Mean log central death rate by age
+    pub ax: Vec<f64>,
+    /// Age response to the mortality index (sums to 1)
+    pub bx: Vec<f64>,
+    /// Mortality index by fitted year (sums to 0)
+    pub kt: Vec<f64>,
+    /// Average yearly change of k(t)
+    pub drift: f64,
+    /// Log rates in the last fitted year (jump-off)
+    #[serde(skip)]
+    jump_off: Vec<f64>,
+}
+
+/// Lee-Carter model fitted to one region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeeCarterFit {
+    pub region_id: String,
+    /// Ages of the fitted tables, ascending
+    pub ages: Vec<u32>,
+    /// Fitted years, ascending
+    pub years: Vec<u32>,
+    pub male: LeeCarterParameters,
+    pub female: LeeCarterParameters,
+}
+
+impl LeeCarterFit {
+    /// Fit the model to mortality tables of one region
+    ///
+    /// Needs at least two years; a later table for the same year replaces
+    /// an earlier one. Every table must cover the same ages, so that no age
+    /// drops out of the forecast.
+    pub fn fit(tables: &[MortalityTable]) -> Result<Self, String> {
+        let mut tables: Vec<&MortalityTable> = tables.iter().collect();
+        tables.sort_by_key(|table| table.year);
+        tables.dedup_by(|later, earlier| {
+            if later.year == earlier.year {
+                *earlier = *later;
+                true
+            } else {
+                false
+            }
+        });
+        if tables.len() < 2 {
+            return Err("Lee-Carter needs mortality tables for at least two years".to_string());
+        }
+        let region_id = tables[0].region_id.clone();
+        if tables.iter().any(|table| table.region_id != region_id) {
+            return Err("Lee-Carter tables must belong to one region".to_string());
+        }
+
+        let ages = table_ages(tables[0]);
+        if ages.is_empty() {
+            return Err("Lee-Carter tables have no ages".to_string());
+        }
+        if let Some(table) = tables.iter().find(|table| table_ages(table) != ages) {
+            return Err(format!(
+                "Lee-Carter mortality table for {} covers different ages than {}",
+                table.year, tables[0].year,
+            ));
+        }
+
+        let years: Vec<u32> = tables.iter().map(|table| table.year).collect();
+        let [male, female] = [Gender::Male, Gender::Female].map(|gender| {
+            // log_rates[age][year]
+            let log_rates: Vec<Vec<f64>> = ages.iter()
+                .map(|&age| tables.iter().map(|table| log_central_rate(table.get_rate(age, gender))).collect())
+                .collect();
+            fit_parameters(&log_rates, &years)
+        });
+
+        Ok(Self { region_id, ages, years, male, female })
+    }
+
+    /// Forecast mortality tables for each year after the last fitted year up to `to_year`
+    pub fn forecast(&self, to_year: u32) -> Vec<MortalityTable> {
+        let last_year = *self.years.last().expect("fit has at least two years");
+        (last_year + 1..=to_year).map(|year| {
+            let horizon = (year - last_year) as f64;
+            let male = self.male.forecast_probabilities(horizon);
+            let female = self.female.forecast_probabilities(horizon);
+            MortalityTable {
+                region_id: self.region_id.clone(),
+                year,
+                rates: self.ages.iter().enumerate().map(|(index, &age)| MortalityRate {
+                    age,
+                    male: male[index],
+                    female: female[index],
+                }).collect(),
+            }
+        }).collect()
+    }
+}
+
+impl LeeCarterParameters {
+    /// Death probabilities `horizon` years after the jump-off year
+    fn forecast_probabilities(&self, horizon: f64) -> Vec<f64> {
+        self.jump_off.iter().zip(&self.bx)
+            .map(|(log_rate, b)| 1.0 - (-(log_rate + b * self.drift * horizon).exp()).exp())
+            .collect()
+    }
+}
+
+/// Distinct ages of a table, ascending
+fn table_ages(table: &MortalityTable) -> Vec<u32> {
+    let mut ages: Vec<u32> = table.rates.iter().map(|rate| rate.age).collect();
+    ages.sort_unstable();
+    ages.dedup();
+    ages
+}
+
+/// Log central death rate from a death probability
+fn log_central_rate(probability: f64) -> f64 {
+    let probability = probability.clamp(MIN_PROBABILITY, MAX_PROBABILITY);
+    (-(1.0 - probability).ln()).ln()
+}
+
+/// Fit a(x), b(x), k(t) to log rates by age (rows) and year (columns)
+fn fit_parameters(log_rates: &[Vec<f64>], years: &[u32]) -> LeeCarterParameters {
+    let periods = years.len() as f64;
+    let ax: Vec<f64> = log_rates.iter().map(|row| row.iter().sum::<f64>() / periods).collect();
+    let centred: Vec<Vec<f64>> = log_rates.iter().zip(&ax)
+        .map(|(row, a)| row.iter().map(|value| value - a).collect())
+        .collect();
+
+    // Leading singular vectors by alternating least squares
+    let mut bx = vec![1.0; centred.len()];
+    let mut kt = vec![0.0; years.len()];
+    for _ in 0..SVD_ITERATIONS {
+        let b_norm: f64 = bx.iter().map(|b| b * b).sum();
+        if b_norm == 0.0 {
+            break;
+        }
+        for (t, k) in kt.iter_mut().enumerate() {
+            *k = centred.iter().zip(&bx).map(|(row, b)| row[t] * b).sum::<f64>() / b_norm;
+        }
+        let k_norm: f64 = kt.iter().map(|k| k * k).sum();
+        if k_norm == 0.0 {
+            break;
+        }
+        for (b, row) in bx.iter_mut().zip(&centred) {
+            *b = row.iter().zip(&kt).map(|(value, k)| value * k).sum::<f64>() / k_norm;
+        }
+    }
+
+    // Normalize: Σ b = 1 (k absorbs the scale); no change in mortality gives k = 0
+    let b_sum: f64 = bx.iter().sum();
+    if b_sum.abs() > f64::EPSILON && kt.iter().any(|k| *k != 0.0) {
+        bx.iter_mut().for_each(|b| *b /= b_sum);
+        kt.iter_mut().for_each(|k| *k *= b_sum);
+    } else {
+        bx = vec![1.0 / centred.len() as f64; centred.len()];
+        kt = vec![0.0; years.len()];
+    }
+
+    let first = years[0];
+    let last = years[years.len() - 1];
+    let drift = (kt[kt.len() - 1] - kt[0]) / (last - first) as f64;
+    let jump_off = log_rates.iter().map(|row| row[row.len() - 1]).collect();
+
+    LeeCarterParameters { ax, bx, kt, drift, jump_off }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tables generated exactly from a Lee-Carter model with linear k(t)
+    fn synthetic_tables() -> Vec<MortalityTable> {
+        (0..10).map(|t| MortalityTable {
+            region_id: "CZ".to_string(),
+            year: 2010 + t,
+            rates: (0..5).map(|age| {
+                let a = -8.0 + age as f64;
+                let b = [0.3, 0.25, 0.2, 0.15, 0.1][age as usize];
+                let k = 4.5 - t as f64; // Σ k = 0 over 10 years
+                let q = |shift: f64| 1.0 - (-(a + shift + b * k).exp()).exp();
+                MortalityRate { age, male: q(0.2), female: q(0.0) }
+            }).collect(),
+        }).collect()
+    }
+
+    #[test]
+    fn test_fit_recovers_parameters() {
+        let fit = LeeCarterFit::fit(&synthetic_tables()).unwrap();
+
+        assert_eq!(fit.ages, vec![0, 1, 2, 3, 4]);
+        assert!((fit.female.bx[0] - 0.3).abs() < 1e-9);
+        assert!((fit.female.kt[0] - 4.5).abs() < 1e-9);
+        assert!((fit.female.drift + 1.0).abs() < 1e-9);
+        assert!((fit.male.ax[0] - (-7.8)).abs() < 1e-9);
+        assert!(fit.male.kt.iter().sum::<f64>().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_forecast_continues_trend() {
+        let tables = synthetic_tables();
+        let fit = LeeCarterFit::fit(&tables).unwrap();
+
+        let forecast = fit.forecast(2021);
+
+        // 2020 and 2021 continue k(t) = 4.5 - t
+        assert_eq!(forecast.iter().map(|table| table.year).collect::<Vec<_>>(), vec![2020, 2021]);
+        let expected = 1.0 - (-(-8.0_f64 + 0.3 * (4.5 - 11.0)).exp()).exp();
+        assert!((forecast[1].get_rate(0, Gender::Female) - expected).abs() < 1e-12);
+        assert!(forecast[1].get_rate(4, Gender::Male) < tables[9].get_rate(4, Gender::Male));
+    }
+
+    #[test]
+    fn test_constant_mortality_has_no_drift() {
+        let table = |year| MortalityTable {
+            region_id: "CZ".to_string(),
+            year,
+            rates: vec![MortalityRate { age: 50, male: 0.01, female: 0.005 }],
+        };
+        let fit = LeeCarterFit::fit(&[table(2000), table(2010)]).unwrap();
+
+        assert_eq!(fit.male.drift, 0.0);
+        assert!((fit.forecast(2030)[19].get_rate(50, Gender::Male) - 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit_needs_two_years_of_one_region() {
+        let tables = synthetic_tables();
+        assert!(LeeCarterFit::fit(&tables[..1]).is_err());
+        assert!(LeeCarterFit::fit(&[tables[0].clone(), tables[0].clone()]).is_err());
+
+        let mut other = tables[1].clone();
+        other.region_id = "SK".to_string();
+        assert!(LeeCarterFit::fit(&[tables[0].clone(), other]).unwrap_err().contains("one region"));
+    }
+
+    #[test]
+    fn test_fit_rejects_mismatched_ages() {
+        let mut tables = synthetic_tables();
+        tables[3].rates.pop();
+
+        let error = LeeCarterFit::fit(&tables).unwrap_err();
+
+        assert!(error.contains("2013"));
+        assert!(error.contains("different ages"));
+    }
+}
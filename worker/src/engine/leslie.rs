@@ -0,0 +1,264 @@
+//! Leslie Matrix and Stable Population Analysis
+//!
+//! One-sex projection matrix for single years of age:
+//!
+//! ```text
+//! | F0  F1  ...  Fω-1  Fω |
+//! | s0  0   ...  0     0  |
+//! | 0   s1  ...  0     0  |
+//! | 0   0   ...  sω-1  sω |
+//! ```
+//!
+//! `F_x` are births of the matrix sex alive at the end of the year per person
+//! aged x, `s_x` the survival from age x to x+1, and `s_ω` the share of the
+//! open age group remaining in it.
+//!
+//! Stable quantities come from the Euler–Lotka equation
+//! `Σ F_x ℓ_x λ^-(x+1) = 1`, with `ℓ_x` the product of survivals below x.
+//! The open group contributes a geometric tail.
+
+/// Leslie matrix stored by its non-zero parts
+#[derive(Debug, Clone)]
+pub struct LeslieMatrix {
+    /// First row: surviving births per person by age
+    pub fertility: Vec<f64>,
+
+    /// Survival from age x to x+1; the last entry is the open group
+    pub survival: Vec<f64>,
+}
+
+/// Long-run properties of a Leslie matrix
+#[derive(Debug, Clone)]
+pub struct StablePopulation {
+    /// Dominant eigenvalue (annual growth ratio)
+    pub lambda: f64,
+
+    /// Intrinsic growth rate r = ln λ
+    pub intrinsic_growth_rate: f64,
+
+    /// Net reproduction rate R0 = Σ F_x ℓ_x
+    pub net_reproduction_rate: f64,
+
+    /// Stable age distribution (shares summing to 1)
+    pub age_distribution: Vec<f64>,
+
+    /// Reproductive values relative to age 0
+    pub reproductive_values: Vec<f64>,
+}
+
+/// Bisection iterations for λ (halves the bracket each time)
+const LAMBDA_ITERATIONS: usize = 200;
+
+impl LeslieMatrix {
+    /// Number of age groups
+    pub fn size(&self) -> usize {
+        self.fertility.len()
+    }
+
+    /// Project a population vector one year
+    #[cfg(test)]
+    pub fn apply(&self, population: &[f64]) -> Vec<f64> {
+        let n = self.size();
+        let mut next = vec![0.0; n];
+        next[0] = self.fertility.iter().zip(population).map(|(f, p)| f * p).sum();
+        for age in 0..n.saturating_sub(1) {
+            next[age + 1] += self.survival[age] * population[age];
+        }
+        if n > 1 {
+            next[n - 1] += self.survival[n - 1] * population[n - 1];
+        }
+        next
+    }
+
+    /// Survivors to each age per newborn (ℓ_x)
+    fn survivorship(&self) -> Vec<f64> {
+        let mut lx = Vec::with_capacity(self.size());
+        let mut survivors = 1.0;
+        for survival in &self.survival {
+            lx.push(survivors);
+            survivors *= survival;
+        }
+        lx
+    }
+
+    /// Discounted births `Σ F_x ℓ_x λ^-(x+1)` over ages `from..`, including the open tail
+    ///
+    /// Infinite when the open group outlives the discount (s_ω ≥ λ) and reproduces.
+    fn discounted_births(&self, lx: &[f64], lambda: f64, from: usize) -> f64 {
+        let n = self.size();
+        let mut total = 0.0;
+        for (age, (fertility, survivors)) in self.fertility.iter().zip(lx).enumerate().skip(from) {
+            let mut term = fertility * survivors * lambda.powi(-(age as i32 + 1));
+            if age == n - 1 && term > 0.0 {
+                let ratio = self.survival[n - 1] / lambda;
+                if ratio >= 1.0 {
+                    return f64::INFINITY;
+                }
+                term /= 1.0 - ratio;
+            }
+            total += term;
+        }
+        total
+    }
+
+    /// Stable population implied by the matrix
+    ///
+    /// Returns `None` if the matrix has no reproduction (R0 = 0).
+    pub fn stable_population(&self) -> Option<StablePopulation> {
+        let n = self.size();
+        if n == 0 {
+            return None;
+        }
+        let lx = self.survivorship();
+        let net_reproduction_rate: f64 = self.fertility.iter().zip(&lx).map(|(f, l)| f * l).sum::<f64>()
+            + self.open_tail(&lx, 1.0);
+        if net_reproduction_rate <= 0.0 || !net_reproduction_rate.is_finite() {
+            return None;
+        }
+
+        // Euler–Lotka is decreasing in λ: bracket the root, then bisect
+        let mut low = f64::EPSILON;
+        let mut high = 1.0;
+        while self.discounted_births(&lx, high, 0) > 1.0 {
+            low = high;
+            high *= 2.0;
+        }
+        for _ in 0..LAMBDA_ITERATIONS {
+            let mid = (low + high) / 2.0;
+            if self.discounted_births(&lx, mid, 0) > 1.0 {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        let lambda = (low + high) / 2.0;
+
+        // c_x ∝ ℓ_x λ^-x, the open group summing its geometric tail
+        let mut age_distribution: Vec<f64> = (0..n)
+            .map(|age| lx[age] * lambda.powi(-(age as i32)))
+            .collect();
+        let open_ratio = self.survival[n - 1] / lambda;
+        age_distribution[n - 1] = if open_ratio < 1.0 {
+            age_distribution[n - 1] / (1.0 - open_ratio)
+        } else {
+            0.0
+        };
+        let total: f64 = age_distribution.iter().sum();
+        age_distribution.iter_mut().for_each(|share| *share /= total);
+
+        // v_x = λ^x / ℓ_x × Σ_{y≥x} F_y ℓ_y λ^-(y+1)
+        let reproductive_values = (0..n)
+            .map(|age| {
+                if lx[age] <= 0.0 {
+                    return 0.0;
+                }
+                lambda.powi(age as i32) / lx[age] * self.discounted_births(&lx, lambda, age)
+            })
+            .collect();
+
+        Some(StablePopulation {
+            lambda,
+            intrinsic_growth_rate: lambda.ln(),
+            net_reproduction_rate,
+            age_distribution,
+            reproductive_values,
+        })
+    }
+
+    /// Births of the open group beyond its first year at growth ratio λ
+    fn open_tail(&self, lx: &[f64], lambda: f64) -> f64 {
+        let n = self.size();
+        let open = self.fertility[n - 1] * lx[n - 1];
+        if open <= 0.0 {
+            return 0.0;
+        }
+        let ratio = self.survival[n - 1] / lambda;
+        if ratio >= 1.0 {
+            return f64::INFINITY;
+        }
+        open * ratio / (1.0 - ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three ages, reproduction at ages 1 and 2
+    fn small_matrix() -> LeslieMatrix {
+        LeslieMatrix {
+            fertility: vec![0.0, 1.2, 0.5],
+            survival: vec![0.8, 0.5, 0.0],
+        }
+    }
+
+    #[test]
+    fn test_apply_projects_one_year() {
+        let next = small_matrix().apply(&[100.0, 50.0, 20.0]);
+
+        assert_eq!(next, vec![70.0, 80.0, 25.0]);
+    }
+
+    #[test]
+    fn test_stable_distribution_is_eigenvector() {
+        let matrix = small_matrix();
+        let stable = matrix.stable_population().unwrap();
+
+        let next = matrix.apply(&stable.age_distribution);
+
+        for (projected, share) in next.iter().zip(&stable.age_distribution) {
+            assert!((projected - stable.lambda * share).abs() < 1e-12);
+        }
+        assert!((stable.age_distribution.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((stable.intrinsic_growth_rate - stable.lambda.ln()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_reproductive_values_are_left_eigenvector() {
+        let matrix = small_matrix();
+        let stable = matrix.stable_population().unwrap();
+        let v = &stable.reproductive_values;
+
+        // (vᵀL)_x = v_0 F_x + v_{x+1} s_x (the open group stays in place)
+        for age in 0..matrix.size() {
+            let next = v[(age + 1).min(matrix.size() - 1)] * matrix.survival[age];
+            let left = v[0] * matrix.fertility[age] + next;
+            assert!((left - stable.lambda * v[age]).abs() < 1e-12);
+        }
+        assert!((v[0] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_net_reproduction_rate() {
+        let stable = small_matrix().stable_population().unwrap();
+
+        // 0.8 × 1.2 + 0.8 × 0.5 × 0.5
+        assert!((stable.net_reproduction_rate - 1.16).abs() < 1e-12);
+        assert!(stable.lambda > 1.0);
+    }
+
+    #[test]
+    fn test_open_group_in_stable_distribution() {
+        let matrix = LeslieMatrix {
+            fertility: vec![0.0, 1.5, 0.0],
+            survival: vec![0.9, 0.8, 0.5],
+        };
+        let stable = matrix.stable_population().unwrap();
+
+        let next = matrix.apply(&stable.age_distribution);
+
+        for (projected, share) in next.iter().zip(&stable.age_distribution) {
+            assert!((projected - stable.lambda * share).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_no_reproduction_has_no_stable_population() {
+        let matrix = LeslieMatrix {
+            fertility: vec![0.0, 0.0],
+            survival: vec![0.9, 0.5],
+        };
+
+        assert!(matrix.stable_population().is_none());
+    }
+}
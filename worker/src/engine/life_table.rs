@@ -0,0 +1,183 @@
+//! Period Life Table
+//!
+//! Built from single-year death probabilities q_x. The last tabulated age
+//! is an open interval: its probability is held for every later year, so
+//! person-years above the table are a geometric tail instead of zero, which
+//! needs a positive open-age probability.
+//!
+//! - q_x: probability of dying between x and x+1
+//! - l_x: survivors to exact age x (radix 1.0 at the first age)
+//! - d_x: deaths between x and x+1 (l_x · q_x)
+//! - L_x: person-years lived between x and x+1, deaths at mid-year except
+//!   at age 0, where they fall at the Coale-Demeny a0 of the sex
+//! - T_x: person-years lived above age x, including the open tail
+//! - e_x: life expectancy at exact age x (T_x / l_x)
+
+use super::infant::coale_demeny_a0;
+use super::types::Gender;
+
+/// Life table over consecutive single years of age
+#[derive(Debug, Clone)]
+pub struct LifeTable {
+    /// First age of the table
+    pub start_age: u32,
+
+    /// Probability of dying between x and x+1
+    pub qx: Vec<f64>,
+
+    /// Survivors to exact age x
+    pub lx: Vec<f64>,
+
+    /// Deaths between x and x+1
+    pub dx: Vec<f64>,
+
+    /// Person-years lived in the year of age x
+    pub person_years: Vec<f64>,
+
+    /// Person-years lived above exact age x
+    pub tx: Vec<f64>,
+
+    /// Life expectancy at exact age x
+    pub ex: Vec<f64>,
+}
+
+impl LifeTable {
+    /// Build a table for one sex from death probabilities for ages `start_age..`
+    ///
+    /// Probabilities are clamped to [0, 1]. Returns `None` for an empty list
+    /// or a zero open-age probability, whose open interval never closes.
+    pub fn from_probabilities(start_age: u32, qx: &[f64], gender: Gender) -> Option<Self> {
+        let qx: Vec<f64> = qx.iter().map(|q| q.clamp(0.0, 1.0)).collect();
+        let last = *qx.last().filter(|&&last| last > 0.0)?;
+
+        let mut lx = Vec::with_capacity(qx.len());
+        let mut dx = Vec::with_capacity(qx.len());
+        let mut person_years = Vec::with_capacity(qx.len());
+        let mut survivors = 1.0;
+        for (age, &q) in (start_age..).zip(&qx) {
+            let separation = if age == 0 { coale_demeny_a0(q, gender) } else { 0.5 };
+            lx.push(survivors);
+            dx.push(survivors * q);
+            person_years.push(survivors * (1.0 - q * (1.0 - separation)));
+            survivors *= 1.0 - q;
+        }
+
+        // Open interval: q held constant, sum of L over (1-q)^k
+        let open_person_years = lx[lx.len() - 1] * (1.0 - last / 2.0) / last;
+
+        let mut tx = vec![0.0; qx.len()];
+        let mut above = open_person_years;
+        tx[qx.len() - 1] = above;
+        for index in (0..qx.len() - 1).rev() {
+            above += person_years[index];
+            tx[index] = above;
+        }
+
+        let ex = tx.iter().zip(&lx)
+            .map(|(total, survivors)| if *survivors > 0.0 { total / survivors } else { 0.0 })
+            .collect();
+
+        Some(Self { start_age, qx, lx, dx, person_years, tx, ex })
+    }
+
+    fn index(&self, age: u32) -> Option<usize> {
+        let index = age.checked_sub(self.start_age)? as usize;
+        (index < self.lx.len()).then_some(index)
+    }
+
+    /// Life expectancy at exact age x (T_x / l_x), 0 outside the table
+    pub fn life_expectancy(&self, age: u32) -> f64 {
+        self.index(age).map(|index| self.ex[index]).unwrap_or(0.0)
+    }
+
+    /// Share of persons aged x and over still alive one year later (T_x+1 / T_x)
+    ///
+    /// This is the survival ratio of an open-ended age group starting at x.
+    pub fn open_interval_survival(&self, age: u32) -> f64 {
+        let Some(index) = self.index(age) else {
+            return 0.0;
+        };
+        let total = self.tx[index];
+        if total <= 0.0 {
+            return 0.0;
+        }
+        ((total - self.person_years[index]) / total).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_probability_gives_matching_open_survival() {
+        let table = LifeTable::from_probabilities(100, &[0.3], Gender::Male).unwrap();
+
+        assert!((table.open_interval_survival(100) - 0.7).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_open_survival_uses_later_ages() {
+        // Open group 85+ in a table continuing to 87
+        let table = LifeTable::from_probabilities(85, &[0.1, 0.2, 0.5], Gender::Female).unwrap();
+
+        let l = [1.0, 0.9, 0.72];
+        let person_years = [0.95, 0.81, 0.54];
+        let tail = l[2] * (1.0 - 0.25) / 0.5;
+        let t85 = person_years[0] + person_years[1] + tail;
+        let expected = (t85 - person_years[0]) / t85;
+
+        assert!((table.lx[2] - l[2]).abs() < 1e-12);
+        assert!((table.open_interval_survival(85) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_life_expectancy() {
+        // Everyone dies in the first year at a0
+        let table = LifeTable::from_probabilities(0, &[1.0, 1.0], Gender::Male).unwrap();
+        assert!((table.life_expectancy(0) - 0.33).abs() < 1e-12);
+
+        // Constant q above infancy: e = (1 - q/2) / q
+        let table = LifeTable::from_probabilities(1, &[0.1, 0.1], Gender::Female).unwrap();
+        assert!((table.life_expectancy(1) - 9.5).abs() < 1e-12);
+        assert!((table.life_expectancy(2) - 9.5).abs() < 1e-12);
+
+        // From birth infant deaths fall at a0 = 0.35: e0 = 9.5 + q·(a0 - 1/2)
+        let table = LifeTable::from_probabilities(0, &[0.1, 0.1], Gender::Female).unwrap();
+        assert!((table.life_expectancy(0) - 9.485).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_columns() {
+        let table = LifeTable::from_probabilities(0, &[0.1, 0.5], Gender::Male).unwrap();
+
+        assert_eq!(table.qx, vec![0.1, 0.5]);
+        assert!((table.dx[0] - 0.1).abs() < 1e-12);
+        assert!((table.dx[1] - 0.45).abs() < 1e-12);
+        // Open tail at q = 0.5: l·(1 - q/2)/q = 0.9 × 1.5
+        assert!((table.tx[1] - 1.35).abs() < 1e-12);
+        // L_0 with a0 = 0.33: l_1 + a0·d_0
+        assert!((table.person_years[0] - 0.933).abs() < 1e-12);
+        assert!((table.tx[0] - (0.933 + 1.35)).abs() < 1e-12);
+        assert!((table.ex[1] - 1.5).abs() < 1e-12);
+        assert_eq!(table.life_expectancy(2), 0.0);
+    }
+
+    #[test]
+    fn test_zero_open_age_probability_has_no_table() {
+        assert!(LifeTable::from_probabilities(100, &[0.0, 0.0], Gender::Male).is_none());
+        assert!(LifeTable::from_probabilities(0, &[0.01, 0.0], Gender::Male).is_none());
+
+        let table = LifeTable::from_probabilities(100, &[0.0, 0.5], Gender::Male).unwrap();
+        assert!(table.life_expectancy(100).is_finite());
+    }
+
+    #[test]
+    fn test_empty_and_out_of_range() {
+        assert!(LifeTable::from_probabilities(0, &[], Gender::Male).is_none());
+
+        let table = LifeTable::from_probabilities(100, &[1.0], Gender::Male).unwrap();
+        assert_eq!(table.open_interval_survival(100), 0.0);
+        assert_eq!(table.open_interval_survival(99), 0.0);
+    }
+}
@@ -5,6 +5,22 @@
 mod types;
 mod projection;
 mod ccm;
+mod abridged;
+mod fertility_schedule;
+mod fiscal;
+mod households;
+mod indicators;
+mod infant;
+mod labor_force;
+mod lee_carter;
+mod leslie;
+mod life_table;
+mod multistate;
+mod profile;
+mod series;
+mod service_demand;
+mod state;
+mod stochastic;
 pub mod geo;
 
 #[cfg(test)]
@@ -12,4 +28,15 @@ mod ccm_tests;
 
 pub use types::*;
 pub use projection::DemographicEngine;
-pub use ccm::CohortComponentModel;
+pub use ccm::{CohortComponentModel, MIN_OPEN_AGE};
+pub use abridged::{AbridgedModel, AGE_STEP};
+pub use series::Interpolation;
+pub use life_table::LifeTable;
+pub use fertility_schedule::{FertilityGenerator, FertilityTarget, ScheduleModel};
+pub use fiscal::{FiscalModel, FiscalSettings, FiscalYear};
+pub use households::{HouseholdModel, HouseholdYear};
+pub use labor_force::{LaborForceModel, LaborForceYear, ParticipationTarget};
+pub use lee_carter::LeeCarterFit;
+pub use multistate::MultistateModel;
+pub use service_demand::{DemandModel, DemandProfile, ServiceDemandYear};
+pub use stochastic::{run_stochastic, StochasticSettings, StochasticYear};
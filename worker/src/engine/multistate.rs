@@ -0,0 +1,366 @@
+//! Multistate Projection
+//!
+//! Adds one user-defined categorical dimension (education, citizenship, ...)
+//! to the (age, sex, region) cohort key. Each state is projected by its own
+//! [`CohortComponentModel`], so fertility, mortality and migration can differ
+//! by state, and persons move between states with age/sex-specific
+//! transition rates.
+//!
+//! For each year t → t+1:
+//! 1. **Transitions**: Move persons between states at the start of the year,
+//!    all outflows computed from the same counts. Outflow rates of a cohort
+//!    summing above one are scaled down to one.
+//! 2. **Projection**: Project every state one year with its own model
+//! 3. **Births**: Newborns stay in the mother's state, or all move to the
+//!    birth state when one is set (e.g. the lowest education level)
+//! 4. **Totals**: Combine the states; the combined TFR is the states' TFR
+//!    weighted by women aged 15-49 at the start of the year
+
+use std::collections::HashMap;
+
+use super::ccm::{CohortComponentModel, FERTILITY_MAX_AGE, FERTILITY_MIN_AGE};
+use super::series::{Interpolation, TableSeries};
+use super::types::*;
+
+/// Cohort-component projection with one extra population dimension
+#[derive(Clone)]
+pub struct MultistateModel {
+    /// State IDs in index order
+    state_ids: Vec<String>,
+
+    /// Projection model of each state
+    models: Vec<CohortComponentModel>,
+
+    /// Last age group (open-ended interval) shared by all states
+    max_age: u32,
+
+    /// Transition tables by region, origin and destination state index
+    transitions: HashMap<(String, usize, usize), TableSeries<TransitionTable>>,
+
+    /// How transition rates are derived between anchor years
+    interpolation: Interpolation,
+
+    /// State of all newborns (None: the mother's state)
+    birth_state: Option<usize>,
+}
+
+impl MultistateModel {
+    /// Create a model whose states start as `template` without its population
+    ///
+    /// Rate tables and settings of the template apply to every state until
+    /// replaced through [`state_model_mut`](Self::state_model_mut).
+    pub fn new(state_ids: &[String], template: &CohortComponentModel) -> Self {
+        let mut empty = template.clone();
+        empty.load_population(&[]);
+        Self {
+            state_ids: state_ids.to_vec(),
+            models: vec![empty; state_ids.len()],
+            max_age: template.open_age_group(),
+            transitions: HashMap::new(),
+            interpolation: template.interpolation(),
+            birth_state: None,
+        }
+    }
+
+    fn state_index(&self, state_id: &str) -> Result<usize, String> {
+        self.state_ids.iter()
+            .position(|id| id == state_id)
+            .ok_or_else(|| format!("Unknown state {}", state_id))
+    }
+
+    /// Model of one state
+    pub fn state_model(&self, state_id: &str) -> Option<&CohortComponentModel> {
+        let index = self.state_index(state_id).ok()?;
+        self.models.get(index)
+    }
+
+    /// Model of one state, e.g. to load its population or adjust its rates
+    pub fn state_model_mut(&mut self, state_id: &str) -> Option<&mut CohortComponentModel> {
+        let index = self.state_index(state_id).ok()?;
+        self.models.get_mut(index)
+    }
+
+    /// Load transition rates from one state to another for a region and year
+    ///
+    /// Tables for other years of the same pair are kept as anchors.
+    pub fn load_transition_table(&mut self, table: TransitionTable) -> Result<(), String> {
+        let from = self.state_index(&table.from_state)?;
+        let to = self.state_index(&table.to_state)?;
+        if from == to {
+            return Err(format!("Transition from {} to itself", table.from_state));
+        }
+        self.transitions.entry((table.region_id.clone(), from, to)).or_default().insert(table);
+        Ok(())
+    }
+
+    /// Assign all newborns to one state (default: the mother's state)
+    pub fn set_birth_state(&mut self, state_id: &str) -> Result<(), String> {
+        self.birth_state = Some(self.state_index(state_id)?);
+        Ok(())
+    }
+
+    /// Get population as cohorts of every state (keys repeat across states)
+    pub fn get_cohorts(&self) -> Vec<Cohort> {
+        self.models.iter().flat_map(|model| model.get_cohorts()).collect()
+    }
+
+    /// Project all states one year forward
+    pub fn project_one_year(&mut self, year: u32, regions: &[String]) -> MultistateYear {
+        let states = self.models.len();
+        let mut transfers_in = vec![0.0; states];
+        let mut transfers_out = vec![0.0; states];
+        let initial_population: f64 = self.models.iter().map(|model| model.total_population()).sum();
+
+        // Step 1: Transitions from the start-of-year counts
+        let interpolation = self.interpolation;
+        for region_id in regions {
+            let tables: Vec<_> = self.transitions.iter()
+                .filter(|((region, _, _), _)| region == region_id)
+                .filter_map(|(&(_, from, to), series)| {
+                    series.table_for(year, interpolation).map(|table| (from, to, table))
+                })
+                .collect();
+            if tables.is_empty() {
+                continue;
+            }
+            for age in 0..=self.max_age {
+                for gender in [Gender::Male, Gender::Female] {
+                    let counts: Vec<f64> = self.models.iter()
+                        .map(|model| model.get_count(age, gender, region_id))
+                        .collect();
+                    let mut outflow = vec![0.0; states];
+                    for (from, _, table) in &tables {
+                        outflow[*from] += table.get_rate(age, gender).max(0.0);
+                    }
+                    for (from, to, table) in &tables {
+                        let scale = if outflow[*from] > 1.0 { 1.0 / outflow[*from] } else { 1.0 };
+                        let moved = counts[*from] * table.get_rate(age, gender).max(0.0) * scale;
+                        if moved <= 0.0 {
+                            continue;
+                        }
+                        self.models[*from].add_count(age, gender, region_id, -moved);
+                        self.models[*to].add_count(age, gender, region_id, moved);
+                        transfers_out[*from] += moved;
+                        transfers_in[*to] += moved;
+                    }
+                }
+            }
+        }
+
+        // Exposures for the combined indicators, by region and state
+        let start_population: Vec<f64> = regions.iter()
+            .map(|region_id| self.models.iter().map(|model| self.region_total(model, region_id)).sum())
+            .collect();
+        let women: Vec<Vec<f64>> = regions.iter()
+            .map(|region_id| self.models.iter().map(|model| fertile_women(model, region_id)).collect())
+            .collect();
+
+        // Step 2: Project each state
+        let results: Vec<ProjectionYear> = self.models.iter_mut()
+            .map(|model| model.project_one_year(year, regions))
+            .collect();
+
+        // Step 3: Newborns to the birth state
+        if let Some(birth_state) = self.birth_state {
+            for state in (0..states).filter(|&state| state != birth_state) {
+                for region_id in regions {
+                    for gender in [Gender::Male, Gender::Female] {
+                        let newborns = self.models[state].get_count(0, gender, region_id);
+                        if newborns <= 0.0 {
+                            continue;
+                        }
+                        self.models[state].add_count(0, gender, region_id, -newborns);
+                        self.models[birth_state].add_count(0, gender, region_id, newborns);
+                        transfers_out[state] += newborns;
+                        transfers_in[birth_state] += newborns;
+                    }
+                }
+            }
+        }
+
+        // Step 4: Combined regions and totals
+        let region_years = regions.iter().enumerate().map(|(index, region_id)| {
+            let mut births = 0.0;
+            let mut deaths = 0.0;
+            let mut net_migration = 0.0;
+            let (mut internal_in, mut internal_out) = (0.0, 0.0);
+            let mut weighted_tfr = 0.0;
+            for (result, state_women) in results.iter().zip(&women[index]) {
+                if let Some(region) = result.regions.iter().find(|region| &region.region_id == region_id) {
+                    births += region.births;
+                    deaths += region.deaths;
+                    net_migration += region.net_migration;
+                    internal_in += region.internal_in;
+                    internal_out += region.internal_out;
+                    weighted_tfr += region.indicators.tfr * state_women;
+                }
+            }
+            let exposed: f64 = women[index].iter().sum();
+            let tfr = if exposed > 0.0 { weighted_tfr / exposed } else { 0.0 };
+            let counts: Vec<f64> = (0..=self.max_age)
+                .map(|age| self.models.iter()
+                    .map(|model| model.get_count(age, Gender::Male, region_id) + model.get_count(age, Gender::Female, region_id))
+                    .sum())
+                .collect();
+            RegionYear {
+                region_id: region_id.clone(),
+                total_population: counts.iter().sum(),
+                births,
+                deaths,
+                net_migration,
+                internal_in,
+                internal_out,
+                indicators: Indicators::compute(&counts, 1, start_population[index], births, deaths, tfr),
+                components: None,
+                audit: None,
+            }
+        }).collect();
+
+        let sum = |component: fn(&ProjectionYear) -> f64| results.iter().map(component).sum::<f64>();
+        let final_population: f64 = self.models.iter().map(|model| model.total_population()).sum();
+        let births = sum(|result| result.births);
+        let deaths = sum(|result| result.deaths);
+        let growth_rate = if initial_population > 0.0 {
+            ((final_population - initial_population) / initial_population) * 100.0
+        } else if final_population > 0.0 {
+            100.0
+        } else {
+            0.0
+        };
+        let total = ProjectionYear {
+            year,
+            total_population: final_population,
+            births,
+            deaths,
+            net_migration: sum(|result| result.net_migration),
+            internal_migration: sum(|result| result.internal_migration),
+            unmet_emigration: sum(|result| result.unmet_emigration),
+            natural_change: births - deaths,
+            growth_rate,
+            regions: region_years,
+        };
+
+        let states = self.state_ids.iter().zip(results).enumerate()
+            .map(|(index, (state_id, projection))| StateYear {
+                state_id: state_id.clone(),
+                transfers_in: transfers_in[index],
+                transfers_out: transfers_out[index],
+                projection,
+            })
+            .collect();
+
+        MultistateYear { total, states }
+    }
+
+    /// Population of one region in a state's model
+    fn region_total(&self, model: &CohortComponentModel, region_id: &str) -> f64 {
+        (0..=self.max_age)
+            .map(|age| model.get_count(age, Gender::Male, region_id) + model.get_count(age, Gender::Female, region_id))
+            .sum()
+    }
+}
+
+/// Women of reproductive age in one region of a state's model
+fn fertile_women(model: &CohortComponentModel, region_id: &str) -> f64 {
+    (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE)
+        .map(|age| model.get_count(age, Gender::Female, region_id))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn states() -> Vec<String> {
+        vec!["low".to_string(), "high".to_string()]
+    }
+
+    /// No deaths, 10% fertility at 30
+    fn template() -> CohortComponentModel {
+        let mut ccm = CohortComponentModel::new();
+        ccm.load_mortality_table(MortalityTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: (0..=120).map(|age| MortalityRate { age, male: 0.0, female: 0.0 }).collect(),
+        });
+        ccm.load_fertility_table(FertilityTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: vec![FertilityRate { age: 30, rate: 0.1 }],
+            sex_ratio_at_birth: 100.0,
+        });
+        ccm
+    }
+
+    fn cohort(age: u32, gender: Gender, count: f64) -> Cohort {
+        Cohort { age, gender, region_id: "CZ".to_string(), count }
+    }
+
+    fn transition(from: &str, to: &str, age: u32, rate: f64) -> TransitionTable {
+        TransitionTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            from_state: from.to_string(),
+            to_state: to.to_string(),
+            rates: vec![TransitionRate { age, male: rate, female: 0.0 }],
+        }
+    }
+
+    #[test]
+    fn test_transitions_move_persons_between_states() {
+        let mut model = MultistateModel::new(&states(), &template());
+        model.state_model_mut("low").unwrap().load_population(&[cohort(20, Gender::Male, 100.0)]);
+        model.load_transition_table(transition("low", "high", 20, 0.1)).unwrap();
+
+        let result = model.project_one_year(2024, &["CZ".to_string()]);
+
+        assert!((model.state_model("low").unwrap().get_count(21, Gender::Male, "CZ") - 90.0).abs() < 1e-9);
+        assert!((model.state_model("high").unwrap().get_count(21, Gender::Male, "CZ") - 10.0).abs() < 1e-9);
+        assert!((result.states[1].transfers_in - 10.0).abs() < 1e-9);
+        assert!((result.states[0].transfers_out - 10.0).abs() < 1e-9);
+        assert!((result.total.total_population - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_outflows_above_one_are_scaled() {
+        let states = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut model = MultistateModel::new(&states, &template());
+        model.state_model_mut("a").unwrap().load_population(&[cohort(20, Gender::Male, 90.0)]);
+        model.load_transition_table(transition("a", "b", 20, 1.0)).unwrap();
+        model.load_transition_table(transition("a", "c", 20, 0.5)).unwrap();
+
+        model.project_one_year(2024, &["CZ".to_string()]);
+
+        // Everyone leaves, split 2:1
+        assert_eq!(model.state_model("a").unwrap().get_count(21, Gender::Male, "CZ"), 0.0);
+        assert!((model.state_model("b").unwrap().get_count(21, Gender::Male, "CZ") - 60.0).abs() < 1e-9);
+        assert!((model.state_model("c").unwrap().get_count(21, Gender::Male, "CZ") - 30.0).abs() < 1e-9);
+        assert!(model.load_transition_table(transition("a", "a", 20, 0.1)).is_err());
+        assert!(model.load_transition_table(transition("a", "x", 20, 0.1)).is_err());
+    }
+
+    #[test]
+    fn test_differential_fertility_and_birth_state() {
+        // Women in the high state have half the fertility, all children start low
+        let mut model = MultistateModel::new(&states(), &template());
+        model.state_model_mut("low").unwrap().load_population(&[cohort(30, Gender::Female, 100.0)]);
+        let high = model.state_model_mut("high").unwrap();
+        high.load_population(&[cohort(30, Gender::Female, 100.0)]);
+        high.set_rate_adjustment("CZ", RateAdjustment { fertility: 0.5, ..RateAdjustment::default() });
+        model.set_birth_state("low").unwrap();
+
+        let result = model.project_one_year(2024, &["CZ".to_string()]);
+
+        assert!((result.total.births - 15.0).abs() < 1e-9);
+        assert!((result.states[1].projection.births - 5.0).abs() < 1e-9);
+        let newborns = |state| {
+            let model = model.state_model(state).unwrap();
+            model.get_count(0, Gender::Male, "CZ") + model.get_count(0, Gender::Female, "CZ")
+        };
+        assert!((newborns("low") - 15.0).abs() < 1e-9);
+        assert_eq!(newborns("high"), 0.0);
+        assert!((result.states[0].transfers_in - 5.0).abs() < 1e-9);
+        // TFR weighted by women of both states
+        assert!((result.total.regions[0].indicators.tfr - 0.075).abs() < 1e-12);
+    }
+}
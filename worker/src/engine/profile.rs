@@ -0,0 +1,91 @@
+//! Age Profiles and Target Paths
+//!
+//! Helpers shared by the models built on the projection: step lookups in
+//! age schedules, target paths given as (year, value) points and the
+//! working-age bound.
+
+use serde::{Deserialize, Serialize};
+
+use super::series::AgeSexRow;
+use super::types::*;
+
+/// Lower bound of the working ages
+pub(super) const WORKING_AGE: u32 = 15;
+
+/// Profile value per person of one age and sex
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProfileValue {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+impl AgeSexRow for ProfileValue {
+    fn new(age: u32, male: f64, female: f64) -> Self {
+        Self { age, male, female }
+    }
+
+    fn age(&self) -> u32 {
+        self.age
+    }
+
+    fn value(&self, gender: Gender) -> f64 {
+        match gender {
+            Gender::Male => self.male,
+            Gender::Female => self.female,
+        }
+    }
+}
+
+/// Value of the row applying at an age, where a row applies from its age up
+/// to the next row; 0 below the first row
+pub(super) fn step_value<R: AgeSexRow>(rows: &[R], age: u32, gender: Gender) -> f64 {
+    rows.iter()
+        .filter(|row| row.age() <= age)
+        .max_by_key(|row| row.age())
+        .map(|row| row.value(gender))
+        .unwrap_or(0.0)
+}
+
+/// Linear interpolation along (year, value) points sorted by year, held
+/// before the first and after the last; None without points
+pub(super) fn path_value(points: &[(u32, f64)], year: u32) -> Option<f64> {
+    let next = points.iter().position(|&(point_year, _)| point_year >= year);
+    match next {
+        None => points.last().map(|&(_, value)| value),
+        Some(0) => Some(points[0].1),
+        Some(index) => {
+            let (start_year, start) = points[index - 1];
+            let (end_year, end) = points[index];
+            let weight = (year - start_year) as f64 / (end_year - start_year) as f64;
+            Some(start + (end - start) * weight)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_value_holds_rows_until_the_next() {
+        let rows = vec![
+            ProfileValue { age: 6, male: 1.0, female: 2.0 },
+            ProfileValue { age: 15, male: 3.0, female: 4.0 },
+        ];
+
+        assert_eq!(step_value(&rows, 5, Gender::Male), 0.0);
+        assert_eq!(step_value(&rows, 14, Gender::Female), 2.0);
+        assert_eq!(step_value(&rows, 90, Gender::Male), 3.0);
+    }
+
+    #[test]
+    fn test_path_value_interpolates_and_holds() {
+        let points = [(2020, 1.0), (2030, 2.0)];
+
+        assert_eq!(path_value(&points, 2015), Some(1.0));
+        assert_eq!(path_value(&points, 2025), Some(1.5));
+        assert_eq!(path_value(&points, 2040), Some(2.0));
+        assert_eq!(path_value(&[], 2025), None);
+    }
+}
@@ -4,78 +4,63 @@ use std::collections::HashMap;
 use chrono::Utc;
 use tracing::debug;
 
+use super::infant::{self, DEFAULT_SEPARATION_FACTOR};
+use super::series::{Interpolation, TableSeries};
+use super::state::PopulationState;
 use super::types::*;
 
 /// Demographic Engine implementing the Cohort-Component Method (CCM)
 pub struct DemographicEngine {
-    /// Current population by "age-gender-region" key
-    population: HashMap<String, f64>,
+    /// Current population by age, gender and region index
+    population: PopulationState,
     
-    /// Mortality tables by region ID
-    mortality_tables: HashMap<String, MortalityTable>,
+    /// Mortality tables by region ID, anchored by year
+    mortality_tables: HashMap<String, TableSeries<MortalityTable>>,
     
-    /// Fertility tables by region ID
-    fertility_tables: HashMap<String, FertilityTable>,
+    /// Fertility tables by region ID, anchored by year
+    fertility_tables: HashMap<String, TableSeries<FertilityTable>>,
     
     /// Active shocks
     shocks: Vec<Shock>,
+    
+    /// Share of infant deaths occurring in the calendar year of birth
+    infant_separation_factor: f64,
 }
 
 impl DemographicEngine {
     /// Create a new demographic engine
     pub fn new() -> Self {
         Self {
-            population: HashMap::new(),
+            population: PopulationState::new(120),
             mortality_tables: HashMap::new(),
             fertility_tables: HashMap::new(),
             shocks: Vec::new(),
+            infant_separation_factor: DEFAULT_SEPARATION_FACTOR,
         }
     }
     
-    /// Generate a cohort key
-    fn cohort_key(age: u32, gender: Gender, region_id: &str) -> String {
-        let gender_str = match gender {
-            Gender::Male => "male",
-            Gender::Female => "female",
-        };
-        format!("{}-{}-{}", age, gender_str, region_id)
-    }
-    
-    /// Parse a cohort key
-    fn parse_key(key: &str) -> Option<(u32, Gender, String)> {
-        let parts: Vec<&str> = key.splitn(3, '-').collect();
-        if parts.len() != 3 {
-            return None;
-        }
-        
-        let age: u32 = parts[0].parse().ok()?;
-        let gender = match parts[1] {
-            "male" => Gender::Male,
-            "female" => Gender::Female,
-            _ => return None,
-        };
-        let region_id = parts[2].to_string();
-        
-        Some((age, gender, region_id))
-    }
-    
     /// Load initial population from cohorts
     pub fn load_population(&mut self, cohorts: &[Cohort]) {
         self.population.clear();
         for cohort in cohorts {
-            let key = Self::cohort_key(cohort.age, cohort.gender, &cohort.region_id);
-            self.population.insert(key, cohort.count);
+            let region = self.population.add_region(&cohort.region_id);
+            self.population.add(cohort.age, cohort.gender, region, cohort.count);
         }
     }
     
-    /// Load mortality table for a region
+    /// Load mortality table for a region and year
     pub fn load_mortality_table(&mut self, table: MortalityTable) {
-        self.mortality_tables.insert(table.region_id.clone(), table);
+        self.mortality_tables.entry(table.region_id.clone()).or_default().insert(table);
     }
     
-    /// Load fertility table for a region
+    /// Load fertility table for a region and year
     pub fn load_fertility_table(&mut self, table: FertilityTable) {
-        self.fertility_tables.insert(table.region_id.clone(), table);
+        self.fertility_tables.entry(table.region_id.clone()).or_default().insert(table);
+    }
+    
+    /// Set the share of infant deaths occurring in the year of birth (0-1)
+    pub fn set_infant_separation_factor(&mut self, factor: f64) {
+        self.infant_separation_factor = factor.clamp(0.0, 1.0);
     }
     
     /// Add a shock modifier
@@ -90,8 +75,10 @@ impl DemographicEngine {
     
     /// Get current population count
     pub fn get_cohort_count(&self, age: u32, gender: Gender, region_id: &str) -> f64 {
-        let key = Self::cohort_key(age, gender, region_id);
-        self.population.get(&key).copied().unwrap_or(0.0)
+        self.population
+            .region_index(region_id)
+            .map(|region| self.population.get(age, gender, region))
+            .unwrap_or(0.0)
     }
     
     /// Apply shock modifiers to a base rate
@@ -103,8 +90,8 @@ impl DemographicEngine {
                 continue;
             }
             
-            if shock.applies_to(year, region_id, gender, age) {
-                value *= shock.modifier;
+            if shock.applies(year, age, gender, region_id) {
+                value = shock.modify(value, year, age);
             }
         }
         
@@ -116,12 +103,16 @@ impl DemographicEngine {
     pub fn project_year(&mut self, year: u32, region_ids: &[String]) -> ProjectionYear {
         let mut total_births = 0.0;
         let mut total_deaths = 0.0;
-        let mut new_population: HashMap<String, f64> = HashMap::new();
+        let region_indices: Vec<usize> = region_ids.iter()
+            .map(|region_id| self.population.add_region(region_id))
+            .collect();
+        let mut new_population = self.population.empty_like();
         
-        let prev_total: f64 = self.population.values().sum();
+        let prev_total = self.population.total();
         
-        for region_id in region_ids {
-            let mortality = match self.mortality_tables.get(region_id) {
+        for (region_id, &region) in region_ids.iter().zip(&region_indices) {
+            let mortality = match self.mortality_tables.get(region_id)
+                .and_then(|series| series.table_for(year, Interpolation::Linear)) {
                 Some(m) => m,
                 None => {
                     debug!("No mortality table for region {}, skipping", region_id);
@@ -129,7 +120,8 @@ impl DemographicEngine {
                 }
             };
             
-            let fertility = match self.fertility_tables.get(region_id) {
+            let fertility = match self.fertility_tables.get(region_id)
+                .and_then(|series| series.table_for(year, Interpolation::Linear)) {
                 Some(f) => f,
                 None => {
                     debug!("No fertility table for region {}, skipping", region_id);
@@ -137,11 +129,13 @@ impl DemographicEngine {
                 }
             };
             
+            // Births by sex, aged to the end of the year after the cohort loop
+            let mut region_births = [(Gender::Male, 0.0), (Gender::Female, 0.0)];
+            
             // Process each cohort
             for age in 0..=120 {
                 for gender in [Gender::Male, Gender::Female] {
-                    let key = Self::cohort_key(age, gender, region_id);
-                    let count = self.population.get(&key).copied().unwrap_or(0.0);
+                    let count = self.population.get(age, gender, region);
                     
                     if count < 0.001 {
                         continue;
@@ -158,16 +152,20 @@ impl DemographicEngine {
                         region_id,
                     );
                     
+                    // The age-0 population already lived through part of infancy
+                    let mortality_rate = if age == 0 {
+                        infant::remaining_infant_mortality(mortality_rate, self.infant_separation_factor)
+                    } else {
+                        mortality_rate
+                    };
+                    
                     // Calculate deaths and survivors
                     let deaths = count * mortality_rate;
                     let survivors = count - deaths;
                     total_deaths += deaths;
                     
-                    // Age the survivors (age + 1 next year)
-                    if age < 120 {
-                        let new_key = Self::cohort_key(age + 1, gender, region_id);
-                        *new_population.entry(new_key).or_insert(0.0) += survivors;
-                    }
+                    // Age the survivors (the open age group stays in place)
+                    new_population.add((age + 1).min(120), gender, region, survivors);
                     
                     // Calculate births (only from females of reproductive age)
                     if gender == Gender::Female && age >= 15 && age <= 49 {
@@ -186,23 +184,32 @@ impl DemographicEngine {
                         
                         // Distribute births by sex ratio
                         let male_ratio = fertility.sex_ratio_at_birth / (100.0 + fertility.sex_ratio_at_birth);
-                        let male_births = births * male_ratio;
-                        let female_births = births * (1.0 - male_ratio);
-                        
-                        let male_key = Self::cohort_key(0, Gender::Male, region_id);
-                        let female_key = Self::cohort_key(0, Gender::Female, region_id);
-                        
-                        *new_population.entry(male_key).or_insert(0.0) += male_births;
-                        *new_population.entry(female_key).or_insert(0.0) += female_births;
+                        region_births[0].1 += births * male_ratio;
+                        region_births[1].1 += births * (1.0 - male_ratio);
                     }
                 }
             }
+            
+            // Newborns surviving to the end of the year enter age 0
+            for (gender, born) in region_births {
+                let q0 = self.apply_shocks(
+                    ShockType::Mortality,
+                    mortality.get_rate(0, gender),
+                    year,
+                    0,
+                    gender,
+                    region_id,
+                );
+                let survivors = born * infant::birth_year_survival(q0, self.infant_separation_factor);
+                total_deaths += born - survivors;
+                new_population.add(0, gender, region, survivors);
+            }
         }
         
         // Update population
         self.population = new_population;
         
-        let new_total: f64 = self.population.values().sum();
+        let new_total = self.population.total();
         let natural_change = total_births - total_deaths;
         let growth_rate = if prev_total > 0.0 {
             ((new_total - prev_total) / prev_total) * 100.0
@@ -216,8 +223,11 @@ impl DemographicEngine {
             births: total_births,
             deaths: total_deaths,
             net_migration: 0.0, // TODO: Implement migration
+            internal_migration: 0.0,
+            unmet_emigration: 0.0,
             natural_change,
             growth_rate,
+            regions: Vec::new(),
         }
     }
     
@@ -280,19 +290,7 @@ impl DemographicEngine {
     
     /// Get current population as cohorts
     pub fn get_population(&self, scenario_id: &str, year: u32) -> Population {
-        let mut cohorts = Vec::new();
-        
-        for (key, &count) in &self.population {
-            if let Some((age, gender, region_id)) = Self::parse_key(key) {
-                cohorts.push(Cohort {
-                    age,
-                    gender,
-                    region_id,
-                    count,
-                });
-            }
-        }
-        
+        let cohorts = self.population.to_cohorts();
         let metadata = Population::calculate_metadata(&cohorts);
         
         Population {
@@ -313,12 +311,86 @@ impl Default for DemographicEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{AgeGroup, AgeTarget, Target};
+    
+    fn engine_with_infant_mortality(q0: f64) -> DemographicEngine {
+        let mut engine = DemographicEngine::new();
+        engine.load_population(&[
+            Cohort { age: 30, gender: Gender::Female, region_id: "CZ".to_string(), count: 1000.0 },
+        ]);
+        engine.load_mortality_table(MortalityTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: (0..=120).map(|age| {
+                let rate = if age == 0 { q0 } else { 0.0 };
+                MortalityRate { age, male: rate, female: rate }
+            }).collect(),
+        });
+        engine.load_fertility_table(FertilityTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: vec![FertilityRate { age: 30, rate: 0.1 }],
+            sex_ratio_at_birth: 100.0,
+        });
+        engine
+    }
+    
+    #[test]
+    fn test_newborns_face_infant_mortality() {
+        let mut engine = engine_with_infant_mortality(0.01);
+        engine.set_infant_separation_factor(0.8);
+        
+        let result = engine.project_year(2024, &["CZ".to_string()]);
+        
+        // 100 births, 100 × 0.8 × 0.01 = 0.8 die in the birth year
+        assert!((result.births - 100.0).abs() < 1e-9);
+        assert!((result.deaths - 0.8).abs() < 1e-9);
+        let newborns = engine.get_cohort_count(0, Gender::Male, "CZ")
+            + engine.get_cohort_count(0, Gender::Female, "CZ");
+        assert!((newborns - 99.2).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_infant_cohort_completes_infant_mortality() {
+        let mut engine = engine_with_infant_mortality(0.01);
+        
+        engine.project_year(2024, &["CZ".to_string()]);
+        let born = 100.0;
+        let age_0 = engine.get_cohort_count(0, Gender::Female, "CZ")
+            + engine.get_cohort_count(0, Gender::Male, "CZ");
+        engine.project_year(2025, &["CZ".to_string()]);
+        let age_1 = engine.get_cohort_count(1, Gender::Female, "CZ")
+            + engine.get_cohort_count(1, Gender::Male, "CZ");
+        
+        // Over both years the birth cohort loses exactly q0
+        assert!(age_0 < born);
+        assert!((age_1 - born * 0.99).abs() < 1e-9);
+    }
     
     #[test]
-    fn test_cohort_key_roundtrip() {
-        let key = DemographicEngine::cohort_key(25, Gender::Male, "CZ");
-        let parsed = DemographicEngine::parse_key(&key);
-        assert_eq!(parsed, Some((25, Gender::Male, "CZ".to_string())));
+    fn test_open_age_group_survives() {
+        let mut engine = DemographicEngine::new();
+        engine.load_population(&[
+            Cohort { age: 120, gender: Gender::Female, region_id: "CZ".to_string(), count: 10.0 },
+        ]);
+        engine.load_mortality_table(MortalityTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: vec![MortalityRate { age: 120, male: 0.5, female: 0.5 }],
+        });
+        engine.load_fertility_table(FertilityTable {
+            region_id: "CZ".to_string(),
+            year: 2024,
+            rates: Vec::new(),
+            sex_ratio_at_birth: 105.0,
+        });
+        
+        let result = engine.project_year(2024, &["CZ".to_string()]);
+        
+        // Survivors of 120+ stay in the open age group
+        assert!((result.deaths - 5.0).abs() < 1e-9);
+        assert!((engine.get_cohort_count(120, Gender::Female, "CZ") - 5.0).abs() < 1e-9);
+        assert!((result.total_population - 5.0).abs() < 1e-9);
     }
     
     #[test]
@@ -326,23 +398,24 @@ mod tests {
         let shock = Shock {
             id: "test".to_string(),
             name: "Test Shock".to_string(),
-            description: None,
+            description: String::new(),
             shock_type: ShockType::Mortality,
             start_year: 2025,
             end_year: 2026,
-            target_regions: vec![],
-            target_genders: vec![],
-            target_ages: Some(AgeGroup { min: 65, max: 120 }),
-            modifier: 1.5,
+            target_regions: Target::All,
+            target_genders: Target::All,
+            target_ages: AgeTarget::Range(AgeGroup { min: 65, max: 120 }),
+            modifier: ShockModifier::multiplier(1.5),
+            intensity_curve: None,
         };
         
         // Should apply: year in range, age in range
-        assert!(shock.applies_to(2025, "CZ", Gender::Male, 70));
+        assert!(shock.applies(2025, 70, Gender::Male, "CZ"));
         
         // Should not apply: year out of range
-        assert!(!shock.applies_to(2024, "CZ", Gender::Male, 70));
+        assert!(!shock.applies(2024, 70, Gender::Male, "CZ"));
         
         // Should not apply: age out of range
-        assert!(!shock.applies_to(2025, "CZ", Gender::Male, 30));
+        assert!(!shock.applies(2025, 30, Gender::Male, "CZ"));
     }
 }
@@ -0,0 +1,368 @@
+//! Time-Varying Rate Tables
+//!
+//! Rate tables are loaded as anchors for specific years. For each projected
+//! year the engine uses the anchor for that year, or derives a table from the
+//! two surrounding anchors. Years before the first anchor use the first table,
+//! years after the last anchor use the last table.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+
+/// How rates are derived between two anchor years
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interpolation {
+    /// Straight line between the surrounding anchors
+    #[default]
+    Linear,
+    /// Hold the most recent anchor until the next one
+    Constant,
+}
+
+/// A rate table anchored to a year that can be blended with a later anchor
+pub trait RateTable: Clone {
+    /// Anchor year of the table
+    fn year(&self) -> u32;
+
+    /// Linear blend of `self` and `next` for a year between their anchors
+    fn interpolate(&self, next: &Self, year: u32) -> Self;
+}
+
+/// Weight of the later anchor for a year between two anchors
+fn blend_weight(from: u32, to: u32, year: u32) -> f64 {
+    (year - from) as f64 / (to - from) as f64
+}
+
+fn lerp(from: f64, to: f64, weight: f64) -> f64 {
+    from + (to - from) * weight
+}
+
+/// Ages present in either of two rate lists
+fn union_ages(left: impl Iterator<Item = u32>, right: impl Iterator<Item = u32>) -> BTreeSet<u32> {
+    left.chain(right).collect()
+}
+
+/// Row of male and female values starting at an age
+pub(super) trait AgeSexRow {
+    fn new(age: u32, male: f64, female: f64) -> Self;
+    fn age(&self) -> u32;
+    fn value(&self, gender: Gender) -> f64;
+}
+
+macro_rules! age_sex_row {
+    ($($row:ty),* $(,)?) => {$(
+        impl AgeSexRow for $row {
+            fn new(age: u32, male: f64, female: f64) -> Self {
+                Self { age, male, female }
+            }
+
+            fn age(&self) -> u32 {
+                self.age
+            }
+
+            fn value(&self, gender: Gender) -> f64 {
+                match gender {
+                    Gender::Male => self.male,
+                    Gender::Female => self.female,
+                }
+            }
+        }
+    )*};
+}
+
+age_sex_row!(
+    MortalityRate,
+    MigrationRate,
+    EmigrationRate,
+    TransitionRate,
+    HeadshipRate,
+    ParticipationRate,
+    SurvivalRatio,
+);
+
+/// Rows for the ages of either table, blending the values each table gives
+/// for the age through `value` (so a table's own rule for ages it lacks applies)
+fn blend_rows<T, R: AgeSexRow>(
+    from: &T,
+    to: &T,
+    weight: f64,
+    rows: impl Fn(&T) -> &[R],
+    value: impl Fn(&T, u32, Gender) -> f64,
+) -> Vec<R> {
+    let ages = union_ages(rows(from).iter().map(R::age), rows(to).iter().map(R::age));
+    ages.into_iter().map(|age| R::new(
+        age,
+        lerp(value(from, age, Gender::Male), value(to, age, Gender::Male), weight),
+        lerp(value(from, age, Gender::Female), value(to, age, Gender::Female), weight),
+    )).collect()
+}
+
+/// Implements [`RateTable`] for a table of `rates` by age and sex read with
+/// `$value`; the listed fields are kept from the earlier anchor
+macro_rules! sex_rate_table {
+    ($table:ident, $value:path $(, $field:ident)*) => {
+        impl RateTable for $table {
+            fn year(&self) -> u32 {
+                self.year
+            }
+
+            fn interpolate(&self, next: &Self, year: u32) -> Self {
+                $table {
+                    region_id: self.region_id.clone(),
+                    year,
+                    $($field: self.$field.clone(),)*
+                    rates: blend_rows(self, next, blend_weight(self.year, next.year, year), |table| &table.rates, $value),
+                }
+            }
+        }
+    };
+}
+
+// Ages above a mortality table's last row keep its rate, rather than the
+// certain death `get_rate` gives for missing ages
+sex_rate_table!(MortalityTable, MortalityTable::get_rate_or_last);
+sex_rate_table!(MigrationTable, MigrationTable::get_rate);
+sex_rate_table!(EmigrationTable, EmigrationTable::get_rate);
+sex_rate_table!(TransitionTable, TransitionTable::get_rate, from_state, to_state);
+sex_rate_table!(HeadshipTable, HeadshipTable::get_rate, household_type);
+sex_rate_table!(ParticipationTable, ParticipationTable::get_rate);
+
+impl RateTable for FertilityTable {
+    fn year(&self) -> u32 {
+        self.year
+    }
+
+    fn interpolate(&self, next: &Self, year: u32) -> Self {
+        let weight = blend_weight(self.year, next.year, year);
+        let ages = union_ages(self.rates.iter().map(|r| r.age), next.rates.iter().map(|r| r.age));
+        FertilityTable {
+            region_id: self.region_id.clone(),
+            year,
+            rates: ages.into_iter().map(|age| FertilityRate {
+                age,
+                rate: lerp(self.get_rate(age), next.get_rate(age), weight),
+            }).collect(),
+            sex_ratio_at_birth: lerp(self.sex_ratio_at_birth, next.sex_ratio_at_birth, weight),
+        }
+    }
+}
+
+impl RateTable for SurvivalTable {
+    fn year(&self) -> u32 {
+        self.year
+    }
+
+    fn interpolate(&self, next: &Self, year: u32) -> Self {
+        let weight = blend_weight(self.year, next.year, year);
+        SurvivalTable {
+            region_id: self.region_id.clone(),
+            year,
+            ratios: blend_rows(self, next, weight, |table| &table.ratios, SurvivalTable::get_ratio),
+            birth_male: lerp(self.birth_male, next.birth_male, weight),
+            birth_female: lerp(self.birth_female, next.birth_female, weight),
+        }
+    }
+}
+
+/// Origin, destination and age of an interregional flow
+type FlowKey<'a> = (&'a str, &'a str, u32);
+
+impl RateTable for InterregionalMigrationTable {
+    fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// Blends every origin → destination pair and age of either matrix; a
+    /// flow missing from one matrix has rate 0 there
+    fn interpolate(&self, next: &Self, year: u32) -> Self {
+        let weight = blend_weight(self.year, next.year, year);
+        // (origin, destination, age) → (male, female) rates of both matrices
+        let mut flows: BTreeMap<FlowKey, [(f64, f64); 2]> = BTreeMap::new();
+        for (index, table) in [self, next].into_iter().enumerate() {
+            for flow in &table.flows {
+                let key = (flow.origin_region_id.as_str(), flow.destination_region_id.as_str(), flow.age);
+                flows.entry(key).or_default()[index] = (flow.male, flow.female);
+            }
+        }
+        InterregionalMigrationTable {
+            year,
+            flows: flows.into_iter().map(|((origin, destination, age), [from, to])| InterregionalFlow {
+                origin_region_id: origin.to_string(),
+                destination_region_id: destination.to_string(),
+                age,
+                male: lerp(from.0, to.0, weight),
+                female: lerp(from.1, to.1, weight),
+            }).collect(),
+        }
+    }
+}
+
+/// Anchor tables for one region, ordered by year
+#[derive(Debug, Clone)]
+pub struct TableSeries<T> {
+    anchors: Vec<T>,
+}
+
+impl<T: RateTable> TableSeries<T> {
+    /// Create an empty series
+    pub fn new() -> Self {
+        Self { anchors: Vec::new() }
+    }
+
+    /// Add an anchor table, replacing any anchor for the same year
+    pub fn insert(&mut self, table: T) {
+        match self.anchors.binary_search_by_key(&table.year(), |t| t.year()) {
+            Ok(index) => self.anchors[index] = table,
+            Err(index) => self.anchors.insert(index, table),
+        }
+    }
+
+    /// Table in effect for a year
+    ///
+    /// Returns a borrowed anchor when no blending is needed.
+    pub fn table_for(&self, year: u32, interpolation: Interpolation) -> Option<Cow<'_, T>> {
+        let first = self.anchors.first()?;
+        let last = self.anchors.last()?;
+        if year <= first.year() {
+            return Some(Cow::Borrowed(first));
+        }
+        if year >= last.year() {
+            return Some(Cow::Borrowed(last));
+        }
+
+        // First anchor strictly after `year`; the one before it is at or before `year`
+        let next_index = self.anchors.partition_point(|t| t.year() <= year);
+        let previous = &self.anchors[next_index - 1];
+        let next = &self.anchors[next_index];
+
+        if previous.year() == year || interpolation == Interpolation::Constant {
+            return Some(Cow::Borrowed(previous));
+        }
+        Some(Cow::Owned(previous.interpolate(next, year)))
+    }
+}
+
+impl<T: RateTable> Default for TableSeries<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mortality(year: u32, rate: f64) -> MortalityTable {
+        MortalityTable {
+            region_id: "CZ".to_string(),
+            year,
+            rates: vec![MortalityRate { age: 50, male: rate, female: rate / 2.0 }],
+        }
+    }
+
+    #[test]
+    fn test_single_anchor_used_for_all_years() {
+        let mut series = TableSeries::new();
+        series.insert(mortality(2024, 0.01));
+
+        for year in [2000, 2024, 2070] {
+            let table = series.table_for(year, Interpolation::Linear).unwrap();
+            assert_eq!(table.get_rate(50, Gender::Male), 0.01);
+        }
+    }
+
+    #[test]
+    fn test_linear_interpolation_between_anchors() {
+        let mut series = TableSeries::new();
+        series.insert(mortality(2030, 0.02));
+        series.insert(mortality(2020, 0.01));
+
+        let table = series.table_for(2025, Interpolation::Linear).unwrap();
+
+        assert_eq!(table.year, 2025);
+        assert!((table.get_rate(50, Gender::Male) - 0.015).abs() < 1e-12);
+        assert!((table.get_rate(50, Gender::Female) - 0.0075).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_constant_holds_previous_anchor() {
+        let mut series = TableSeries::new();
+        series.insert(mortality(2020, 0.01));
+        series.insert(mortality(2030, 0.02));
+
+        let table = series.table_for(2029, Interpolation::Constant).unwrap();
+
+        assert_eq!(table.get_rate(50, Gender::Male), 0.01);
+    }
+
+    #[test]
+    fn test_years_outside_anchors_are_held() {
+        let mut series = TableSeries::new();
+        series.insert(mortality(2020, 0.01));
+        series.insert(mortality(2030, 0.02));
+
+        assert_eq!(series.table_for(2010, Interpolation::Linear).unwrap().get_rate(50, Gender::Male), 0.01);
+        assert_eq!(series.table_for(2050, Interpolation::Linear).unwrap().get_rate(50, Gender::Male), 0.02);
+    }
+
+    #[test]
+    fn test_insert_replaces_same_year() {
+        let mut series = TableSeries::new();
+        series.insert(mortality(2020, 0.01));
+        series.insert(mortality(2020, 0.05));
+
+        assert_eq!(series.table_for(2020, Interpolation::Linear).unwrap().get_rate(50, Gender::Male), 0.05);
+    }
+
+    #[test]
+    fn test_fertility_interpolates_union_of_ages() {
+        let mut series = TableSeries::new();
+        series.insert(FertilityTable {
+            region_id: "CZ".to_string(),
+            year: 2020,
+            rates: vec![FertilityRate { age: 25, rate: 0.1 }],
+            sex_ratio_at_birth: 104.0,
+        });
+        series.insert(FertilityTable {
+            region_id: "CZ".to_string(),
+            year: 2040,
+            rates: vec![FertilityRate { age: 35, rate: 0.1 }],
+            sex_ratio_at_birth: 106.0,
+        });
+
+        let table = series.table_for(2030, Interpolation::Linear).unwrap();
+
+        assert!((table.get_rate(25) - 0.05).abs() < 1e-12);
+        assert!((table.get_rate(35) - 0.05).abs() < 1e-12);
+        assert!((table.sex_ratio_at_birth - 105.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mortality_beyond_shorter_anchor_keeps_last_rate() {
+        let anchor = |year: u32, last_age: u32, rate: f64| MortalityTable {
+            region_id: "CZ".to_string(),
+            year,
+            rates: (50..=last_age).map(|age| MortalityRate { age, male: rate, female: rate }).collect(),
+        };
+        let mut series = TableSeries::new();
+        series.insert(anchor(2020, 100, 0.1));
+        series.insert(anchor(2030, 90, 0.2));
+
+        let table = series.table_for(2025, Interpolation::Linear).unwrap();
+
+        // Age 95 blends 0.1 with the 2030 table's rate at 90, not with 1.0
+        assert!((table.get_rate(95, Gender::Male) - 0.15).abs() < 1e-12);
+        assert!((table.get_rate(100, Gender::Female) - 0.15).abs() < 1e-12);
+        assert!((table.get_rate(70, Gender::Male) - 0.15).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_empty_series_has_no_table() {
+        let series: TableSeries<MigrationTable> = TableSeries::new();
+        assert!(series.table_for(2024, Interpolation::Linear).is_none());
+    }
+}
@@ -0,0 +1,173 @@
+//! Service Demand Projections
+//!
+//! Multiplies populations by named age profiles: demand for a service in a
+//! region is the sum over ages and sexes of P(x, s) times the profile value
+//! u(x, s). Profiles can hold enrollment rates, bed-days per capita,
+//! care uptake or any other per-person quantity.
+//!
+//! - A profile row applies from its age up to the next row, so profiles by
+//!   age group work with single-year populations; ages below the first row
+//!   have no demand
+//! - Profiles apply to every region and year
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::profile::{step_value, ProfileValue};
+use super::types::*;
+
+/// Named age profile, e.g. school enrollment rates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DemandProfile {
+    pub name: String,
+    /// Unit of the demand, e.g. "pupils" or "bed-days"
+    #[serde(default)]
+    pub unit: Option<String>,
+    pub values: Vec<ProfileValue>,
+}
+
+impl DemandProfile {
+    /// Value of the row applying at an age; 0 below the first row
+    pub fn get_value(&self, age: u32, gender: Gender) -> f64 {
+        step_value(&self.values, age, gender)
+    }
+}
+
+/// Demand of one profile in one region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionDemand {
+    pub region_id: String,
+    pub demand: f64,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Demand of one profile in a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDemand {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub total: f64,
+    /// Regions in alphabetical order
+    pub regions: Vec<RegionDemand>,
+}
+
+/// Projected service demand in a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceDemandYear {
+    pub year: u32,
+    /// Profiles in registration order
+    pub profiles: Vec<ProfileDemand>,
+}
+
+/// Registered age profiles
+#[derive(Debug, Clone, Default)]
+pub struct DemandModel {
+    profiles: Vec<DemandProfile>,
+}
+
+impl DemandModel {
+    /// Create a model without profiles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a profile, replacing one with the same name
+    pub fn register(&mut self, profile: DemandProfile) {
+        match self.profiles.iter_mut().find(|other| other.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    /// Demand of every profile for the cohorts of a year
+    pub fn project(&self, cohorts: &[Cohort], year: u32) -> ServiceDemandYear {
+        let profiles = self.profiles.iter().map(|profile| {
+            let mut regions: BTreeMap<&str, RegionDemand> = BTreeMap::new();
+            for cohort in cohorts {
+                let demand = cohort.count * profile.get_value(cohort.age, cohort.gender);
+                let region = regions.entry(&cohort.region_id).or_insert_with(|| RegionDemand {
+                    region_id: cohort.region_id.clone(),
+                    demand: 0.0,
+                    male: 0.0,
+                    female: 0.0,
+                });
+                match cohort.gender {
+                    Gender::Male => region.male += demand,
+                    Gender::Female => region.female += demand,
+                }
+                region.demand += demand;
+            }
+            ProfileDemand {
+                name: profile.name.clone(),
+                unit: profile.unit.clone(),
+                total: regions.values().map(|region| region.demand).sum(),
+                regions: regions.into_values().collect(),
+            }
+        }).collect();
+
+        ServiceDemandYear { year, profiles }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cohort(age: u32, gender: Gender, region_id: &str, count: f64) -> Cohort {
+        Cohort { age, gender, region_id: region_id.to_string(), count }
+    }
+
+    fn profile(name: &str, values: Vec<ProfileValue>) -> DemandProfile {
+        DemandProfile { name: name.to_string(), unit: None, values }
+    }
+
+    #[test]
+    fn test_demand_by_profile_and_region() {
+        let mut model = DemandModel::new();
+        // Primary school 6-14, bed-days rising after 65
+        model.register(profile("pupils", vec![
+            ProfileValue { age: 6, male: 0.98, female: 0.99 },
+            ProfileValue { age: 15, male: 0.0, female: 0.0 },
+        ]));
+        model.register(profile("bedDays", vec![
+            ProfileValue { age: 0, male: 0.5, female: 0.4 },
+            ProfileValue { age: 65, male: 3.0, female: 2.5 },
+        ]));
+        let cohorts = vec![
+            cohort(10, Gender::Male, "SK", 100.0),
+            cohort(10, Gender::Female, "CZ", 100.0),
+            cohort(70, Gender::Female, "CZ", 40.0),
+        ];
+
+        let result = model.project(&cohorts, 2030);
+
+        assert_eq!(result.year, 2030);
+        let pupils = &result.profiles[0];
+        assert_eq!(pupils.name, "pupils");
+        assert!((pupils.total - 197.0).abs() < 1e-9);
+        assert_eq!(pupils.regions.iter().map(|r| r.region_id.as_str()).collect::<Vec<_>>(), vec!["CZ", "SK"]);
+        assert!((pupils.regions[1].male - 98.0).abs() < 1e-9);
+        let bed_days = &result.profiles[1];
+        assert!((bed_days.regions[0].demand - (40.0 + 100.0)).abs() < 1e-9);
+        assert!((bed_days.total - 190.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_register_replaces_profile_of_same_name() {
+        let mut model = DemandModel::new();
+        model.register(profile("care", vec![ProfileValue { age: 80, male: 0.1, female: 0.1 }]));
+        model.register(profile("care", vec![ProfileValue { age: 80, male: 0.2, female: 0.3 }]));
+
+        let result = model.project(&[cohort(85, Gender::Female, "CZ", 100.0), cohort(50, Gender::Male, "CZ", 100.0)], 2024);
+
+        assert_eq!(result.profiles.len(), 1);
+        assert!((result.profiles[0].total - 30.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,212 @@
+//! Dense Population State
+//!
+//! Population counts stored as a flat age × sex × region tensor.
+//! Regions are addressed by index, so the projection loop never
+//! allocates or hashes string keys per cohort.
+//!
+//! Layout: `counts[(region * 2 + sex) * (max_age + 1) + age]`
+
+use std::collections::HashMap;
+
+use super::types::*;
+
+/// Number of sexes stored per region
+const SEXES: usize = 2;
+
+/// Index of a gender along the sex axis
+pub(super) fn gender_index(gender: Gender) -> usize {
+    match gender {
+        Gender::Male => 0,
+        Gender::Female => 1,
+    }
+}
+
+/// Gender at an index along the sex axis
+fn gender_at(index: usize) -> Gender {
+    if index == 0 { Gender::Male } else { Gender::Female }
+}
+
+/// Population counts by age, sex and region index
+#[derive(Debug, Clone)]
+pub struct PopulationState {
+    /// Last age group (open-ended interval)
+    max_age: u32,
+
+    /// Region IDs in index order
+    region_ids: Vec<String>,
+
+    /// Region ID → index
+    region_index: HashMap<String, usize>,
+
+    /// Flat tensor of counts
+    counts: Vec<f64>,
+}
+
+impl PopulationState {
+    /// Create an empty state with ages 0..=max_age
+    pub fn new(max_age: u32) -> Self {
+        Self {
+            max_age,
+            region_ids: Vec::new(),
+            region_index: HashMap::new(),
+            counts: Vec::new(),
+        }
+    }
+
+    /// Create a zero-filled state with the same regions and ages
+    pub fn empty_like(&self) -> Self {
+        Self {
+            max_age: self.max_age,
+            region_ids: self.region_ids.clone(),
+            region_index: self.region_index.clone(),
+            counts: vec![0.0; self.counts.len()],
+        }
+    }
+
+    /// Remove all regions and counts
+    pub fn clear(&mut self) {
+        self.region_ids.clear();
+        self.region_index.clear();
+        self.counts.clear();
+    }
+
+    /// Number of age groups per sex and region
+    fn ages(&self) -> usize {
+        self.max_age as usize + 1
+    }
+
+    /// Number of registered regions
+    pub fn region_count(&self) -> usize {
+        self.region_ids.len()
+    }
+
+    /// Index of a region, if registered
+    pub fn region_index(&self, region_id: &str) -> Option<usize> {
+        self.region_index.get(region_id).copied()
+    }
+
+    /// Register a region (no-op if present) and return its index
+    pub fn add_region(&mut self, region_id: &str) -> usize {
+        if let Some(index) = self.region_index(region_id) {
+            return index;
+        }
+        let index = self.region_ids.len();
+        self.region_ids.push(region_id.to_string());
+        self.region_index.insert(region_id.to_string(), index);
+        self.counts.resize(self.counts.len() + SEXES * self.ages(), 0.0);
+        index
+    }
+
+    /// Flat offset of a cohort; ages above max_age fall into the open interval
+    fn offset(&self, age: u32, gender: Gender, region: usize) -> usize {
+        let age = age.min(self.max_age) as usize;
+        (region * SEXES + gender_index(gender)) * self.ages() + age
+    }
+
+    /// Count for a cohort
+    pub fn get(&self, age: u32, gender: Gender, region: usize) -> f64 {
+        if age > self.max_age || region >= self.region_count() {
+            return 0.0;
+        }
+        self.counts[self.offset(age, gender, region)]
+    }
+
+    /// Add to a cohort count
+    pub fn add(&mut self, age: u32, gender: Gender, region: usize, count: f64) {
+        let offset = self.offset(age, gender, region);
+        self.counts[offset] += count;
+    }
+
+    /// Total population across all cohorts
+    pub fn total(&self) -> f64 {
+        self.counts.iter().sum()
+    }
+
+    /// Non-empty cohorts in region, sex, age order
+    pub fn to_cohorts(&self) -> Vec<Cohort> {
+        let ages = self.ages();
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0.0)
+            .map(|(offset, &count)| {
+                let age = (offset % ages) as u32;
+                let sex = (offset / ages) % SEXES;
+                let region = offset / (ages * SEXES);
+                Cohort {
+                    age,
+                    gender: gender_at(sex),
+                    region_id: self.region_ids[region].clone(),
+                    count,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_get() {
+        let mut state = PopulationState::new(120);
+        let cz = state.add_region("CZ");
+        state.add(25, Gender::Male, cz, 10.0);
+        state.add(25, Gender::Male, cz, 5.0);
+
+        assert_eq!(state.get(25, Gender::Male, cz), 15.0);
+        assert_eq!(state.get(25, Gender::Female, cz), 0.0);
+        assert_eq!(state.total(), 15.0);
+    }
+
+    #[test]
+    fn test_add_region_is_idempotent() {
+        let mut state = PopulationState::new(120);
+        let a = state.add_region("A");
+        let b = state.add_region("B");
+
+        assert_eq!(state.add_region("A"), a);
+        assert_ne!(a, b);
+        assert_eq!(state.region_count(), 2);
+        assert_eq!(state.region_index("B"), Some(b));
+    }
+
+    #[test]
+    fn test_ages_above_max_fold_into_open_interval() {
+        let mut state = PopulationState::new(100);
+        let cz = state.add_region("CZ");
+        state.add(105, Gender::Female, cz, 3.0);
+
+        assert_eq!(state.get(100, Gender::Female, cz), 3.0);
+        assert_eq!(state.get(105, Gender::Female, cz), 0.0);
+    }
+
+    #[test]
+    fn test_to_cohorts_roundtrip() {
+        let mut state = PopulationState::new(120);
+        let a = state.add_region("A");
+        let b = state.add_region("B");
+        state.add(0, Gender::Female, a, 1.0);
+        state.add(120, Gender::Male, b, 2.0);
+
+        let cohorts = state.to_cohorts();
+
+        assert_eq!(cohorts.len(), 2);
+        assert_eq!((cohorts[0].age, cohorts[0].gender, cohorts[0].region_id.as_str()), (0, Gender::Female, "A"));
+        assert_eq!((cohorts[1].age, cohorts[1].gender, cohorts[1].region_id.as_str()), (120, Gender::Male, "B"));
+    }
+
+    #[test]
+    fn test_empty_like_keeps_regions() {
+        let mut state = PopulationState::new(120);
+        let a = state.add_region("A");
+        state.add(10, Gender::Male, a, 7.0);
+
+        let empty = state.empty_like();
+
+        assert_eq!(empty.region_index("A"), Some(a));
+        assert_eq!(empty.total(), 0.0);
+        assert_eq!(state.total(), 7.0);
+    }
+}
@@ -0,0 +1,439 @@
+//! Stochastic Projections
+//!
+//! Monte Carlo simulation around a deterministic CCM scenario. Each
+//! simulation draws yearly error paths for the total fertility rate, life
+//! expectancy at birth and net migration, applies them as rate adjustments
+//! and projects the whole horizon. Simulations run in parallel and each is
+//! seeded from a hash of the user seed and its index, so results do not
+//! depend on thread scheduling and nearby seeds do not share paths.
+//!
+//! The TFR of a region sums its fertility rates over the reproductive ages
+//! the model applies (15-49).
+//!
+//! Errors are shared by all regions of a simulation:
+//! - **TFR**: additive (children per woman); rates scaled by (TFR + ε) / TFR
+//! - **e0**: additive (years); death probabilities scaled so e0 moves by ε
+//! - **Net migration**: multiplicative; migration scaled by exp(ε), which is
+//!   close to 1 + ε for small errors but never reaches zero or flips the sign
+//!   of the flows
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::ccm::{CohortComponentModel, FERTILITY_MAX_AGE, FERTILITY_MIN_AGE};
+use super::infant::coale_demeny_a0;
+use super::life_table::LifeTable;
+use super::types::*;
+
+/// Upper limit on simulations per run
+pub const MAX_SIMULATIONS: usize = 10_000;
+
+/// Bisection iterations when solving for a mortality multiplier
+const MORTALITY_ITERATIONS: usize = 50;
+
+/// Range of ln(multiplier) searched for a target e0
+const MAX_LOG_MORTALITY_FACTOR: f64 = 10.0;
+
+/// Golden-ratio increment of SplitMix64
+const SPLITMIX_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// How yearly errors evolve over the projection horizon
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ErrorProcess {
+    /// ε_t = ε_t-1 + σ·z: uncertainty grows without bound
+    #[default]
+    RandomWalk,
+    /// ε_t = φ·ε_t-1 + σ·z: errors revert towards the deterministic path
+    Autoregressive { phi: f64 },
+}
+
+/// Error model for one component
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorModel {
+    #[serde(default)]
+    pub process: ErrorProcess,
+    /// Standard deviation of the yearly innovation (0 = deterministic)
+    #[serde(default)]
+    pub sd: f64,
+}
+
+impl ErrorModel {
+    /// Error for the next year given the previous one
+    fn next(&self, previous: f64, rng: &mut StdRng) -> f64 {
+        if self.sd == 0.0 {
+            return previous;
+        }
+        let innovation = self.sd * standard_normal(rng);
+        match self.process {
+            ErrorProcess::RandomWalk => previous + innovation,
+            ErrorProcess::Autoregressive { phi } => phi * previous + innovation,
+        }
+    }
+
+    fn validate(&self, name: &str) -> Result<(), String> {
+        if !self.sd.is_finite() || self.sd < 0.0 {
+            return Err(format!("{} error standard deviation must be non-negative", name));
+        }
+        if let ErrorProcess::Autoregressive { phi } = self.process {
+            if !(-1.0..=1.0).contains(&phi) {
+                return Err(format!("{} autoregressive coefficient must be between -1 and 1", name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Settings for a stochastic run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StochasticSettings {
+    /// Number of simulated paths
+    pub simulations: usize,
+    /// Seed for reproducible paths
+    #[serde(default)]
+    pub seed: u64,
+    /// Total fertility rate error (children per woman)
+    #[serde(default)]
+    pub tfr: ErrorModel,
+    /// Life expectancy at birth error (years)
+    #[serde(default)]
+    pub e0: ErrorModel,
+    /// Net migration error (log of the migration multiplier)
+    #[serde(default)]
+    pub migration: ErrorModel,
+}
+
+impl StochasticSettings {
+    /// Check simulation count and error models
+    pub fn validate(&self) -> Result<(), String> {
+        if self.simulations == 0 || self.simulations > MAX_SIMULATIONS {
+            return Err(format!("Simulations must be between 1 and {}", MAX_SIMULATIONS));
+        }
+        self.tfr.validate("TFR")?;
+        self.e0.validate("e0")?;
+        self.migration.validate("Migration")
+    }
+}
+
+/// Median and prediction intervals of a simulated quantity
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Quantiles {
+    pub median: f64,
+    pub lower80: f64,
+    pub upper80: f64,
+    pub lower95: f64,
+    pub upper95: f64,
+}
+
+impl Quantiles {
+    /// Quantiles of a sample (linear interpolation between order statistics)
+    fn from_sample(mut values: Vec<f64>) -> Self {
+        values.sort_by(f64::total_cmp);
+        let at = |p: f64| {
+            if values.is_empty() {
+                return 0.0;
+            }
+            let position = p * (values.len() - 1) as f64;
+            let below = position.floor() as usize;
+            let above = position.ceil() as usize;
+            values[below] + (values[above] - values[below]) * (position - below as f64)
+        };
+        Self {
+            median: at(0.5),
+            lower80: at(0.1),
+            upper80: at(0.9),
+            lower95: at(0.025),
+            upper95: at(0.975),
+        }
+    }
+}
+
+/// Distribution of simulated outcomes for one projected year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StochasticYear {
+    pub year: u32,
+    pub total_population: Quantiles,
+    pub births: Quantiles,
+    pub deaths: Quantiles,
+    pub net_migration: Quantiles,
+    /// Total fertility rate (mean over regions)
+    pub tfr: Quantiles,
+    /// Life expectancy at birth, males (mean over regions)
+    pub e0_male: Quantiles,
+    /// Life expectancy at birth, females (mean over regions)
+    pub e0_female: Quantiles,
+}
+
+/// Simulated indicators in [`StochasticYear`] order
+const INDICATORS: usize = 7;
+
+/// Sexes in the order of [`RegionBase`] columns
+const SEXES: [Gender; 2] = [Gender::Male, Gender::Female];
+
+/// Deterministic rates of one region in one year
+struct RegionBase {
+    tfr: f64,
+    /// Death probabilities by age, [male, female]
+    qx: [Vec<f64>; 2],
+    /// Life expectancy at birth, [male, female]
+    e0: [f64; 2],
+}
+
+/// Run simulations and summarize each year from `base_year` to `end_year`
+///
+/// The model is cloned per simulation; it is not modified.
+pub fn run_stochastic(
+    model: &CohortComponentModel,
+    regions: &[String],
+    base_year: u32,
+    end_year: u32,
+    settings: &StochasticSettings,
+) -> Vec<StochasticYear> {
+    let years: Vec<u32> = (base_year..=end_year).collect();
+    let base: Vec<Vec<RegionBase>> = years.iter()
+        .map(|&year| regions.iter().map(|region| region_base(model, region, year)).collect())
+        .collect();
+
+    let simulations: Vec<Vec<[f64; INDICATORS]>> = (0..settings.simulations)
+        .into_par_iter()
+        .map(|simulation| simulate(model, regions, &years, &base, settings, simulation))
+        .collect();
+
+    years.iter().enumerate().map(|(index, &year)| {
+        let quantiles = |indicator: usize| {
+            Quantiles::from_sample(simulations.iter().map(|path| path[index][indicator]).collect())
+        };
+        StochasticYear {
+            year,
+            total_population: quantiles(0),
+            births: quantiles(1),
+            deaths: quantiles(2),
+            net_migration: quantiles(3),
+            tfr: quantiles(4),
+            e0_male: quantiles(5),
+            e0_female: quantiles(6),
+        }
+    }).collect()
+}
+
+/// Deterministic TFR and mortality of a region in a year
+fn region_base(model: &CohortComponentModel, region_id: &str, year: u32) -> RegionBase {
+    let tfr = model.fertility_table_for(region_id, year)
+        .map(|table| (FERTILITY_MIN_AGE..=FERTILITY_MAX_AGE).map(|age| table.get_rate(age)).sum())
+        .unwrap_or(0.0);
+    let mortality = model.mortality_table_for(region_id, year);
+    let qx = SEXES.map(|gender| {
+        (0..=model.open_age_group())
+            .map(|age| mortality.as_ref().map(|table| table.get_rate_or_last(age, gender)).unwrap_or(1.0))
+            .collect::<Vec<f64>>()
+    });
+    let e0 = [0, 1].map(|sex| {
+        LifeTable::from_probabilities(0, &qx[sex], SEXES[sex])
+            .map(|table| table.life_expectancy(0))
+            .unwrap_or(0.0)
+    });
+    RegionBase { tfr, qx, e0 }
+}
+
+/// SplitMix64 output mix
+fn splitmix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Seed of one simulation; the user seed is mixed before the index is
+/// added, so (seed, i + 1) and (seed + 1, i) draw unrelated paths
+fn simulation_seed(seed: u64, simulation: usize) -> u64 {
+    splitmix(splitmix(seed).wrapping_add((simulation as u64 + 1).wrapping_mul(SPLITMIX_GAMMA)))
+}
+
+/// Project one simulated path; returns indicators by year
+fn simulate(
+    model: &CohortComponentModel,
+    regions: &[String],
+    years: &[u32],
+    base: &[Vec<RegionBase>],
+    settings: &StochasticSettings,
+    simulation: usize,
+) -> Vec<[f64; INDICATORS]> {
+    let mut rng = StdRng::seed_from_u64(simulation_seed(settings.seed, simulation));
+    let mut model = model.clone();
+    let (mut tfr_error, mut e0_error, mut migration_error) = (0.0, 0.0, 0.0);
+    let region_count = regions.len().max(1) as f64;
+
+    years.iter().zip(base).map(|(&year, year_base)| {
+        tfr_error = settings.tfr.next(tfr_error, &mut rng);
+        e0_error = settings.e0.next(e0_error, &mut rng);
+        migration_error = settings.migration.next(migration_error, &mut rng);
+
+        let mut tfr = 0.0;
+        let mut e0 = [0.0; 2];
+        for (region_id, region) in regions.iter().zip(year_base) {
+            let fertility = if region.tfr > 0.0 {
+                ((region.tfr + tfr_error) / region.tfr).max(0.0)
+            } else {
+                1.0
+            };
+            let mortality = [0, 1].map(|sex| {
+                // Without a finite e0 there is no target to move
+                if e0_error == 0.0 || region.e0[sex] == 0.0 {
+                    1.0
+                } else {
+                    mortality_factor_for(&region.qx[sex], SEXES[sex], region.e0[sex] + e0_error)
+                }
+            });
+            model.set_rate_adjustment(region_id, RateAdjustment {
+                fertility,
+                male_mortality: mortality[0],
+                female_mortality: mortality[1],
+                migration: migration_error.exp(),
+            });
+
+            tfr += region.tfr * fertility;
+            for sex in 0..2 {
+                e0[sex] += life_expectancy_at_birth(&region.qx[sex], SEXES[sex], mortality[sex]);
+            }
+        }
+
+        let result = model.project_one_year(year, regions);
+        [
+            result.total_population,
+            result.births,
+            result.deaths,
+            result.net_migration,
+            tfr / region_count,
+            e0[0] / region_count,
+            e0[1] / region_count,
+        ]
+    }).collect()
+}
+
+/// Life expectancy at birth with death probabilities scaled by `factor`
+///
+/// Same construction as [`LifeTable`], without allocating; 0 where the
+/// table has no life expectancy (zero open-age probability).
+fn life_expectancy_at_birth(qx: &[f64], gender: Gender, factor: f64) -> f64 {
+    let Some((&last, younger)) = qx.split_last() else {
+        return 0.0;
+    };
+    let mut survivors = 1.0;
+    let mut person_years = 0.0;
+    for (age, q) in younger.iter().enumerate() {
+        let q = (q * factor).clamp(0.0, 1.0);
+        let separation = if age == 0 { coale_demeny_a0(q, gender) } else { 0.5 };
+        person_years += survivors * (1.0 - q * (1.0 - separation));
+        survivors *= 1.0 - q;
+    }
+    let last = (last * factor).clamp(0.0, 1.0);
+    if last <= 0.0 {
+        return 0.0;
+    }
+    person_years + survivors * (1.0 - last / 2.0) / last
+}
+
+/// Multiplier on death probabilities that gives life expectancy `target`
+fn mortality_factor_for(qx: &[f64], gender: Gender, target: f64) -> f64 {
+    // e0 decreases with the multiplier: bisect on its logarithm
+    let mut low = -MAX_LOG_MORTALITY_FACTOR;
+    let mut high = MAX_LOG_MORTALITY_FACTOR;
+    for _ in 0..MORTALITY_ITERATIONS {
+        let mid = (low + high) / 2.0;
+        if life_expectancy_at_birth(qx, gender, mid.exp()) > target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    ((low + high) / 2.0).exp()
+}
+
+/// Standard normal draw (Box–Muller)
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>(); // (0, 1], avoids ln(0)
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantiles_of_uniform_sample() {
+        let quantiles = Quantiles::from_sample((0..=100).map(f64::from).collect());
+
+        assert_eq!(quantiles.median, 50.0);
+        assert_eq!(quantiles.lower80, 10.0);
+        assert_eq!(quantiles.upper95, 97.5);
+    }
+
+    #[test]
+    fn test_life_expectancy_matches_life_table() {
+        let qx: Vec<f64> = (0..=100).map(|age| 0.0005 * 1.09_f64.powi(age)).collect();
+        for gender in SEXES {
+            let table = LifeTable::from_probabilities(0, &qx, gender).unwrap();
+
+            assert!((life_expectancy_at_birth(&qx, gender, 1.0) - table.life_expectancy(0)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_mortality_factor_reaches_target_e0() {
+        let qx: Vec<f64> = (0..=100).map(|age| 0.0005 * 1.09_f64.powi(age)).collect();
+        let base = life_expectancy_at_birth(&qx, Gender::Female, 1.0);
+
+        let factor = mortality_factor_for(&qx, Gender::Female, base + 2.0);
+
+        assert!(factor < 1.0);
+        assert!((life_expectancy_at_birth(&qx, Gender::Female, factor) - (base + 2.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_error_processes() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let none = ErrorModel::default();
+        assert_eq!(none.next(0.3, &mut rng), 0.3);
+
+        // φ = 0 forgets the previous error: same draws, different memory
+        let walk = ErrorModel { process: ErrorProcess::RandomWalk, sd: 1.0 };
+        let white = ErrorModel { process: ErrorProcess::Autoregressive { phi: 0.0 }, sd: 1.0 };
+        let walked = walk.next(5.0, &mut StdRng::seed_from_u64(1));
+        let reverted = white.next(5.0, &mut StdRng::seed_from_u64(1));
+        assert!((walked - reverted - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_simulation_seeds_do_not_overlap_across_seeds() {
+        let seeds: std::collections::HashSet<u64> = (0..100u64)
+            .flat_map(|seed| (0..100).map(move |simulation| simulation_seed(seed, simulation)))
+            .collect();
+
+        assert_eq!(seeds.len(), 100 * 100);
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let mut settings = StochasticSettings {
+            simulations: 100,
+            seed: 1,
+            tfr: ErrorModel { process: ErrorProcess::Autoregressive { phi: 0.9 }, sd: 0.1 },
+            e0: ErrorModel::default(),
+            migration: ErrorModel::default(),
+        };
+        assert!(settings.validate().is_ok());
+
+        settings.simulations = 0;
+        assert!(settings.validate().is_err());
+
+        settings.simulations = 10;
+        settings.e0.sd = -1.0;
+        assert!(settings.validate().unwrap_err().contains("e0"));
+    }
+}
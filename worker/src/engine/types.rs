@@ -4,30 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
-/// Gender enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum Gender {
-    Male,
-    Female,
-}
+use super::profile::step_value;
 
-/// Age group representation
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub struct AgeGroup {
-    pub min: u32,
-    pub max: u32,
-}
-
-impl AgeGroup {
-    pub fn all() -> Self {
-        Self { min: 0, max: 120 }
-    }
-    
-    pub fn contains(&self, age: u32) -> bool {
-        age >= self.min && age <= self.max
-    }
-}
+pub use crate::types::{Gender, Shock, ShockModifier, ShockType};
 
 /// Population cohort
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +102,22 @@ impl MortalityTable {
             })
             .unwrap_or(1.0) // Default: 100% mortality for undefined ages
     }
+
+    /// Last tabulated age, if any rates are defined
+    pub fn last_age(&self) -> Option<u32> {
+        self.rates.iter().map(|r| r.age).max()
+    }
+
+    /// Get mortality rate, holding the last tabulated rate for older ages
+    ///
+    /// Treats the last tabulated age as an open interval rather than
+    /// assuming everyone above it dies.
+    pub fn get_rate_or_last(&self, age: u32, gender: Gender) -> f64 {
+        match self.last_age() {
+            Some(last) if age > last => self.get_rate(last, gender),
+            _ => self.get_rate(age, gender),
+        }
+    }
 }
 
 /// Fertility rate by mother's age
@@ -184,77 +179,273 @@ impl MigrationTable {
     }
 }
 
-/// Shock type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum ShockType {
-    Mortality,
-    Fertility,
-    Migration,
+/// Emigration rates by age and gender
+/// Share of the cohort leaving the region during the year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmigrationRate {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
 }
 
-/// Target specification (all or specific values)
+/// Emigration rate table for a region and year
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum Target<T> {
-    All,
-    Specific(Vec<T>),
+#[serde(rename_all = "camelCase")]
+pub struct EmigrationTable {
+    pub region_id: String,
+    pub year: u32,
+    pub rates: Vec<EmigrationRate>,
+}
+
+impl EmigrationTable {
+    /// Get emigration rate for a specific age and gender
+    /// Returns 0 if no rate defined for that age
+    pub fn get_rate(&self, age: u32, gender: Gender) -> f64 {
+        self.rates.iter()
+            .find(|r| r.age == age)
+            .map(|r| match gender {
+                Gender::Male => r.male,
+                Gender::Female => r.female,
+            })
+            .unwrap_or(0.0)
+    }
 }
 
-/// Age target
+/// Transition rates by age and gender between two states of an extra
+/// population dimension (e.g. education levels)
+/// Share of the cohort in the origin state moving to the destination state
+/// during the year
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum AgeTarget {
-    All,
-    Range(AgeGroup),
+pub struct TransitionRate {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
 }
 
-/// Shock modifier
+/// Transition rate table from one state to another for a region and year
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Shock {
-    pub id: String,
-    pub name: String,
-    pub description: Option<String>,
-    #[serde(rename = "type")]
-    pub shock_type: ShockType,
-    pub start_year: u32,
-    pub end_year: u32,
-    pub target_regions: Vec<String>, // Empty = all
-    pub target_genders: Vec<Gender>, // Empty = all
-    pub target_ages: Option<AgeGroup>, // None = all
-    pub modifier: f64, // Multiplier
-}
-
-impl Shock {
-    /// Check if shock applies to given parameters
-    pub fn applies_to(&self, year: u32, region_id: &str, gender: Gender, age: u32) -> bool {
-        // Check year range
-        if year < self.start_year || year > self.end_year {
-            return false;
+pub struct TransitionTable {
+    pub region_id: String,
+    pub year: u32,
+    pub from_state: String,
+    pub to_state: String,
+    pub rates: Vec<TransitionRate>,
+}
+
+impl TransitionTable {
+    /// Get transition rate for a specific age and gender
+    /// Returns 0 if no rate defined for that age
+    pub fn get_rate(&self, age: u32, gender: Gender) -> f64 {
+        self.rates.iter()
+            .find(|r| r.age == age)
+            .map(|r| match gender {
+                Gender::Male => r.male,
+                Gender::Female => r.female,
+            })
+            .unwrap_or(0.0)
+    }
+}
+
+/// Headship rates by age and gender for one household type
+/// Share of persons who are the reference person of a household of the type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadshipRate {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Headship rate table of one household type for a region and year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadshipTable {
+    pub region_id: String,
+    pub year: u32,
+    pub household_type: String,
+    pub rates: Vec<HeadshipRate>,
+}
+
+impl HeadshipTable {
+    /// Get headship rate for a specific age and gender
+    /// A row applies from its age up to the next row; returns 0 below the first row
+    pub fn get_rate(&self, age: u32, gender: Gender) -> f64 {
+        step_value(&self.rates, age, gender)
+    }
+}
+
+/// Labor force participation rates by age and gender
+/// Share of persons who are employed or looking for work
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipationRate {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Participation rate table for a region and year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipationTable {
+    pub region_id: String,
+    pub year: u32,
+    pub rates: Vec<ParticipationRate>,
+}
+
+impl ParticipationTable {
+    /// Get participation rate for a specific age and gender
+    /// A row applies from its age up to the next row; returns 0 below the first row
+    pub fn get_rate(&self, age: u32, gender: Gender) -> f64 {
+        step_value(&self.rates, age, gender)
+    }
+}
+
+/// Five-year survival ratio by age group (5Lx+5 / 5Lx)
+/// Share of persons in the group starting at `age` alive five years later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurvivalRatio {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Survival ratios for an abridged (five-year) projection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SurvivalTable {
+    pub region_id: String,
+    pub year: u32,
+    pub ratios: Vec<SurvivalRatio>,
+    /// Share of male births in the step alive in the 0-4 group (5L0 / 5·l0)
+    pub birth_male: f64,
+    /// Share of female births in the step alive in the 0-4 group
+    pub birth_female: f64,
+}
+
+impl SurvivalTable {
+    /// Get the survival ratio for an age group, holding the last tabulated
+    /// ratio for older groups; 0 if no ratios are defined
+    pub fn get_ratio(&self, age: u32, gender: Gender) -> f64 {
+        let age = match self.ratios.iter().map(|r| r.age).max() {
+            Some(last) => age.min(last),
+            None => return 0.0,
+        };
+        self.ratios.iter()
+            .find(|r| r.age == age)
+            .map(|r| match gender {
+                Gender::Male => r.male,
+                Gender::Female => r.female,
+            })
+            .unwrap_or(0.0)
+    }
+
+    /// Get the survival of births to the 0-4 group
+    pub fn birth_survival(&self, gender: Gender) -> f64 {
+        match gender {
+            Gender::Male => self.birth_male,
+            Gender::Female => self.birth_female,
         }
-        
-        // Check region
-        if !self.target_regions.is_empty() && !self.target_regions.contains(&region_id.to_string()) {
-            return false;
+    }
+}
+
+/// When net migrants arrive within a one-year projection step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationTiming {
+    /// All migrants arrive at the start: full-year mortality and fertility exposure
+    #[default]
+    Start,
+    /// Half arrive at the start, half at the end of the year
+    MidPeriod,
+    /// All migrants arrive at the end: no mortality exposure, half the
+    /// fertility exposure of residents
+    End,
+}
+
+impl MigrationTiming {
+    /// Share of the year's net migrants applied at the start of the step
+    pub fn start_share(&self) -> f64 {
+        match self {
+            MigrationTiming::Start => 1.0,
+            MigrationTiming::MidPeriod => 0.5,
+            MigrationTiming::End => 0.0,
         }
-        
-        // Check gender
-        if !self.target_genders.is_empty() && !self.target_genders.contains(&gender) {
-            return false;
+    }
+}
+
+/// Female population used as exposure for age-specific fertility rates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FertilityExposure {
+    /// Women at age x after start-of-period migration, with end-of-period
+    /// migrants counted half
+    #[default]
+    Start,
+    /// Mean of women at age x at the start and at the end of the year
+    Average,
+}
+
+/// Multipliers applied to a region's rates in effect (e.g. stochastic paths)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateAdjustment {
+    pub fertility: f64,
+    pub male_mortality: f64,
+    pub female_mortality: f64,
+    pub migration: f64,
+}
+
+impl RateAdjustment {
+    /// Mortality multiplier for a gender
+    pub fn mortality(&self, gender: Gender) -> f64 {
+        match gender {
+            Gender::Male => self.male_mortality,
+            Gender::Female => self.female_mortality,
         }
-        
-        // Check age
-        if let Some(ref age_group) = self.target_ages {
-            if !age_group.contains(age) {
-                return false;
-            }
+    }
+}
+
+impl Default for RateAdjustment {
+    fn default() -> Self {
+        Self {
+            fertility: 1.0,
+            male_mortality: 1.0,
+            female_mortality: 1.0,
+            migration: 1.0,
+        }
+    }
+}
+
+/// Interregional migration rate by age and gender for one origin → destination pair
+/// Rates are the probability that a resident of the origin region moves to the
+/// destination region during the year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterregionalFlow {
+    pub origin_region_id: String,
+    pub destination_region_id: String,
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+impl InterregionalFlow {
+    /// Get the move rate for a gender
+    pub fn get_rate(&self, gender: Gender) -> f64 {
+        match gender {
+            Gender::Male => self.male,
+            Gender::Female => self.female,
         }
-        
-        true
     }
 }
 
+/// Origin → destination migration matrix between regions of one country
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterregionalMigrationTable {
+    pub year: u32,
+    pub flows: Vec<InterregionalFlow>,
+}
+
 /// Scenario definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -279,8 +470,203 @@ pub struct ProjectionYear {
     pub births: f64,
     pub deaths: f64,
     pub net_migration: f64,
+    /// Persons who moved between projected regions (nets to zero nationally)
+    #[serde(default)]
+    pub internal_migration: f64,
+    /// Emigration from net migration counts that exceeded the population
+    #[serde(default)]
+    pub unmet_emigration: f64,
     pub natural_change: f64,
     pub growth_rate: f64,
+    /// Components and indicators by projected region
+    #[serde(default)]
+    pub regions: Vec<RegionYear>,
+}
+
+/// Population estimated back one year by reverse survival
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackcastYear {
+    /// Year whose start-of-year population was estimated
+    pub year: u32,
+    pub total_population: f64,
+    /// Births implied by the estimated women during the year
+    pub births: f64,
+    pub deaths: f64,
+    pub net_migration: f64,
+    /// Observed age 0 at the end of the year minus newborns implied by `births`
+    pub newborn_discrepancy: f64,
+    /// Persons added where reversing migration left a cohort negative
+    pub clamped_population: f64,
+}
+
+/// Result of one state of an extra population dimension in a projected year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateYear {
+    pub state_id: String,
+    /// Persons moving into the state, including newborns assigned to it
+    pub transfers_in: f64,
+    /// Persons leaving the state, including newborns assigned elsewhere
+    pub transfers_out: f64,
+    /// Components of the state's own population
+    pub projection: ProjectionYear,
+}
+
+/// Projected year of a population with an extra dimension
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultistateYear {
+    /// All states combined
+    pub total: ProjectionYear,
+    pub states: Vec<StateYear>,
+}
+
+/// Standard demographic indicators of a population in one projected year
+///
+/// Crude rates are per 1,000 mid-year population, dependency ratios per
+/// 100 persons aged 15-64, shares in percent of the end-of-year population.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Indicators {
+    pub tfr: f64,
+    pub crude_birth_rate: f64,
+    pub crude_death_rate: f64,
+    /// Persons 65+ per 100 aged 15-64
+    pub old_age_dependency_ratio: f64,
+    /// Persons 0-14 and 65+ per 100 aged 15-64
+    pub total_dependency_ratio: f64,
+    /// Persons 15-64 per person 65+
+    pub potential_support_ratio: f64,
+    pub mean_age: f64,
+    pub share_under_15: f64,
+    pub share_15_to_64: f64,
+    pub share_65_plus: f64,
+    pub share_80_plus: f64,
+}
+
+/// Components and indicators of one region in a projected year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionYear {
+    pub region_id: String,
+    pub total_population: f64,
+    pub births: f64,
+    pub deaths: f64,
+    pub net_migration: f64,
+    /// Persons who moved in from / out to other projected regions
+    #[serde(default)]
+    pub internal_in: f64,
+    #[serde(default)]
+    pub internal_out: f64,
+    pub indicators: Indicators,
+    /// Components by age (when detail recording is enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<ComponentDetail>,
+    /// Balancing-equation check (when auditing is enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit: Option<RegionAudit>,
+}
+
+/// Balancing-equation audit of one region in a projected year
+///
+/// Checks `P(t+1) = P(t) + B - D + M`, where M includes interregional
+/// moves, and each cohort's own balance against what was carried forward.
+/// Clamped rates and emigration change what is applied without breaking
+/// the balance; clamped negative cohorts show up in both discrepancies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionAudit {
+    /// Net interregional moves into the region
+    pub internal_migration: f64,
+    /// `P(t+1) - (P(t) + B - D + M)`; zero when the year balances
+    pub discrepancy: f64,
+    /// Largest gap between a cohort's balance (start + moves - deaths +
+    /// migration) and the population carried to the next age
+    pub max_cohort_discrepancy: f64,
+    /// Balance of the cohorts entering the open age group missing from it
+    /// next year
+    pub open_age_loss: f64,
+    /// Net emigration that exceeded the cohorts and was not applied
+    pub clamped_emigration: f64,
+    /// Mortality, emigration, newborn mortality and fertility rates outside
+    /// their domain that were clamped
+    pub clamped_rates: u32,
+    /// Persons added when cohorts left negative by interregional moves were
+    /// set to zero
+    pub clamped_population: f64,
+}
+
+impl RegionAudit {
+    /// Audit of two regions together: amounts add up, the cohort gap is the
+    /// larger one
+    pub fn merge(self, other: RegionAudit) -> RegionAudit {
+        RegionAudit {
+            internal_migration: self.internal_migration + other.internal_migration,
+            discrepancy: self.discrepancy + other.discrepancy,
+            max_cohort_discrepancy: self.max_cohort_discrepancy.max(other.max_cohort_discrepancy),
+            open_age_loss: self.open_age_loss + other.open_age_loss,
+            clamped_emigration: self.clamped_emigration + other.clamped_emigration,
+            clamped_rates: self.clamped_rates + other.clamped_rates,
+            clamped_population: self.clamped_population + other.clamped_population,
+        }
+    }
+}
+
+/// Persons by single year of age and sex
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgeSexCount {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Births to mothers of one age
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BirthsByAge {
+    pub age: u32,
+    pub births: f64,
+}
+
+/// Components of change of one region in a projected year
+///
+/// Deaths and migration are indexed by age at the start of the year (the
+/// cohort passing from x to x+1); deaths of babies born during the year
+/// are kept apart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDetail {
+    pub deaths: Vec<AgeSexCount>,
+    /// Deaths of the year's births before the end of the year
+    pub newborn_deaths_male: f64,
+    pub newborn_deaths_female: f64,
+    pub births_by_mother_age: Vec<BirthsByAge>,
+    /// Net migration applied, including rate-based emigration
+    pub migration: Vec<AgeSexCount>,
+}
+
+impl ComponentDetail {
+    /// Empty detail for ages `0..=max_age` and mothers aged `fertile_ages`
+    pub fn new(max_age: u32, fertile_ages: std::ops::RangeInclusive<u32>) -> Self {
+        let by_age = || (0..=max_age).map(|age| AgeSexCount { age, male: 0.0, female: 0.0 }).collect();
+        Self {
+            deaths: by_age(),
+            newborn_deaths_male: 0.0,
+            newborn_deaths_female: 0.0,
+            births_by_mother_age: fertile_ages.map(|age| BirthsByAge { age, births: 0.0 }).collect(),
+            migration: by_age(),
+        }
+    }
+}
+
+impl AgeSexCount {
+    /// Add to the count of one sex
+    pub fn add(&mut self, gender: Gender, count: f64) {
+        match gender {
+            Gender::Male => self.male += count,
+            Gender::Female => self.female += count,
+        }
+    }
 }
 
 /// Complete projection result
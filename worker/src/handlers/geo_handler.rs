@@ -142,11 +142,18 @@ mod tests {
             },
         };
         
-        let payload = serde_json::to_vec(&request).unwrap();
+        let envelope = serde_json::json!({
+            "id": "msg-1",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "correlationId": "corr-1",
+            "payload": request,
+        });
+        let payload = serde_json::to_vec(&envelope).unwrap();
         let result = handle_request(&payload).await;
         
         assert!(result.is_ok());
-        let response = result.unwrap();
+        let (response, correlation_id) = result.unwrap();
+        assert_eq!(correlation_id, "corr-1");
         assert_eq!(response.metadata.feature_count, 1);
         assert_eq!(response.metadata.source_crs, "EPSG:5514");
         assert_eq!(response.metadata.target_crs, "EPSG:4326");
@@ -5,7 +5,7 @@
 
 use async_nats::Client;
 use serde::{Deserialize, Serialize};
-use tracing::{info, error};
+use tracing::{info, error, warn};
 use anyhow::Result;
 use chrono::Utc;
 use uuid::Uuid;
@@ -13,20 +13,75 @@ use futures::StreamExt;
 use std::time::Instant;
 
 use crate::engine::{
+    run_stochastic,
+    AbridgedModel,
     CohortComponentModel,
     Cohort, 
+    ComponentDetail,
+    DemandModel,
+    DemandProfile,
+    EmigrationRate,
+    EmigrationTable,
     Gender, 
+    Population,
+    FertilityExposure,
+    FertilityGenerator,
+    FertilityTarget,
+    FiscalModel,
+    FiscalSettings,
+    FiscalYear,
+    HeadshipRate,
+    HeadshipTable,
+    HouseholdModel,
+    HouseholdYear,
+    Indicators,
+    InterregionalMigrationTable,
+    Interpolation,
+    LaborForceModel,
+    LaborForceYear,
+    LifeTable,
+    LeeCarterFit,
+    MigrationTiming,
+    ParticipationRate,
+    ParticipationTable,
+    ParticipationTarget,
+    ProjectionYear,
+    RegionAudit,
     MortalityTable, 
     MortalityRate, 
+    ScheduleModel,
+    ServiceDemandYear,
+    Shock,
+    ShockModifier,
+    ShockType,
     FertilityTable, 
     FertilityRate, 
     MigrationTable, 
-    MigrationRate
+    MigrationRate,
+    MultistateModel,
+    RateAdjustment,
+    StochasticSettings,
+    StochasticYear,
+    SurvivalRatio,
+    SurvivalTable,
+    TransitionRate,
+    TransitionTable,
+    AGE_STEP,
+    MIN_OPEN_AGE,
 };
 
 /// NATS subject for projection requests
 pub const SUBJECT_PROJECTION_RUN: &str = "popula.projection.run";
 
+/// Region ID of the single region projected per workspace
+const REGION_ID: &str = "DEFAULT";
+
+/// Balancing-equation discrepancy (persons) above which a year is logged
+const AUDIT_TOLERANCE: f64 = 1e-6;
+
+/// Rounding allowed in the sum of explicit migration shares
+const SHARE_TOLERANCE: f64 = 1e-6;
+
 // ============================================================
 // Request/Response Types (match TypeScript definitions)
 // ============================================================
@@ -58,6 +113,161 @@ pub struct MigrationRow {
     pub female: f64,
 }
 
+/// Emigration rate row: share of the cohort leaving during the year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmigrationRow {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Five-year survival ratio row (abridged projections)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurvivalRow {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Survival of births to the 0-4 group (abridged projections)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BirthSurvivalRow {
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Rate rows anchored to a year (time-varying assumptions)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearRows<T> {
+    pub year: u32,
+    pub rows: Vec<T>,
+}
+
+/// Extra population dimension, e.g. education or citizenship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DimensionInput {
+    /// Name of the dimension, e.g. "education"
+    pub name: String,
+    pub states: Vec<StateInput>,
+    #[serde(default)]
+    pub transitions: Vec<TransitionInput>,
+    /// State of all newborns (default: the mother's state)
+    #[serde(default)]
+    pub birth_state: Option<String>,
+}
+
+/// Base population and rate differentials of one state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateInput {
+    pub id: String,
+    pub population: Vec<PopulationRow>,
+    /// Multiplier on the fertility rates (default: 1)
+    #[serde(default)]
+    pub relative_fertility: Option<f64>,
+    /// Multipliers on the death probabilities (default: 1)
+    #[serde(default)]
+    pub relative_mortality_male: Option<f64>,
+    #[serde(default)]
+    pub relative_mortality_female: Option<f64>,
+    /// Share of the net migrant counts assigned to the state (default: the
+    /// shares left by the other states, split by base population)
+    #[serde(default)]
+    pub migration_share: Option<f64>,
+}
+
+/// Transition rates from one state to another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitionInput {
+    pub from: String,
+    pub to: String,
+    /// Anchor year of the rates (default: base year)
+    #[serde(default)]
+    pub year: Option<u32>,
+    pub rates: Vec<TransitionRow>,
+}
+
+/// Headship rates of one household type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadshipInput {
+    pub household_type: String,
+    /// Anchor year of the rates (default: base year)
+    #[serde(default)]
+    pub year: Option<u32>,
+    pub rows: Vec<HeadshipRow>,
+}
+
+/// Headship rate row: share of persons heading a household of the type;
+/// applies from its age up to the next row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadshipRow {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Labor force participation rates anchored to a year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipationInput {
+    /// Anchor year of the rates (default: base year)
+    #[serde(default)]
+    pub year: Option<u32>,
+    pub rows: Vec<ParticipationRow>,
+}
+
+/// Participation rate row: share of persons in the labor force;
+/// applies from its age up to the next row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipationRow {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Transition rate row: share of the cohort changing state during the year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionRow {
+    pub age: u32,
+    pub male: f64,
+    pub female: f64,
+}
+
+/// Regions projected together with migration between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterregionalInput {
+    /// Base population and own rates of each region (replaces `population`)
+    pub regions: Vec<RegionInput>,
+    /// Origin → destination move rates, anchored by year
+    pub tables: Vec<InterregionalMigrationTable>,
+}
+
+/// Base population and rate tables of one region
+///
+/// A region without its own table uses the request's, including its series,
+/// Lee-Carter forecast and fertility targets; its own tables hold for the
+/// whole projection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionInput {
+    pub id: String,
+    pub population: Vec<PopulationRow>,
+    #[serde(default)]
+    pub mortality: Option<Vec<MortalityRow>>,
+    #[serde(default)]
+    pub fertility: Option<Vec<FertilityRow>>,
+    /// Net international migrants of the region (default: its share of the
+    /// request's migration)
+    #[serde(default)]
+    pub migration: Option<Vec<MigrationRow>>,
+    /// Share of the request's net migrants (default: the shares left by the
+    /// other regions without their own migration, split by base population)
+    #[serde(default)]
+    pub migration_share: Option<f64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectionRunRequest {
@@ -70,6 +280,101 @@ pub struct ProjectionRunRequest {
     pub fertility: Vec<FertilityRow>,
     #[serde(default)]
     pub migration: Option<Vec<MigrationRow>>,
+    /// Mortality anchors for later years (base-year table is `mortality`)
+    #[serde(default)]
+    pub mortality_series: Option<Vec<YearRows<MortalityRow>>>,
+    /// Observed mortality of past years; when given, mortality after the base
+    /// year is forecast with Lee-Carter from this history and `mortality`
+    #[serde(default)]
+    pub mortality_history: Option<Vec<YearRows<MortalityRow>>>,
+    /// Fertility anchors for later years (base-year table is `fertility`)
+    #[serde(default)]
+    pub fertility_series: Option<Vec<YearRows<FertilityRow>>>,
+    /// Migration anchors for later years (base-year table is `migration`)
+    #[serde(default)]
+    pub migration_series: Option<Vec<YearRows<MigrationRow>>>,
+    /// Emigration as age/sex rates of the population, on top of the
+    /// `migration` counts (which then usually hold immigration only)
+    #[serde(default)]
+    pub emigration_rates: Option<Vec<EmigrationRow>>,
+    /// Emigration rate anchors for later years (base-year table is `emigration_rates`)
+    #[serde(default)]
+    pub emigration_series: Option<Vec<YearRows<EmigrationRow>>>,
+    /// TFR and mean-age targets; when given, fertility after the base year is
+    /// generated from the shape of the base-year `fertility` schedule
+    #[serde(default)]
+    pub fertility_targets: Option<Vec<FertilityTarget>>,
+    /// Schedule shape used with `fertility_targets` (default: shift)
+    #[serde(default)]
+    pub fertility_model: Option<ScheduleModel>,
+    /// How rates are derived between anchor years (default: linear)
+    #[serde(default)]
+    pub interpolation: Option<Interpolation>,
+    /// When net migrants arrive within each year (default: start)
+    #[serde(default)]
+    pub migration_timing: Option<MigrationTiming>,
+    /// Share of infant deaths occurring in the year of birth (default: 0.85)
+    #[serde(default)]
+    pub infant_separation_factor: Option<f64>,
+    /// Female exposure used for births (default: start of year)
+    #[serde(default)]
+    pub fertility_exposure: Option<FertilityExposure>,
+    /// First age of the open-ended age group, e.g. 85 or 100 (default: 120)
+    #[serde(default)]
+    pub open_age_group: Option<u32>,
+    /// Width of age groups and projection steps: 1 or 5 (default: 1)
+    #[serde(default)]
+    pub age_interval: Option<u32>,
+    /// Five-year survival ratios by group (required when `age_interval` is 5)
+    #[serde(default)]
+    pub survival_ratios: Option<Vec<SurvivalRow>>,
+    /// Survival of births to the 0-4 group (required when `age_interval` is 5)
+    #[serde(default)]
+    pub birth_survival: Option<BirthSurvivalRow>,
+    /// Include Leslie matrices and stable population analysis of base-year rates
+    #[serde(default)]
+    pub include_stable_population: Option<bool>,
+    /// Include full period life tables by projected year and sex
+    #[serde(default)]
+    pub include_life_tables: Option<bool>,
+    /// Include deaths, births and migration by age for every year (single-year mode)
+    #[serde(default)]
+    pub include_components: Option<bool>,
+    /// Check the balancing equation for every year and cohort (single-year mode)
+    #[serde(default)]
+    pub audit: Option<bool>,
+    /// Estimate populations back to this year by reverse survival (single-year mode)
+    #[serde(default)]
+    pub backcast_to_year: Option<u32>,
+    /// Project by the states of an extra dimension (single-year mode)
+    #[serde(default)]
+    pub dimension: Option<DimensionInput>,
+    /// Project several regions with migration between them; net migrant
+    /// counts are split by base population (single-year mode)
+    #[serde(default)]
+    pub interregional_migration: Option<InterregionalInput>,
+    /// Headship rates by household type for household projections
+    #[serde(default)]
+    pub headship_rates: Option<Vec<HeadshipInput>>,
+    /// Labor force participation rates for labor force projections
+    #[serde(default)]
+    pub participation_rates: Option<Vec<ParticipationInput>>,
+    /// Participation targets by age range, e.g. under a pension reform
+    #[serde(default)]
+    pub participation_targets: Option<Vec<ParticipationTarget>>,
+    /// Named age profiles (enrollment rates, bed-days per capita, ...) for
+    /// service demand projections
+    #[serde(default)]
+    pub demand_profiles: Option<Vec<DemandProfile>>,
+    /// Revenue and expenditure age profiles for fiscal projections
+    #[serde(default)]
+    pub fiscal: Option<FiscalSettings>,
+    /// Scenario shocks to mortality, fertility and migration (single-year mode)
+    #[serde(default)]
+    pub shocks: Option<Vec<Shock>>,
+    /// Monte Carlo simulations around the deterministic run (single-year mode)
+    #[serde(default)]
+    pub stochastic: Option<StochasticSettings>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,8 +385,111 @@ pub struct ProjectionYearResult {
     pub births: i64,
     pub deaths: i64,
     pub net_migration: i64,
+    /// Net emigration that exceeded the population and was not applied
+    pub unmet_emigration: i64,
     pub natural_change: i64,
     pub growth_rate: f64,
+    /// Period life expectancy at birth and at 65 from the year's mortality
+    /// (single-year mode with mortality shared by all regions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e0_male: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e0_female: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e65_male: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e65_female: Option<f64>,
+    /// TFR, crude rates, dependency ratios, mean age and age-group shares
+    pub indicators: Indicators,
+    /// Deaths, births by mother's age and migration by age (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<ComponentDetail>,
+    /// Balancing-equation discrepancies and clamping, over all regions
+    /// (when auditing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit: Option<RegionAudit>,
+    /// Results by region (multiregional projections)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regions: Option<Vec<RegionYearResult>>,
+}
+
+/// One region of a multiregional projection in a projected year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionYearResult {
+    pub region_id: String,
+    pub total_population: i64,
+    pub births: i64,
+    pub deaths: i64,
+    /// Net international migration
+    pub net_migration: i64,
+    /// Persons moving in from and out to the other regions
+    pub internal_in: i64,
+    pub internal_out: i64,
+    /// Period life expectancy at birth from the region's mortality
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e0_male: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e0_female: Option<f64>,
+    /// TFR, crude rates, dependency ratios, mean age and age-group shares
+    /// of the region
+    pub indicators: Indicators,
+    /// Balancing-equation discrepancies and clamping (when auditing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit: Option<RegionAudit>,
+    /// Population of the region at the end of the year
+    pub population: YearPopulationSnapshot,
+}
+
+/// Period life table for one sex and year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifeTableResult {
+    pub year: u32,
+    pub gender: Gender,
+    pub ages: Vec<u32>,
+    pub qx: Vec<f64>,
+    pub lx: Vec<f64>,
+    pub dx: Vec<f64>,
+    /// Person-years lived in the year of age (L_x)
+    pub person_years: Vec<f64>,
+    pub tx: Vec<f64>,
+    pub ex: Vec<f64>,
+}
+
+/// Population estimated back to the start of one year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackcastYearResult {
+    pub year: u32,
+    pub total_population: i64,
+    /// Components of the year implied by the estimate
+    pub births: i64,
+    pub deaths: i64,
+    pub net_migration: i64,
+    /// Observed age 0 at the end of the year minus newborns implied by the births
+    pub newborn_discrepancy: f64,
+    /// Persons added where reversing migration left a cohort negative
+    pub clamped_population: f64,
+    pub population: YearPopulationSnapshot,
+}
+
+/// One state of the extra dimension in a projected year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateYearResult {
+    pub year: u32,
+    pub state: String,
+    pub total_population: i64,
+    pub births: i64,
+    pub deaths: i64,
+    pub net_migration: i64,
+    /// Persons changing into and out of the state, including newborns
+    /// assigned to the birth state
+    pub transfers_in: i64,
+    pub transfers_out: i64,
+    /// Population of the state at the end of the year
+    pub population: YearPopulationSnapshot,
 }
 
 /// Population snapshot by age and sex
@@ -103,6 +511,23 @@ pub struct YearPopulationSnapshot {
     pub total: i64,
 }
 
+/// Leslie matrix and stable population for one sex
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StablePopulationResult {
+    pub gender: Gender,
+    /// First row of the Leslie matrix by age
+    pub leslie_fertility: Vec<f64>,
+    /// Survival to the next age (last entry: open age group)
+    pub leslie_survival: Vec<f64>,
+    /// Dominant eigenvalue (annual growth ratio)
+    pub lambda: Option<f64>,
+    pub intrinsic_growth_rate: Option<f64>,
+    pub net_reproduction_rate: Option<f64>,
+    pub stable_age_distribution: Vec<f64>,
+    pub reproductive_values: Vec<f64>,
+}
+
 /// Statistics about the input data received
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -119,7 +544,7 @@ pub struct InputDataStats {
     pub years_projected: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectionRunResponse {
     pub workspace_id: String,
@@ -134,6 +559,39 @@ pub struct ProjectionRunResponse {
     /// Full population snapshots by year (age/sex breakdown)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub population_by_year: Option<Vec<YearPopulationSnapshot>>,
+    /// Stable population analysis by sex (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stable_population: Option<Vec<StablePopulationResult>>,
+    /// Period life tables by year and sex (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub life_tables: Option<Vec<LifeTableResult>>,
+    /// Median and 80%/95% prediction intervals by year (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stochastic: Option<Vec<StochasticYear>>,
+    /// Fitted Lee-Carter parameters (when mortality history is given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lee_carter: Option<LeeCarterFit>,
+    /// Populations estimated before the base year, ascending (when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backcast: Option<Vec<BackcastYearResult>>,
+    /// Results by state of the extra dimension (when one is given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub states: Option<Vec<StateYearResult>>,
+    /// Households by type and age of head for every population snapshot
+    /// (when headship rates are given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub households: Option<Vec<HouseholdYear>>,
+    /// Labor force for every population snapshot (when participation rates are given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labor_force: Option<Vec<LaborForceYear>>,
+    /// Demand by profile and region for every population snapshot (when
+    /// demand profiles are given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_demand: Option<Vec<ServiceDemandYear>>,
+    /// Revenue, expenditure and balance for every population snapshot
+    /// (when fiscal profiles are given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fiscal: Option<Vec<FiscalYear>>,
 }
 
 /// Message envelope (matches TypeScript definition)
@@ -162,15 +620,13 @@ impl<T> MessageEnvelope<T> {
 // ============================================================
 
 /// Capture current population state as a snapshot
-fn capture_population_snapshot(ccm: &CohortComponentModel, year: u32) -> YearPopulationSnapshot {
+fn capture_population_snapshot(cohorts: &[Cohort], year: u32) -> YearPopulationSnapshot {
     use std::collections::HashMap;
     
-    let cohorts = ccm.get_cohorts();
-    
     // Aggregate by age (combining genders into single row)
     let mut by_age: HashMap<u32, (i64, i64)> = HashMap::new();
     
-    for cohort in &cohorts {
+    for cohort in cohorts {
         let entry = by_age.entry(cohort.age).or_insert((0, 0));
         match cohort.gender {
             Gender::Male => entry.0 += cohort.count.round() as i64,
@@ -198,52 +654,124 @@ fn capture_population_snapshot(ccm: &CohortComponentModel, year: u32) -> YearPop
     }
 }
 
-/// Run a projection using the CCM engine
-pub fn run_projection(request: &ProjectionRunRequest) -> Result<ProjectionRunResponse, String> {
-    let start = Instant::now();
-    
-    // Validate input
-    if request.population.is_empty() {
-        return Err("Population data is required".to_string());
-    }
-    if request.mortality.is_empty() {
-        return Err("Mortality data is required".to_string());
-    }
-    if request.fertility.is_empty() {
-        return Err("Fertility data is required".to_string());
+/// Populations before the base year by reverse survival, ascending
+fn backcast_results(ccm: &CohortComponentModel, region_id: &str, base_year: u32, to_year: u32) -> Vec<BackcastYearResult> {
+    let mut model = ccm.clone();
+    let regions = vec![region_id.to_string()];
+    let mut results: Vec<BackcastYearResult> = (to_year..base_year).rev().map(|year| {
+        let step = model.backcast_one_year(year, &regions);
+        BackcastYearResult {
+            year,
+            total_population: step.total_population.round() as i64,
+            births: step.births.round() as i64,
+            deaths: step.deaths.round() as i64,
+            net_migration: step.net_migration.round() as i64,
+            newborn_discrepancy: step.newborn_discrepancy,
+            clamped_population: step.clamped_population,
+            population: capture_population_snapshot(&model.get_cohorts(), year),
+        }
+    }).collect();
+    results.reverse();
+    results
+}
+
+/// Leslie matrices and stable population by sex from the base-year rates
+fn stable_population_results(ccm: &CohortComponentModel, region_id: &str, year: u32) -> Vec<StablePopulationResult> {
+    [Gender::Female, Gender::Male].into_iter().map(|gender| {
+        let matrix = ccm.leslie_matrix(region_id, year, gender);
+        let stable = matrix.stable_population();
+        StablePopulationResult {
+            gender,
+            lambda: stable.as_ref().map(|s| s.lambda),
+            intrinsic_growth_rate: stable.as_ref().map(|s| s.intrinsic_growth_rate),
+            net_reproduction_rate: stable.as_ref().map(|s| s.net_reproduction_rate),
+            stable_age_distribution: stable.as_ref().map(|s| s.age_distribution.clone()).unwrap_or_default(),
+            reproductive_values: stable.map(|s| s.reproductive_values).unwrap_or_default(),
+            leslie_fertility: matrix.fertility,
+            leslie_survival: matrix.survival,
+        }
+    }).collect()
+}
+
+/// Indicators of one region in a projected year
+fn region_indicators(result: &ProjectionYear, region_id: &str) -> Indicators {
+    result.regions.iter()
+        .find(|region| region.region_id == region_id)
+        .map(|region| region.indicators)
+        .unwrap_or_default()
+}
+
+/// Results of each region of a multiregional projection in a projected year
+fn region_year_results(ccm: &CohortComponentModel, result: &ProjectionYear, cohorts: &[Cohort]) -> Vec<RegionYearResult> {
+    result.regions.iter().map(|region| {
+        let expectancy = |gender| ccm.life_table(&region.region_id, result.year, gender).map(|table| table.life_expectancy(0));
+        let region_cohorts: Vec<Cohort> = cohorts.iter()
+            .filter(|cohort| cohort.region_id == region.region_id)
+            .cloned()
+            .collect();
+        RegionYearResult {
+            region_id: region.region_id.clone(),
+            total_population: region.total_population.round() as i64,
+            births: region.births.round() as i64,
+            deaths: region.deaths.round() as i64,
+            net_migration: region.net_migration.round() as i64,
+            internal_in: region.internal_in.round() as i64,
+            internal_out: region.internal_out.round() as i64,
+            e0_male: expectancy(Gender::Male),
+            e0_female: expectancy(Gender::Female),
+            indicators: region.indicators,
+            audit: region.audit,
+            population: capture_population_snapshot(&region_cohorts, result.year + 1),
+        }
+    }).collect()
+}
+
+/// Indicators of all projected regions together in a projected year
+///
+/// The TFR is the mean of the regions' TFRs weighted by their women aged
+/// 15-49 at the end of the year.
+fn national_indicators(result: &ProjectionYear, start_population: f64, cohorts: &[Cohort]) -> Indicators {
+    let mut counts = Vec::new();
+    for cohort in cohorts {
+        let age = cohort.age as usize;
+        if counts.len() <= age {
+            counts.resize(age + 1, 0.0);
+        }
+        counts[age] += cohort.count;
     }
-    if request.base_year >= request.end_year {
-        return Err("End year must be greater than base year".to_string());
+    let women = |region_id: &str| cohorts.iter()
+        .filter(|cohort| cohort.region_id == region_id && cohort.gender == Gender::Female && (15..50).contains(&cohort.age))
+        .map(|cohort| cohort.count)
+        .sum::<f64>();
+    let (weighted_tfr, exposed) = result.regions.iter().fold((0.0, 0.0), |(tfr, exposed), region| {
+        let region_women = women(&region.region_id);
+        (tfr + region.indicators.tfr * region_women, exposed + region_women)
+    });
+    let tfr = match exposed {
+        exposed if exposed > 0.0 => weighted_tfr / exposed,
+        _ => result.regions.first().map(|region| region.indicators.tfr).unwrap_or(0.0),
+    };
+    Indicators::compute(&counts, 1, start_population, result.births, result.deaths, tfr)
+}
+
+/// Response columns of a period life table
+fn life_table_result(year: u32, gender: Gender, table: LifeTable) -> LifeTableResult {
+    LifeTableResult {
+        year,
+        gender,
+        ages: (table.start_age..).take(table.lx.len()).collect(),
+        qx: table.qx,
+        lx: table.lx,
+        dx: table.dx,
+        person_years: table.person_years,
+        tx: table.tx,
+        ex: table.ex,
     }
-    
-    // Create CCM model
-    let mut ccm = CohortComponentModel::new();
-    
-    // Use a region ID for this workspace
-    let region_id = "DEFAULT";
-    
-    // Calculate input statistics
-    let male_pop: f64 = request.population.iter().map(|r| r.male).sum();
-    let female_pop: f64 = request.population.iter().map(|r| r.female).sum();
-    let total_initial_pop = male_pop + female_pop;
-    let migration_rows = request.migration.as_ref().map(|m| m.len()).unwrap_or(0);
-    
-    info!(
-        "📊 Received data: {} population rows, {} mortality rows, {} fertility rows, {} migration rows",
-        request.population.len(),
-        request.mortality.len(),
-        request.fertility.len(),
-        migration_rows
-    );
-    info!(
-        "📊 Input population: {} total ({} male, {} female)",
-        total_initial_pop.round() as i64,
-        male_pop.round() as i64,
-        female_pop.round() as i64
-    );
-    
-    // Load population data
-    let cohorts: Vec<Cohort> = request.population.iter().flat_map(|row| {
+}
+
+/// Build cohorts from population rows
+fn population_cohorts(rows: &[PopulationRow], region_id: &str) -> Vec<Cohort> {
+    rows.iter().flat_map(|row| {
         vec![
             Cohort {
                 age: row.age,
@@ -258,77 +786,639 @@ pub fn run_projection(request: &ProjectionRunRequest) -> Result<ProjectionRunRes
                 count: row.female,
             },
         ]
-    }).collect();
-    
-    ccm.load_population(&cohorts);
-    
-    // Debug: Log loaded population stats
-    let loaded_pop = ccm.total_population();
-    info!(
-        "📊 Loaded population: {} people from {} cohorts (expected ~10.2M for Humania)",
-        loaded_pop.round() as i64,
-        cohorts.len()
-    );
-    
-    // Load mortality table
-    let mortality_rates: Vec<MortalityRate> = request.mortality.iter().map(|row| {
-        MortalityRate {
-            age: row.age,
-            male: row.male,
-            female: row.female,
-        }
-    }).collect();
-    
-    ccm.load_mortality_table(MortalityTable {
-        region_id: region_id.to_string(),
-        year: request.base_year,
-        rates: mortality_rates,
-    });
-    
-    // Load fertility table
-    let fertility_rates: Vec<FertilityRate> = request.fertility.iter().map(|row| {
-        FertilityRate {
-            age: row.age,
+    }).collect()
+}
+
+/// Base population rows of a request, including those given by region
+fn base_population_rows(request: &ProjectionRunRequest) -> impl Iterator<Item = &PopulationRow> {
+    let regions = request.interregional_migration.iter().flat_map(|input| &input.regions);
+    request.population.iter().chain(regions.flat_map(|region| &region.population))
+}
+
+/// Summarize the input data of a request
+fn input_stats(request: &ProjectionRunRequest, years_projected: u32) -> InputDataStats {
+    let male_pop: f64 = base_population_rows(request).map(|r| r.male).sum();
+    let female_pop: f64 = base_population_rows(request).map(|r| r.female).sum();
+    InputDataStats {
+        population_rows: base_population_rows(request).count(),
+        mortality_rows: request.mortality.len(),
+        fertility_rows: request.fertility.len(),
+        migration_rows: request.migration.as_ref().map(|m| m.len()).unwrap_or(0),
+        total_initial_population: (male_pop + female_pop).round() as i64,
+        male_population: male_pop.round() as i64,
+        female_population: female_pop.round() as i64,
+        base_year: request.base_year,
+        end_year: request.end_year,
+        years_projected,
+    }
+}
+
+/// Build a mortality table from request rows
+fn mortality_table(region_id: &str, year: u32, rows: &[MortalityRow]) -> MortalityTable {
+    MortalityTable {
+        region_id: region_id.to_string(),
+        year,
+        rates: rows.iter().map(|row| MortalityRate {
+            age: row.age,
+            male: row.male,
+            female: row.female,
+        }).collect(),
+    }
+}
+
+/// Build a fertility table from request rows
+fn fertility_table(region_id: &str, year: u32, rows: &[FertilityRow], sex_ratio_at_birth: f64) -> FertilityTable {
+    FertilityTable {
+        region_id: region_id.to_string(),
+        year,
+        rates: rows.iter().map(|row| FertilityRate {
+            age: row.age,
             rate: row.rate,
-        }
-    }).collect();
-    
-    ccm.load_fertility_table(FertilityTable {
+        }).collect(),
+        sex_ratio_at_birth,
+    }
+}
+
+/// Build a migration table from request rows
+fn migration_table(region_id: &str, year: u32, rows: &[MigrationRow]) -> MigrationTable {
+    MigrationTable {
         region_id: region_id.to_string(),
-        year: request.base_year,
-        rates: fertility_rates,
-        sex_ratio_at_birth: request.sex_ratio_at_birth,
+        year,
+        rates: rows.iter().map(|row| MigrationRate {
+            age: row.age,
+            male: row.male,
+            female: row.female,
+        }).collect(),
+    }
+}
+
+/// Base population and own rate tables of one projected region
+struct RegionSetup<'a> {
+    id: &'a str,
+    population: &'a [PopulationRow],
+    /// Tables replacing the request's for this region
+    mortality: Option<&'a [MortalityRow]>,
+    fertility: Option<&'a [FertilityRow]>,
+    migration: Option<&'a [MigrationRow]>,
+    /// Share of the request's net migrants (0 with own migration)
+    migration_share: f64,
+}
+
+/// The workspace region, or the regions of a multiregional projection
+fn projected_regions(request: &ProjectionRunRequest) -> Vec<RegionSetup<'_>> {
+    let Some(input) = &request.interregional_migration else {
+        return vec![RegionSetup {
+            id: REGION_ID,
+            population: &request.population,
+            mortality: None,
+            fertility: None,
+            migration: None,
+            migration_share: 1.0,
+        }];
+    };
+    let parts: Vec<(Option<f64>, &[PopulationRow])> = input.regions.iter()
+        .filter(|region| region.migration.is_none())
+        .map(|region| (region.migration_share, region.population.as_slice()))
+        .collect();
+    let mut shares = migration_shares(&parts).into_iter();
+    input.regions.iter().map(|region| RegionSetup {
+        id: &region.id,
+        population: &region.population,
+        mortality: region.mortality.as_deref(),
+        fertility: region.fertility.as_deref(),
+        migration: region.migration.as_deref(),
+        migration_share: match region.migration {
+            Some(_) => 0.0,
+            None => shares.next().unwrap_or(0.0),
+        },
+    }).collect()
+}
+
+/// Net migration rows scaled to a share of the migrants
+fn scaled_migration(rows: &[MigrationRow], share: f64) -> Vec<MigrationRow> {
+    rows.iter().map(|row| MigrationRow { age: row.age, male: row.male * share, female: row.female * share }).collect()
+}
+
+/// Build an emigration rate table from request rows
+fn emigration_table(region_id: &str, year: u32, rows: &[EmigrationRow]) -> EmigrationTable {
+    EmigrationTable {
+        region_id: region_id.to_string(),
+        year,
+        rates: rows.iter().map(|row| EmigrationRate {
+            age: row.age,
+            male: row.male,
+            female: row.female,
+        }).collect(),
+    }
+}
+
+/// Run a projection and derive households, the labor force, service demand
+/// and public finances from its unrounded yearly populations
+pub fn run_projection(request: &ProjectionRunRequest) -> Result<ProjectionRunResponse, String> {
+    for input in request.headship_rates.iter().flatten() {
+        if input.household_type.is_empty() {
+            return Err("Household type is required".to_string());
+        }
+        if input.rows.iter().any(|row| !(0.0..=1.0).contains(&row.male) || !(0.0..=1.0).contains(&row.female)) {
+            return Err("Headship rates must be between 0 and 1".to_string());
+        }
+    }
+    validate_participation(request)?;
+    let profiles = request.demand_profiles.as_deref().unwrap_or_default();
+    for (index, profile) in profiles.iter().enumerate() {
+        if profile.name.is_empty() {
+            return Err("Demand profile name is required".to_string());
+        }
+        if profiles[..index].iter().any(|other| other.name == profile.name) {
+            return Err(format!("Duplicate demand profile {}", profile.name));
+        }
+        if profile.values.iter().any(|value| !(value.male >= 0.0 && value.female >= 0.0)) {
+            return Err(format!("Demand profile {} has negative values", profile.name));
+        }
+    }
+    if let Some(settings) = &request.fiscal {
+        settings.validate()?;
+    }
+
+    let CohortRun { mut response, populations } = run_cohort_projection(request)?;
+    response.households = request.headship_rates.as_ref()
+        .map(|inputs| household_results(request, inputs, &populations));
+    response.labor_force = request.participation_rates.as_ref()
+        .map(|inputs| labor_force_results(request, inputs, &populations));
+    response.service_demand = request.demand_profiles.as_ref()
+        .map(|profiles| service_demand_results(profiles, &populations));
+    response.fiscal = request.fiscal.as_ref().map(|settings| {
+        let model = FiscalModel::new(request.base_year, settings.clone());
+        populations.iter()
+            .map(|population| model.project(&population.cohorts, population.year))
+            .collect()
     });
-    
-    // Load migration table (optional)
-    if let Some(migration) = &request.migration {
-        if !migration.is_empty() {
-            let migration_rates: Vec<MigrationRate> = migration.iter().map(|row| {
-                MigrationRate {
+    Ok(response)
+}
+
+fn validate_participation(request: &ProjectionRunRequest) -> Result<(), String> {
+    let in_range = |rate: f64| (0.0..=1.0).contains(&rate);
+    for input in request.participation_rates.iter().flatten() {
+        if input.rows.iter().any(|row| !in_range(row.male) || !in_range(row.female)) {
+            return Err("Participation rates must be between 0 and 1".to_string());
+        }
+    }
+    let targets = request.participation_targets.as_deref().unwrap_or_default();
+    if !targets.is_empty() && request.participation_rates.is_none() {
+        return Err("Participation targets require participation rates".to_string());
+    }
+    for target in targets {
+        if target.year <= request.base_year {
+            return Err("Participation target year must be after the base year".to_string());
+        }
+        if target.min_age > target.max_age {
+            return Err("Participation target minimum age exceeds its maximum age".to_string());
+        }
+        if [target.male, target.female].into_iter().flatten().any(|rate| !in_range(rate)) {
+            return Err("Participation rates must be between 0 and 1".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Households of the yearly populations with time-varying headship rates,
+/// applied alike in every region
+fn household_results(
+    request: &ProjectionRunRequest,
+    inputs: &[HeadshipInput],
+    populations: &[Population],
+) -> Vec<HouseholdYear> {
+    let mut model = HouseholdModel::new();
+    model.set_interpolation(request.interpolation.unwrap_or_default());
+    for region in projected_regions(request) {
+        for input in inputs {
+            model.load_headship_table(HeadshipTable {
+                region_id: region.id.to_string(),
+                year: input.year.unwrap_or(request.base_year),
+                household_type: input.household_type.clone(),
+                rates: input.rows.iter().map(|row| HeadshipRate {
                     age: row.age,
                     male: row.male,
                     female: row.female,
-                }
-            }).collect();
-            
-            ccm.load_migration_table(MigrationTable {
-                region_id: region_id.to_string(),
-                year: request.base_year,
-                rates: migration_rates,
+                }).collect(),
+            });
+        }
+    }
+
+    populations.iter().map(|population| model.project(population)).collect()
+}
+
+/// Labor force of the yearly populations with time-varying participation
+/// rates, applied alike in every region
+fn labor_force_results(
+    request: &ProjectionRunRequest,
+    inputs: &[ParticipationInput],
+    populations: &[Population],
+) -> Vec<LaborForceYear> {
+    let mut model = LaborForceModel::new(request.base_year);
+    model.set_interpolation(request.interpolation.unwrap_or_default());
+    for region in projected_regions(request) {
+        for input in inputs {
+            model.load_participation_table(ParticipationTable {
+                region_id: region.id.to_string(),
+                year: input.year.unwrap_or(request.base_year),
+                rates: input.rows.iter().map(|row| ParticipationRate {
+                    age: row.age,
+                    male: row.male,
+                    female: row.female,
+                }).collect(),
             });
         }
     }
+    for target in request.participation_targets.iter().flatten() {
+        model.add_target(*target);
+    }
+
+    populations.iter().map(|population| model.project(population)).collect()
+}
+
+/// Demand of every profile for the yearly populations
+fn service_demand_results(profiles: &[DemandProfile], populations: &[Population]) -> Vec<ServiceDemandYear> {
+    let mut model = DemandModel::new();
+    for profile in profiles {
+        model.register(profile.clone());
+    }
+
+    populations.iter()
+        .map(|population| model.project(&population.cohorts, population.year))
+        .collect()
+}
+
+/// Projection response with the unrounded populations behind its snapshots
+struct CohortRun {
+    response: ProjectionRunResponse,
+    /// One population per snapshot year; empty for abridged runs, which the
+    /// single-year models do not accept
+    populations: Vec<Population>,
+}
+
+/// Engine population of a projected year, with the cohorts of the states of
+/// each region summed into the region
+fn workspace_population(request: &ProjectionRunRequest, cohorts: &[Cohort], year: u32) -> Population {
+    use std::collections::BTreeMap;
+
+    let mut by_region: BTreeMap<&str, BTreeMap<u32, PopulationRow>> = BTreeMap::new();
+    for cohort in cohorts {
+        let row = by_region.entry(&cohort.region_id).or_default()
+            .entry(cohort.age)
+            .or_insert(PopulationRow { age: cohort.age, male: 0.0, female: 0.0 });
+        match cohort.gender {
+            Gender::Male => row.male += cohort.count,
+            Gender::Female => row.female += cohort.count,
+        }
+    }
+    let cohorts: Vec<Cohort> = by_region.into_iter()
+        .flat_map(|(region_id, by_age)| {
+            let rows: Vec<PopulationRow> = by_age.into_values().collect();
+            population_cohorts(&rows, region_id)
+        })
+        .collect();
+    Population {
+        scenario_id: request.workspace_id.clone(),
+        year,
+        metadata: Population::calculate_metadata(&cohorts),
+        cohorts,
+    }
+}
+
+/// Run a projection using the CCM engine
+fn run_cohort_projection(request: &ProjectionRunRequest) -> Result<CohortRun, String> {
+    let start = Instant::now();
+    
+    // Validate input
+    if request.population.is_empty() && request.dimension.is_none() && request.interregional_migration.is_none() {
+        return Err("Population data is required".to_string());
+    }
+    let abridged = match request.age_interval.unwrap_or(1) {
+        1 => false,
+        AGE_STEP => true,
+        _ => return Err(format!("Age interval must be 1 or {}", AGE_STEP)),
+    };
+    if abridged {
+        if request.survival_ratios.as_ref().is_none_or(|rows| rows.is_empty()) {
+            return Err("Survival ratios are required for five-year age groups".to_string());
+        }
+        if request.birth_survival.is_none() {
+            return Err("Birth survival is required for five-year age groups".to_string());
+        }
+    } else if request.mortality.is_empty() {
+        return Err("Mortality data is required".to_string());
+    }
+    if request.fertility.is_empty() {
+        return Err("Fertility data is required".to_string());
+    }
+    if request.base_year >= request.end_year {
+        return Err("End year must be greater than base year".to_string());
+    }
+    if abridged {
+        // Steps start at the base year; the last one must start at the end year
+        if !(request.end_year - request.base_year).is_multiple_of(AGE_STEP) {
+            return Err(format!("End year must be a multiple of {} years after the base year", AGE_STEP));
+        }
+        // Snapshots hold five-year groups, which these models would read as single years
+        if request.headship_rates.is_some()
+            || request.participation_rates.is_some()
+            || request.demand_profiles.is_some()
+            || request.fiscal.is_some()
+        {
+            return Err(
+                "Household, labor force, service demand and fiscal projections require \
+                 single-year age groups".to_string(),
+            );
+        }
+    }
+    if let Some(factor) = request.infant_separation_factor {
+        if !(0.0..=1.0).contains(&factor) {
+            return Err("Infant separation factor must be between 0 and 1".to_string());
+        }
+    }
+    if let Some(open_age) = request.open_age_group {
+        if open_age < MIN_OPEN_AGE {
+            return Err(format!("Open age group must be at least {}", MIN_OPEN_AGE));
+        }
+    }
+    if let Some(settings) = &request.stochastic {
+        if abridged {
+            return Err("Stochastic projections require single-year age groups".to_string());
+        }
+        settings.validate()?;
+    }
+    let has_emigration_rates = request.emigration_rates.is_some() || request.emigration_series.is_some();
+    if abridged && request.include_components.unwrap_or(false) {
+        return Err("Component detail requires single-year age groups".to_string());
+    }
+    if abridged && request.audit.unwrap_or(false) {
+        return Err("Audit requires single-year age groups".to_string());
+    }
+    for shock in request.shocks.iter().flatten() {
+        if abridged {
+            return Err("Shocks require single-year age groups".to_string());
+        }
+        if shock.end_year < shock.start_year {
+            return Err(format!("Shock {} ends before it starts", shock.name));
+        }
+        if shock.intensity_curve.iter().flatten().any(|intensity| !(0.0..=1.0).contains(intensity)) {
+            return Err(format!("Shock {} intensity must be between 0 and 1", shock.name));
+        }
+        shock.modifier.validate().map_err(|error| format!("Shock {}: {}", shock.name, error))?;
+    }
+    if let Some(to_year) = request.backcast_to_year {
+        if abridged {
+            return Err("Backcasting requires single-year age groups".to_string());
+        }
+        if to_year >= request.base_year {
+            return Err("Backcast year must be before the base year".to_string());
+        }
+    }
+    if has_emigration_rates {
+        if abridged {
+            return Err("Emigration rates require single-year age groups".to_string());
+        }
+        let rows = request.emigration_rates.iter().flatten()
+            .chain(request.emigration_series.iter().flatten().flat_map(|anchor| &anchor.rows));
+        for row in rows {
+            if !(0.0..=1.0).contains(&row.male) || !(0.0..=1.0).contains(&row.female) {
+                return Err("Emigration rates must be between 0 and 1".to_string());
+            }
+        }
+    }
+    if let Some(targets) = &request.fertility_targets {
+        if abridged {
+            return Err("Fertility targets require single-year age groups".to_string());
+        }
+        if request.fertility_series.is_some() {
+            return Err("Fertility series and fertility targets cannot be combined".to_string());
+        }
+        if targets.iter().any(|target| target.tfr.is_some_and(|tfr| tfr < 0.0)) {
+            return Err("Target TFR must not be negative".to_string());
+        }
+        if targets.iter().any(|target| target.mean_age.is_some_and(|age| !(15.0..50.0).contains(&age))) {
+            return Err("Target mean age must be between 15 and 50".to_string());
+        }
+    }
+    if let Some(dimension) = &request.dimension {
+        if abridged {
+            return Err("Population dimensions require single-year age groups".to_string());
+        }
+        validate_dimension(request, dimension)?;
+    }
+    if let Some(input) = &request.interregional_migration {
+        if abridged {
+            return Err("Interregional migration requires single-year age groups".to_string());
+        }
+        validate_interregional(request, input)?;
+    }
+    if request.mortality_history.is_some() {
+        if abridged {
+            return Err("Lee-Carter forecasting requires single-year age groups".to_string());
+        }
+        if request.mortality_series.is_some() {
+            return Err("Mortality series and mortality history cannot be combined".to_string());
+        }
+    }
+    
+    // One region per workspace, or the regions of a multiregional projection
+    let region_setups = projected_regions(request);
+    // Rate-based results need shared rates and use the first region
+    let region_id = region_setups[0].id;
+    let shared_mortality = region_setups.iter().all(|region| region.mortality.is_none());
+    
+    // Calculate input statistics
+    let male_pop: f64 = base_population_rows(request).map(|r| r.male).sum();
+    let female_pop: f64 = base_population_rows(request).map(|r| r.female).sum();
+    let total_initial_pop = male_pop + female_pop;
+    let migration_rows = request.migration.as_ref().map(|m| m.len()).unwrap_or(0);
+    
+    info!(
+        "📊 Received data: {} population rows, {} mortality rows, {} fertility rows, {} migration rows",
+        request.population.len(),
+        request.mortality.len(),
+        request.fertility.len(),
+        migration_rows
+    );
+    info!(
+        "📊 Input population: {} total ({} male, {} female)",
+        total_initial_pop.round() as i64,
+        male_pop.round() as i64,
+        female_pop.round() as i64
+    );
+    
+    if abridged {
+        let response = run_abridged_projection(request, region_id, start);
+        return Ok(CohortRun { response, populations: Vec::new() });
+    }
+
+    // Create CCM model
+    let mut ccm = CohortComponentModel::new();
+    if let Some(open_age) = request.open_age_group {
+        ccm.set_open_age_group(open_age);
+    }
+    
+    // Load population data
+    let cohorts: Vec<Cohort> = region_setups.iter()
+        .flat_map(|region| population_cohorts(region.population, region.id))
+        .collect();
+    ccm.load_population(&cohorts);
+    
+    // Debug: Log loaded population stats
+    let loaded_pop = ccm.total_population();
+    info!(
+        "📊 Loaded population: {} people from {} cohorts (expected ~10.2M for Humania)",
+        loaded_pop.round() as i64,
+        cohorts.len()
+    );
+    
+    for region in &region_setups {
+        let region_id = region.id;
+
+        // Load base-year rate tables; the request's unless the region has its own
+        ccm.load_mortality_table(mortality_table(region_id, request.base_year, region.mortality.unwrap_or(&request.mortality)));
+        ccm.load_fertility_table(fertility_table(
+            region_id,
+            request.base_year,
+            region.fertility.unwrap_or(&request.fertility),
+            request.sex_ratio_at_birth,
+        ));
+
+        // Load migration table (optional); regions without their own get
+        // their share of the net migrants
+        let migration = match region.migration {
+            Some(rows) => Some(rows.to_vec()),
+            None => request.migration.as_ref().map(|rows| scaled_migration(rows, region.migration_share)),
+        };
+        if let Some(migration) = migration.filter(|rows| !rows.is_empty()) {
+            ccm.load_migration_table(migration_table(region_id, request.base_year, &migration));
+        }
+
+        // Load anchors for later years (time-varying assumptions) of the
+        // request's tables
+        if region.mortality.is_none() {
+            for anchor in request.mortality_series.iter().flatten() {
+                ccm.load_mortality_table(mortality_table(region_id, anchor.year, &anchor.rows));
+            }
+        }
+        if region.fertility.is_none() {
+            for anchor in request.fertility_series.iter().flatten() {
+                ccm.load_fertility_table(fertility_table(
+                    region_id,
+                    anchor.year,
+                    &anchor.rows,
+                    request.sex_ratio_at_birth,
+                ));
+            }
+        }
+        if region.migration.is_none() {
+            for anchor in request.migration_series.iter().flatten() {
+                ccm.load_migration_table(migration_table(region_id, anchor.year, &scaled_migration(&anchor.rows, region.migration_share)));
+            }
+        }
+        if let Some(rows) = &request.emigration_rates {
+            ccm.load_emigration_table(emigration_table(region_id, request.base_year, rows));
+        }
+        for anchor in request.emigration_series.iter().flatten() {
+            ccm.load_emigration_table(emigration_table(region_id, anchor.year, &anchor.rows));
+        }
+    }
+    let lee_carter = match &request.mortality_history {
+        Some(history) => {
+            let fit = fit_lee_carter(request, region_id, history)?;
+            info!("📉 Lee-Carter fitted to {} years, forecasting to {}", fit.years.len(), request.end_year);
+            for table in fit.forecast(request.end_year) {
+                for region in region_setups.iter().filter(|region| region.mortality.is_none()) {
+                    ccm.load_mortality_table(MortalityTable { region_id: region.id.to_string(), ..table.clone() });
+                }
+            }
+            Some(fit)
+        }
+        None => None,
+    };
+    if let Some(targets) = &request.fertility_targets {
+        let base = fertility_table(region_id, request.base_year, &request.fertility, request.sex_ratio_at_birth);
+        let generator = FertilityGenerator::from_table(&base, request.fertility_model.unwrap_or_default())
+            .ok_or("Fertility targets need base-year births at ages 15-49")?;
+        for table in generator.series(targets, request.end_year) {
+            for region in region_setups.iter().filter(|region| region.fertility.is_none()) {
+                ccm.load_fertility_table(FertilityTable { region_id: region.id.to_string(), ..table.clone() });
+            }
+        }
+    }
+    for table in request.interregional_migration.iter().flat_map(|input| &input.tables) {
+        ccm.load_interregional_migration_table(table.clone());
+    }
+    ccm.set_interpolation(request.interpolation.unwrap_or_default());
+    ccm.set_migration_timing(request.migration_timing.unwrap_or_default());
+    if let Some(factor) = request.infant_separation_factor {
+        ccm.set_infant_separation_factor(factor);
+    }
+    ccm.set_fertility_exposure(request.fertility_exposure.unwrap_or_default());
+    ccm.set_component_detail(request.include_components.unwrap_or(false));
+    ccm.set_audit(request.audit.unwrap_or(false));
     
+    if let Some(dimension) = &request.dimension {
+        return run_multistate_projection(request, dimension, &ccm, region_id, lee_carter, start);
+    }
+    for shock in request.shocks.iter().flatten() {
+        ccm.add_shock(shock.clone());
+    }
+
+    let stable_population = request.include_stable_population.unwrap_or(false)
+        .then(|| stable_population_results(&ccm, region_id, request.base_year));
+    let backcast = request.backcast_to_year
+        .map(|to_year| backcast_results(&ccm, region_id, request.base_year, to_year));
+
     // Run projection year by year
-    let regions = vec![region_id.to_string()];
+    let regions: Vec<String> = region_setups.iter().map(|region| region.id.to_string()).collect();
+    let stochastic = request.stochastic.as_ref().map(|settings| {
+        info!("🎲 Running {} stochastic simulations", settings.simulations);
+        run_stochastic(&ccm, &regions, request.base_year, request.end_year, settings)
+    });
     let mut results = Vec::new();
     let mut population_snapshots = Vec::new();
+    let mut populations = Vec::new();
+    let include_life_tables = request.include_life_tables.unwrap_or(false);
+    let mut life_tables = Vec::new();
     
     // Capture initial population (base year, before any projection)
-    population_snapshots.push(capture_population_snapshot(&ccm, request.base_year));
+    let cohorts = ccm.get_cohorts();
+    population_snapshots.push(capture_population_snapshot(&cohorts, request.base_year));
+    populations.push(workspace_population(request, &cohorts, request.base_year));
     
     for year in request.base_year..=request.end_year {
+        // Life tables from the mortality in effect this year
+        let [male_table, female_table] = [Gender::Male, Gender::Female]
+            .map(|gender| ccm.life_table(region_id, year, gender).filter(|_| shared_mortality));
+        let expectancy = |table: &Option<LifeTable>, age| table.as_ref().map(|table| table.life_expectancy(age));
+        let (e0_male, e0_female) = (expectancy(&male_table, 0), expectancy(&female_table, 0));
+        let (e65_male, e65_female) = (expectancy(&male_table, 65), expectancy(&female_table, 65));
+        if include_life_tables {
+            for (gender, table) in [(Gender::Male, male_table), (Gender::Female, female_table)] {
+                if let Some(table) = table {
+                    life_tables.push(life_table_result(year, gender, table));
+                }
+            }
+        }
+
+        let start_population = ccm.total_population();
         let year_result = ccm.project_one_year(year, &regions);
+        let cohorts = ccm.get_cohorts();
+        let indicators = match regions.len() {
+            1 => region_indicators(&year_result, region_id),
+            _ => national_indicators(&year_result, start_population, &cohorts),
+        };
+        let region_year = year_result.regions.iter().find(|region| region.region_id == region_id);
+        for region in &year_result.regions {
+            if let Some(audit) = region.audit.filter(|audit| audit.discrepancy.abs() > AUDIT_TOLERANCE) {
+                warn!("⚠️ Population of {} in {} off balance by {:.6}", region.region_id, year, audit.discrepancy);
+            }
+        }
+        let audit = year_result.regions.iter().filter_map(|region| region.audit).reduce(RegionAudit::merge);
+        let region_results = request.interregional_migration.is_some()
+            .then(|| region_year_results(&ccm, &year_result, &cohorts));
         
         results.push(ProjectionYearResult {
             year,
@@ -336,30 +1426,29 @@ pub fn run_projection(request: &ProjectionRunRequest) -> Result<ProjectionRunRes
             births: year_result.births.round() as i64,
             deaths: year_result.deaths.round() as i64,
             net_migration: year_result.net_migration.round() as i64,
+            unmet_emigration: year_result.unmet_emigration.round() as i64,
             natural_change: year_result.natural_change.round() as i64,
             growth_rate: year_result.growth_rate,
+            e0_male,
+            e0_female,
+            e65_male,
+            e65_female,
+            indicators,
+            components: region_year.and_then(|region| region.components.clone()),
+            audit,
+            regions: region_results,
         });
         
         // Capture population snapshot after this year's projection
         // The snapshot represents population at the END of this year
-        population_snapshots.push(capture_population_snapshot(&ccm, year + 1));
+        population_snapshots.push(capture_population_snapshot(&cohorts, year + 1));
+        populations.push(workspace_population(request, &cohorts, year + 1));
     }
     
     let processing_time = start.elapsed().as_millis() as u64;
     
     // Build input statistics
-    let input_stats = InputDataStats {
-        population_rows: request.population.len(),
-        mortality_rows: request.mortality.len(),
-        fertility_rows: request.fertility.len(),
-        migration_rows,
-        total_initial_population: total_initial_pop.round() as i64,
-        male_population: male_pop.round() as i64,
-        female_population: female_pop.round() as i64,
-        base_year: request.base_year,
-        end_year: request.end_year,
-        years_projected: request.end_year - request.base_year + 1,
-    };
+    let input_stats = input_stats(request, request.end_year - request.base_year + 1);
     
     info!(
         "📊 Projection complete: {} years in {}ms, final pop = {}",
@@ -368,32 +1457,402 @@ pub fn run_projection(request: &ProjectionRunRequest) -> Result<ProjectionRunRes
         results.last().map(|r| r.total_population).unwrap_or(0)
     );
     
-    Ok(ProjectionRunResponse {
+    let response = ProjectionRunResponse {
         workspace_id: request.workspace_id.clone(),
         success: true,
         years: results,
-        error: None,
         processing_time_ms: processing_time,
         input_stats: Some(input_stats),
         population_by_year: Some(population_snapshots),
-    })
-}
-
-// ============================================================
-// NATS Handler
-// ============================================================
-
-/// Projection handler that subscribes to projection requests
-pub struct ProjectionHandler {
-    client: Client,
+        stable_population,
+        life_tables: include_life_tables.then_some(life_tables),
+        stochastic,
+        lee_carter,
+        backcast,
+        ..Default::default()
+    };
+    Ok(CohortRun { response, populations })
 }
 
-impl ProjectionHandler {
-    pub fn new(client: Client) -> Self {
-        Self { client }
+/// Check states, transitions and options of an extra population dimension
+fn validate_dimension(request: &ProjectionRunRequest, dimension: &DimensionInput) -> Result<(), String> {
+    if !request.population.is_empty() {
+        return Err("Population is given by state when a dimension is used".to_string());
     }
-
-    /// Start listening for projection requests
+    if request.stochastic.is_some()
+        || request.backcast_to_year.is_some()
+        || request.include_stable_population.unwrap_or(false)
+        || request.include_life_tables.unwrap_or(false)
+        || request.include_components.unwrap_or(false)
+        || request.audit.unwrap_or(false)
+    {
+        return Err(
+            "Population dimensions cannot be combined with stochastic runs, backcasting, \
+             stable population, life tables, component detail or audit".to_string(),
+        );
+    }
+    if dimension.states.is_empty() {
+        return Err("A population dimension needs at least one state".to_string());
+    }
+    let known = |id: &str| dimension.states.iter().any(|state| state.id == id);
+    for (index, state) in dimension.states.iter().enumerate() {
+        if dimension.states[..index].iter().any(|other| other.id == state.id) {
+            return Err(format!("Duplicate state {}", state.id));
+        }
+        let multipliers = [state.relative_fertility, state.relative_mortality_male, state.relative_mortality_female];
+        if multipliers.into_iter().flatten().any(|value| value < 0.0) {
+            return Err("Relative rates must not be negative".to_string());
+        }
+    }
+    validate_migration_shares(dimension.states.iter().map(|state| state.migration_share))?;
+    if let Some(birth_state) = &dimension.birth_state {
+        if !known(birth_state) {
+            return Err(format!("Unknown birth state {}", birth_state));
+        }
+    }
+    for transition in &dimension.transitions {
+        if !known(&transition.from) || !known(&transition.to) || transition.from == transition.to {
+            return Err(format!("Invalid transition from {} to {}", transition.from, transition.to));
+        }
+        if transition.rates.iter().any(|row| !(0.0..=1.0).contains(&row.male) || !(0.0..=1.0).contains(&row.female)) {
+            return Err("Transition rates must be between 0 and 1".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Check regions and move rates of a multiregional projection
+fn validate_interregional(request: &ProjectionRunRequest, input: &InterregionalInput) -> Result<(), String> {
+    if !request.population.is_empty() {
+        return Err("Population is given by region when interregional migration is used".to_string());
+    }
+    if request.dimension.is_some()
+        || request.backcast_to_year.is_some()
+        || request.include_components.unwrap_or(false)
+    {
+        return Err(
+            "Interregional migration cannot be combined with population dimensions, backcasting \
+             or component detail".to_string(),
+        );
+    }
+    if input.regions.is_empty() {
+        return Err("Interregional migration needs at least one region".to_string());
+    }
+    for (index, region) in input.regions.iter().enumerate() {
+        if region.id.is_empty() {
+            return Err("Region id is required".to_string());
+        }
+        if input.regions[..index].iter().any(|other| other.id == region.id) {
+            return Err(format!("Duplicate region {}", region.id));
+        }
+        if region.migration.is_some() && region.migration_share.is_some() {
+            return Err(format!("Region {} has its own migration and a migration share", region.id));
+        }
+    }
+    validate_migration_shares(input.regions.iter()
+        .filter(|region| region.migration.is_none())
+        .map(|region| region.migration_share))?;
+    let own_rates = input.regions.iter().any(|region| region.mortality.is_some() || region.fertility.is_some());
+    if own_rates && (request.include_stable_population.unwrap_or(false) || request.include_life_tables.unwrap_or(false)) {
+        return Err("Stable population and life tables need rates shared by all regions".to_string());
+    }
+    let known = |id: &str| input.regions.iter().any(|region| region.id == id);
+    for flow in input.tables.iter().flat_map(|table| &table.flows) {
+        if !known(&flow.origin_region_id) || !known(&flow.destination_region_id) {
+            return Err(format!("Invalid flow from {} to {}", flow.origin_region_id, flow.destination_region_id));
+        }
+        if !(0.0..=1.0).contains(&flow.male) || !(0.0..=1.0).contains(&flow.female) {
+            return Err("Interregional migration rates must be between 0 and 1".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Check explicit migration shares: each between 0 and 1, summing to at
+/// most 1, or exactly 1 when every share is given
+fn validate_migration_shares(shares: impl Iterator<Item = Option<f64>>) -> Result<(), String> {
+    let shares: Vec<Option<f64>> = shares.collect();
+    if shares.iter().flatten().any(|share| !(0.0..=1.0).contains(share)) {
+        return Err("Migration share must be between 0 and 1".to_string());
+    }
+    let explicit: f64 = shares.iter().flatten().sum();
+    let all_explicit = shares.iter().all(Option::is_some);
+    if explicit > 1.0 + SHARE_TOLERANCE || (all_explicit && (explicit - 1.0).abs() > SHARE_TOLERANCE) {
+        return Err("Migration shares must sum to 1".to_string());
+    }
+    Ok(())
+}
+
+/// Share of the net migrants assigned to each (explicit share, base
+/// population) pair of a state or region
+///
+/// Those without an explicit share split what the others leave by their
+/// base population, or evenly when they have none.
+fn migration_shares(parts: &[(Option<f64>, &[PopulationRow])]) -> Vec<f64> {
+    let part_total = |rows: &[PopulationRow]| rows.iter().map(|row| row.male + row.female).sum::<f64>();
+    let unset: Vec<&[PopulationRow]> = parts.iter().filter(|(share, _)| share.is_none()).map(|&(_, rows)| rows).collect();
+    let remainder = (1.0 - parts.iter().filter_map(|&(share, _)| share).sum::<f64>()).max(0.0);
+    let unset_total: f64 = unset.iter().map(|rows| part_total(rows)).sum();
+    parts.iter().map(|&(share, rows)| match share {
+        Some(share) => share,
+        None if unset_total > 0.0 => remainder * part_total(rows) / unset_total,
+        None => remainder / unset.len() as f64,
+    }).collect()
+}
+
+/// Project the states of an extra dimension, each starting from the common model
+fn run_multistate_projection(
+    request: &ProjectionRunRequest,
+    dimension: &DimensionInput,
+    template: &CohortComponentModel,
+    region_id: &str,
+    lee_carter: Option<LeeCarterFit>,
+    start: Instant,
+) -> Result<CohortRun, String> {
+    let state_ids: Vec<String> = dimension.states.iter().map(|state| state.id.clone()).collect();
+    let mut model = MultistateModel::new(&state_ids, template);
+    info!("🧩 Projecting {} by {} states", dimension.name, state_ids.len());
+
+    let parts: Vec<(Option<f64>, &[PopulationRow])> = dimension.states.iter()
+        .map(|state| (state.migration_share, state.population.as_slice()))
+        .collect();
+    let shares = migration_shares(&parts);
+    for (state, &share) in dimension.states.iter().zip(&shares) {
+        let state_model = model.state_model_mut(&state.id).ok_or("Unknown state")?;
+        state_model.load_population(&population_cohorts(&state.population, region_id));
+        state_model.set_rate_adjustment(region_id, RateAdjustment {
+            fertility: state.relative_fertility.unwrap_or(1.0),
+            male_mortality: state.relative_mortality_male.unwrap_or(1.0),
+            female_mortality: state.relative_mortality_female.unwrap_or(1.0),
+            migration: 1.0,
+        });
+
+        // Net migrant counts are split between the states
+        if let Some(migration) = request.migration.as_ref().filter(|rows| !rows.is_empty()) {
+            state_model.load_migration_table(migration_table(region_id, request.base_year, &scaled_migration(migration, share)));
+        }
+        for anchor in request.migration_series.iter().flatten() {
+            state_model.load_migration_table(migration_table(region_id, anchor.year, &scaled_migration(&anchor.rows, share)));
+        }
+        // and so are the migrants of absolute migration shocks
+        for shock in request.shocks.iter().flatten() {
+            let mut shock = shock.clone();
+            if let (ShockType::Migration, ShockModifier::Absolute { value }) = (shock.shock_type, &mut shock.modifier) {
+                *value *= share;
+            }
+            state_model.add_shock(shock);
+        }
+    }
+    for transition in &dimension.transitions {
+        model.load_transition_table(TransitionTable {
+            region_id: region_id.to_string(),
+            year: transition.year.unwrap_or(request.base_year),
+            from_state: transition.from.clone(),
+            to_state: transition.to.clone(),
+            rates: transition.rates.iter().map(|row| TransitionRate {
+                age: row.age,
+                male: row.male,
+                female: row.female,
+            }).collect(),
+        })?;
+    }
+    if let Some(birth_state) = &dimension.birth_state {
+        model.set_birth_state(birth_state)?;
+    }
+
+    let regions = vec![region_id.to_string()];
+    let mut results = Vec::new();
+    let mut state_results = Vec::new();
+    let cohorts = model.get_cohorts();
+    let mut population_snapshots = vec![capture_population_snapshot(&cohorts, request.base_year)];
+    let mut populations = vec![workspace_population(request, &cohorts, request.base_year)];
+
+    for year in request.base_year..=request.end_year {
+        let year_result = model.project_one_year(year, &regions);
+        let total = &year_result.total;
+        results.push(ProjectionYearResult {
+            year,
+            total_population: total.total_population.round() as i64,
+            births: total.births.round() as i64,
+            deaths: total.deaths.round() as i64,
+            net_migration: total.net_migration.round() as i64,
+            unmet_emigration: total.unmet_emigration.round() as i64,
+            natural_change: total.natural_change.round() as i64,
+            growth_rate: total.growth_rate,
+            e0_male: None,
+            e0_female: None,
+            e65_male: None,
+            e65_female: None,
+            indicators: region_indicators(total, region_id),
+            components: None,
+            audit: None,
+            regions: None,
+        });
+        for state in &year_result.states {
+            let cohorts = model.state_model(&state.state_id).map(|state_model| state_model.get_cohorts()).unwrap_or_default();
+            state_results.push(StateYearResult {
+                year,
+                state: state.state_id.clone(),
+                total_population: state.projection.total_population.round() as i64,
+                births: state.projection.births.round() as i64,
+                deaths: state.projection.deaths.round() as i64,
+                net_migration: state.projection.net_migration.round() as i64,
+                transfers_in: state.transfers_in.round() as i64,
+                transfers_out: state.transfers_out.round() as i64,
+                population: capture_population_snapshot(&cohorts, year + 1),
+            });
+        }
+        let cohorts = model.get_cohorts();
+        population_snapshots.push(capture_population_snapshot(&cohorts, year + 1));
+        populations.push(workspace_population(request, &cohorts, year + 1));
+    }
+
+    let processing_time = start.elapsed().as_millis() as u64;
+    let response = ProjectionRunResponse {
+        workspace_id: request.workspace_id.clone(),
+        success: true,
+        years: results,
+        processing_time_ms: processing_time,
+        input_stats: Some(input_stats(request, request.end_year - request.base_year + 1)),
+        population_by_year: Some(population_snapshots),
+        lee_carter,
+        states: Some(state_results),
+        ..Default::default()
+    };
+    Ok(CohortRun { response, populations })
+}
+
+/// Fit Lee-Carter to the mortality history up to and including the base year
+///
+/// The base-year `mortality` table is the jump-off; history rows for the
+/// base year or later are ignored.
+fn fit_lee_carter(
+    request: &ProjectionRunRequest,
+    region_id: &str,
+    history: &[YearRows<MortalityRow>],
+) -> Result<LeeCarterFit, String> {
+    let mut tables: Vec<MortalityTable> = history.iter()
+        .filter(|rows| rows.year < request.base_year)
+        .map(|rows| mortality_table(region_id, rows.year, &rows.rows))
+        .collect();
+    tables.push(mortality_table(region_id, request.base_year, &request.mortality));
+    LeeCarterFit::fit(&tables)
+}
+
+/// Run an abridged projection: five-year groups in five-year steps
+///
+/// Steps start at the base year and continue up to the step starting at the
+/// end year, a whole number of steps later. Each result row is the step
+/// starting that year.
+fn run_abridged_projection(
+    request: &ProjectionRunRequest,
+    region_id: &str,
+    start: Instant,
+) -> ProjectionRunResponse {
+    let mut model = AbridgedModel::new();
+    if let Some(open_age) = request.open_age_group {
+        model.set_open_age_group(open_age);
+    }
+    model.load_population(&population_cohorts(&request.population, region_id));
+
+    let birth_survival = request.birth_survival.as_ref();
+    model.load_survival_table(SurvivalTable {
+        region_id: region_id.to_string(),
+        year: request.base_year,
+        ratios: request.survival_ratios.iter().flatten().map(|row| SurvivalRatio {
+            age: row.age,
+            male: row.male,
+            female: row.female,
+        }).collect(),
+        birth_male: birth_survival.map(|row| row.male).unwrap_or(0.0),
+        birth_female: birth_survival.map(|row| row.female).unwrap_or(0.0),
+    });
+    model.load_fertility_table(fertility_table(
+        region_id,
+        request.base_year,
+        &request.fertility,
+        request.sex_ratio_at_birth,
+    ));
+    if let Some(migration) = &request.migration {
+        if !migration.is_empty() {
+            model.load_migration_table(migration_table(region_id, request.base_year, migration));
+        }
+    }
+    for anchor in request.fertility_series.iter().flatten() {
+        model.load_fertility_table(fertility_table(
+            region_id,
+            anchor.year,
+            &anchor.rows,
+            request.sex_ratio_at_birth,
+        ));
+    }
+    for anchor in request.migration_series.iter().flatten() {
+        model.load_migration_table(migration_table(region_id, anchor.year, &anchor.rows));
+    }
+    model.set_interpolation(request.interpolation.unwrap_or_default());
+    model.set_migration_timing(request.migration_timing.unwrap_or_default());
+
+    let regions = vec![region_id.to_string()];
+    let mut results = Vec::new();
+    let mut population_snapshots = vec![capture_population_snapshot(&model.get_cohorts(), request.base_year)];
+
+    for year in (request.base_year..=request.end_year).step_by(AGE_STEP as usize) {
+        let step_result = model.project_step(year, &regions);
+
+        results.push(ProjectionYearResult {
+            year,
+            total_population: step_result.total_population.round() as i64,
+            births: step_result.births.round() as i64,
+            deaths: step_result.deaths.round() as i64,
+            net_migration: step_result.net_migration.round() as i64,
+            unmet_emigration: step_result.unmet_emigration.round() as i64,
+            natural_change: step_result.natural_change.round() as i64,
+            growth_rate: step_result.growth_rate,
+            e0_male: None,
+            e0_female: None,
+            e65_male: None,
+            e65_female: None,
+            indicators: region_indicators(&step_result, region_id),
+            components: None,
+            audit: None,
+            regions: None,
+        });
+        population_snapshots.push(capture_population_snapshot(&model.get_cohorts(), year + AGE_STEP));
+    }
+
+    let processing_time = start.elapsed().as_millis() as u64;
+    info!(
+        "📊 Abridged projection complete: {} steps in {}ms",
+        results.len(),
+        processing_time
+    );
+
+    ProjectionRunResponse {
+        workspace_id: request.workspace_id.clone(),
+        success: true,
+        input_stats: Some(input_stats(request, results.len() as u32 * AGE_STEP)),
+        years: results,
+        processing_time_ms: processing_time,
+        population_by_year: Some(population_snapshots),
+        ..Default::default()
+    }
+}
+
+// ============================================================
+// NATS Handler
+// ============================================================
+
+/// Projection handler that subscribes to projection requests
+pub struct ProjectionHandler {
+    client: Client,
+}
+
+impl ProjectionHandler {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Start listening for projection requests
     pub async fn start(self) -> Result<()> {
         let mut subscriber = self.client.subscribe(SUBJECT_PROJECTION_RUN).await?;
         
@@ -425,11 +1884,8 @@ impl ProjectionHandler {
                             ProjectionRunResponse {
                                 workspace_id: envelope.payload.workspace_id.clone(),
                                 success: false,
-                                years: vec![],
                                 error: Some(err),
-                                processing_time_ms: 0,
-                                input_stats: None,
-                                population_by_year: None,
+                                ..Default::default()
                             }
                         }
                     };
@@ -453,11 +1909,8 @@ impl ProjectionHandler {
                         let error_response = ProjectionRunResponse {
                             workspace_id: "unknown".to_string(),
                             success: false,
-                            years: vec![],
                             error: Some(format!("Failed to parse request: {}", e)),
-                            processing_time_ms: 0,
-                            input_stats: None,
-                            population_by_year: None,
+                            ..Default::default()
                         };
                         let error_envelope = MessageEnvelope::new(error_response, None);
                         let response_json = serde_json::to_string(&error_envelope)?;
@@ -499,6 +1952,36 @@ mod tests {
                 FertilityRow { age: 30, rate: 0.1 },
             ],
             migration: None,
+            mortality_series: None,
+            mortality_history: None,
+            emigration_rates: None,
+            emigration_series: None,
+            fertility_targets: None,
+            fertility_model: None,
+            fertility_series: None,
+            migration_series: None,
+            interpolation: None,
+            migration_timing: None,
+            infant_separation_factor: None,
+            fertility_exposure: None,
+            open_age_group: None,
+            age_interval: None,
+            survival_ratios: None,
+            birth_survival: None,
+            include_stable_population: None,
+            include_life_tables: None,
+            include_components: None,
+            audit: None,
+            backcast_to_year: None,
+            dimension: None,
+            interregional_migration: None,
+            headship_rates: None,
+            participation_rates: None,
+            participation_targets: None,
+            demand_profiles: None,
+            fiscal: None,
+            shocks: None,
+            stochastic: None,
         }
     }
 
@@ -546,6 +2029,873 @@ mod tests {
         assert_eq!(year_2024.net_migration, 200);
     }
 
+    /// Request with rates for every age, so cohorts survive across years
+    fn full_age_request() -> ProjectionRunRequest {
+        let mut request = sample_request();
+        request.population = (0..=40).map(|age| PopulationRow { age, male: 1000.0, female: 1000.0 }).collect();
+        request.mortality = (0..=100).map(|age| MortalityRow { age, male: 0.002, female: 0.001 }).collect();
+        request
+    }
+
+    #[test]
+    fn test_run_projection_with_mortality_series() {
+        let mut request = full_age_request();
+        request.end_year = 2034;
+        let baseline = run_projection(&request).unwrap();
+
+        // Mortality doubles by 2034
+        request.mortality_series = Some(vec![YearRows {
+            year: 2034,
+            rows: request.mortality.iter().map(|row| MortalityRow {
+                age: row.age,
+                male: row.male * 2.0,
+                female: row.female * 2.0,
+            }).collect(),
+        }]);
+        let declining = run_projection(&request).unwrap();
+
+        // Base year is unchanged, later years have more deaths
+        assert_eq!(declining.years[0].deaths, baseline.years[0].deaths);
+        assert!(declining.years[5].total_population < baseline.years[5].total_population);
+    }
+
+    #[test]
+    fn test_run_projection_constant_interpolation_holds_base_rates() {
+        let mut request = full_age_request();
+        request.fertility_series = Some(vec![YearRows {
+            year: 2026,
+            rows: vec![FertilityRow { age: 30, rate: 0.2 }],
+        }]);
+        request.interpolation = Some(Interpolation::Constant);
+        let constant = run_projection(&request).unwrap();
+
+        request.interpolation = Some(Interpolation::Linear);
+        let linear = run_projection(&request).unwrap();
+
+        // 2025 is between anchors: constant keeps 0.1, linear uses 0.15
+        assert!(linear.years[1].births > constant.years[1].births);
+        // 2026 is the anchor year itself
+        assert!(constant.years[2].births > constant.years[1].births);
+    }
+
+    #[test]
+    fn test_run_projection_migration_timing() {
+        let mut request = sample_request();
+        request.migration = Some(vec![
+            MigrationRow { age: 30, male: 1000.0, female: 1000.0 },
+        ]);
+        let start = run_projection(&request).unwrap();
+
+        request.migration_timing = Some(MigrationTiming::End);
+        let end = run_projection(&request).unwrap();
+
+        // Same migration, but end-of-year migrants neither die nor give birth
+        assert_eq!(start.years[0].net_migration, end.years[0].net_migration);
+        assert!(end.years[0].births < start.years[0].births);
+        assert!(end.years[0].deaths < start.years[0].deaths);
+    }
+
+    #[test]
+    fn test_run_projection_error_invalid_separation_factor() {
+        let mut request = sample_request();
+        request.infant_separation_factor = Some(1.5);
+        
+        let result = run_projection(&request);
+        
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("separation factor"));
+    }
+
+    #[test]
+    fn test_run_projection_open_age_group() {
+        let mut request = full_age_request();
+        request.population.push(PopulationRow { age: 95, male: 10.0, female: 10.0 });
+        request.open_age_group = Some(85);
+
+        let result = run_projection(&request).unwrap();
+
+        // 95-year-olds are reported in the 85+ group
+        let snapshot = &result.population_by_year.unwrap()[0];
+        assert_eq!(snapshot.cohorts.last().unwrap().age, 85);
+    }
+
+    #[test]
+    fn test_run_projection_error_open_age_group_too_low() {
+        let mut request = sample_request();
+        request.open_age_group = Some(40);
+
+        let result = run_projection(&request);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Open age group"));
+    }
+
+    #[test]
+    fn test_run_projection_stable_population() {
+        let mut request = full_age_request();
+        request.include_stable_population = Some(true);
+
+        let result = run_projection(&request).unwrap();
+
+        let stable = result.stable_population.unwrap();
+        assert_eq!(stable.len(), 2);
+        assert_eq!(stable[0].gender, Gender::Female);
+        assert_eq!(stable[0].leslie_survival.len(), 121);
+        // 10% at age 30 only: R0 of daughters is far below replacement
+        assert!(stable[0].net_reproduction_rate.unwrap() < 0.1);
+        assert!(stable[0].intrinsic_growth_rate.unwrap() < 0.0);
+        assert!(run_projection(&full_age_request()).unwrap().stable_population.is_none());
+    }
+
+    #[test]
+    fn test_run_projection_stochastic() {
+        let mut request = full_age_request();
+        request.stochastic = Some(serde_json::from_value(serde_json::json!({
+            "simulations": 20,
+            "seed": 7,
+            "tfr": { "process": { "type": "autoregressive", "phi": 0.8 }, "sd": 0.05 },
+            "e0": { "sd": 0.3 },
+            "migration": { "sd": 0.1 }
+        })).unwrap());
+
+        let result = run_projection(&request).unwrap();
+
+        let stochastic = result.stochastic.unwrap();
+        assert_eq!(stochastic.len(), result.years.len());
+        let last = stochastic.last().unwrap();
+        assert!(last.total_population.lower95 < last.total_population.upper95);
+        assert!(last.tfr.lower80 < 0.1 && last.tfr.upper80 > 0.1);
+    }
+
+    #[test]
+    fn test_run_projection_error_invalid_stochastic_settings() {
+        let mut request = sample_request();
+        request.stochastic = Some(serde_json::from_value(serde_json::json!({ "simulations": 0 })).unwrap());
+
+        let result = run_projection(&request);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Simulations"));
+    }
+
+    #[test]
+    fn test_run_projection_lee_carter_forecast() {
+        let mut request = full_age_request();
+        request.end_year = 2034;
+        let baseline = run_projection(&request).unwrap();
+
+        // Mortality was 50% higher ten years before the base year
+        request.mortality_history = Some(vec![YearRows {
+            year: 2014,
+            rows: request.mortality.iter().map(|row| MortalityRow {
+                age: row.age,
+                male: row.male * 1.5,
+                female: row.female * 1.5,
+            }).collect(),
+        }]);
+        let result = run_projection(&request).unwrap();
+
+        // Base year keeps its rates, the decline continues afterwards
+        let fit = result.lee_carter.unwrap();
+        assert_eq!(fit.years, vec![2014, 2024]);
+        assert!(fit.female.drift < 0.0);
+        assert_eq!(result.years[0].deaths, baseline.years[0].deaths);
+        assert!(result.years[10].deaths < baseline.years[10].deaths);
+    }
+
+    #[test]
+    fn test_run_projection_error_lee_carter_needs_history() {
+        let mut request = full_age_request();
+        request.mortality_history = Some(vec![]);
+
+        let result = run_projection(&request);
+
+        assert!(result.unwrap_err().contains("at least two years"));
+    }
+
+    #[test]
+    fn test_run_projection_fertility_targets() {
+        let mut request = full_age_request();
+        request.end_year = 2034;
+        request.fertility = (20..=40).map(|age| FertilityRow { age, rate: 0.07 }).collect();
+        let baseline = run_projection(&request).unwrap();
+
+        // TFR 1.47 doubles by 2034 with a later mean age
+        request.fertility_targets = Some(serde_json::from_value(serde_json::json!([
+            { "year": 2034, "tfr": 2.94, "meanAge": 32.0 }
+        ])).unwrap());
+        let result = run_projection(&request).unwrap();
+
+        assert_eq!(result.years[0].births, baseline.years[0].births);
+        assert!(result.years[10].births > baseline.years[10].births);
+    }
+
+    #[test]
+    fn test_run_projection_error_invalid_fertility_target() {
+        let mut request = full_age_request();
+        request.fertility_targets = Some(vec![FertilityTarget { year: 2030, tfr: None, mean_age: Some(60.0) }]);
+
+        let result = run_projection(&request);
+
+        assert!(result.unwrap_err().contains("mean age"));
+    }
+
+    #[test]
+    fn test_run_projection_emigration_rates() {
+        let mut request = full_age_request();
+        request.emigration_rates = Some((0..=100).map(|age| EmigrationRow { age, male: 0.01, female: 0.01 }).collect());
+
+        let result = run_projection(&request).unwrap();
+
+        // About 1% of ~82,000 leave in the first year
+        assert!(result.years[0].net_migration < -700);
+        assert_eq!(result.years[0].unmet_emigration, 0);
+    }
+
+    #[test]
+    fn test_run_projection_reports_unmet_emigration() {
+        let mut request = sample_request();
+        request.migration = Some(vec![MigrationRow { age: 30, male: -3000.0, female: 0.0 }]);
+
+        let result = run_projection(&request).unwrap();
+
+        // 2000 men aged 30 can leave, 1000 cannot
+        assert_eq!(result.years[0].net_migration, -2000);
+        assert_eq!(result.years[0].unmet_emigration, 1000);
+    }
+
+    #[test]
+    fn test_run_projection_error_invalid_emigration_rate() {
+        let mut request = full_age_request();
+        request.emigration_rates = Some(vec![EmigrationRow { age: 30, male: 1.5, female: 0.0 }]);
+
+        let result = run_projection(&request);
+
+        assert!(result.unwrap_err().contains("Emigration rates"));
+    }
+
+    #[test]
+    fn test_run_projection_life_expectancy() {
+        let result = run_projection(&full_age_request()).unwrap();
+
+        // Constant q above age 100 is held: e_x = (1 - q/2) / q above
+        // infancy; infant deaths at a0 = 0.053 shorten e0 by q·(1/2 - a0)
+        let year = &result.years[0];
+        assert!((year.e0_female.unwrap() - (999.5 - 0.001 * (0.5 - 0.053))).abs() < 1e-6);
+        assert!((year.e65_male.unwrap() - 499.5).abs() < 1e-6);
+        assert!(year.e0_male < year.e0_female);
+        assert!(result.life_tables.is_none());
+    }
+
+    #[test]
+    fn test_run_projection_life_tables() {
+        let mut request = full_age_request();
+        request.include_life_tables = Some(true);
+        request.open_age_group = Some(100);
+
+        let result = run_projection(&request).unwrap();
+
+        let tables = result.life_tables.unwrap();
+        assert_eq!(tables.len(), 2 * result.years.len());
+        let table = &tables[0];
+        assert_eq!(table.ages.len(), 101);
+        assert_eq!(table.lx[0], 1.0);
+        assert!((table.dx[0] - 0.002).abs() < 1e-12);
+        assert!((table.ex[0] - result.years[0].e0_male.unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_run_projection_indicators() {
+        let result = run_projection(&sample_request()).unwrap();
+
+        let year = &result.years[0];
+        let indicators = &year.indicators;
+        assert!((indicators.tfr - 0.1).abs() < 1e-12);
+        assert!(indicators.crude_birth_rate > 0.0);
+        assert!(indicators.crude_death_rate > 0.0);
+        // Nobody is 65+ yet
+        assert_eq!(indicators.share_65_plus, 0.0);
+        assert_eq!(indicators.potential_support_ratio, 0.0);
+        let shares = indicators.share_under_15 + indicators.share_15_to_64 + indicators.share_65_plus;
+        assert!((shares - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_projection_components() {
+        let mut request = full_age_request();
+        request.include_components = Some(true);
+        request.migration = Some(vec![MigrationRow { age: 25, male: 10.0, female: -5.0 }]);
+
+        let result = run_projection(&request).unwrap();
+
+        let year = &result.years[0];
+        let components = year.components.as_ref().unwrap();
+        let deaths: f64 = components.deaths.iter().map(|row| row.male + row.female).sum::<f64>()
+            + components.newborn_deaths_male + components.newborn_deaths_female;
+        let births: f64 = components.births_by_mother_age.iter().map(|row| row.births).sum();
+        assert_eq!(deaths.round() as i64, year.deaths);
+        assert_eq!(births.round() as i64, year.births);
+        assert!((components.deaths[40].male - 2.0).abs() < 1e-9);
+        assert_eq!((components.migration[25].male, components.migration[25].female), (10.0, -5.0));
+        assert!(run_projection(&full_age_request()).unwrap().years[0].components.is_none());
+    }
+
+    #[test]
+    fn test_run_projection_audit() {
+        let mut request = full_age_request();
+        request.audit = Some(true);
+        request.migration = Some(vec![MigrationRow { age: 25, male: -3000.0, female: 0.0 }]);
+
+        let result = run_projection(&request).unwrap();
+
+        let audit = result.years[0].audit.unwrap();
+        assert!(audit.discrepancy.abs() < 1e-6);
+        assert!((audit.clamped_emigration - 2000.0).abs() < 1e-9);
+        assert!(run_projection(&full_age_request()).unwrap().years[0].audit.is_none());
+
+        let mut abridged = abridged_request();
+        abridged.audit = Some(true);
+        assert!(run_projection(&abridged).unwrap_err().contains("single-year"));
+    }
+
+    #[test]
+    fn test_run_projection_backcast() {
+        let mut request = full_age_request();
+        request.backcast_to_year = Some(2022);
+
+        let result = run_projection(&request).unwrap();
+
+        let backcast = result.backcast.unwrap();
+        assert_eq!(backcast.iter().map(|year| year.year).collect::<Vec<_>>(), vec![2022, 2023]);
+        // 1000 men aged 40 in 2024 were 1000 / 0.998 aged 39 a year earlier
+        let cohort = backcast[1].population.cohorts.iter().find(|cohort| cohort.age == 39).unwrap();
+        assert_eq!(cohort.male, 1002);
+        // 2000 infants in 2024, but only 100 births from women aged 30
+        assert!(backcast[1].newborn_discrepancy > 1800.0);
+        assert!(result.years[0].total_population > 0);
+
+        request.backcast_to_year = Some(2024);
+        assert!(run_projection(&request).unwrap_err().contains("before the base year"));
+    }
+
+    #[test]
+    fn test_run_projection_households() {
+        let mut request = sample_request();
+        let rows = |rate| vec![HeadshipRow { age: 20, male: rate, female: rate }];
+        request.headship_rates = Some(vec![
+            HeadshipInput { household_type: "single".to_string(), year: None, rows: rows(0.2) },
+            HeadshipInput { household_type: "couple".to_string(), year: None, rows: rows(0.3) },
+            HeadshipInput { household_type: "couple".to_string(), year: Some(2026), rows: rows(0.4) },
+        ]);
+
+        let result = run_projection(&request).unwrap();
+
+        let households = result.households.unwrap();
+        let snapshots = result.population_by_year.unwrap();
+        assert_eq!(households.len(), snapshots.len());
+        // 4000 persons aged 30 head 0.2 + 0.3 households each in the base year
+        let base = &households[0];
+        assert_eq!(base.year, 2024);
+        assert!((base.total_households - 2000.0).abs() < 1e-9);
+        assert!((base.average_size - snapshots[0].total as f64 / 2000.0).abs() < 1e-9);
+        assert_eq!(base.types[0].by_age_of_head[0].age, 30);
+        // Couple headship is halfway to 0.4 in 2025
+        let adults: i64 = snapshots[1].cohorts.iter().filter(|c| c.age >= 20).map(|c| c.male + c.female).sum();
+        assert!((households[1].types[0].households - 0.35 * adults as f64).abs() < 1e-9);
+
+        request.headship_rates = Some(vec![HeadshipInput { household_type: "single".to_string(), year: None, rows: rows(1.5) }]);
+        assert!(run_projection(&request).unwrap_err().contains("Headship rates"));
+    }
+
+    #[test]
+    fn test_run_projection_households_use_unrounded_population() {
+        let mut request = sample_request();
+        request.population = vec![PopulationRow { age: 30, male: 0.4, female: 0.4 }];
+        request.headship_rates = Some(vec![HeadshipInput {
+            household_type: "single".to_string(),
+            year: None,
+            rows: vec![HeadshipRow { age: 0, male: 1.0, female: 1.0 }],
+        }]);
+
+        let result = run_projection(&request).unwrap();
+
+        // The snapshot rounds both cohorts to zero; households keep them
+        assert_eq!(result.population_by_year.unwrap()[0].total, 0);
+        assert!((result.households.unwrap()[0].total_households - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_projection_labor_force() {
+        let mut request = sample_request();
+        request.participation_rates = Some(vec![ParticipationInput {
+            year: None,
+            rows: vec![
+                ParticipationRow { age: 15, male: 0.8, female: 0.7 },
+                ParticipationRow { age: 60, male: 0.2, female: 0.1 },
+            ],
+        }]);
+        request.participation_targets = Some(vec![
+            ParticipationTarget { year: 2026, min_age: 15, max_age: 59, male: None, female: Some(0.9) },
+        ]);
+
+        let result = run_projection(&request).unwrap();
+
+        let labor_force = result.labor_force.unwrap();
+        let snapshots = result.population_by_year.unwrap();
+        assert_eq!(labor_force.len(), snapshots.len());
+        // 2000 men and 2000 women aged 30 in the base year
+        let base = &labor_force[0];
+        assert!((base.male_labor_force - 1600.0).abs() < 1e-9);
+        assert!((base.female_labor_force - 1400.0).abs() < 1e-9);
+        let persons = snapshots[0].total as f64;
+        assert!((base.economic_dependency_ratio - (persons - 3000.0) / 3000.0 * 100.0).abs() < 1e-9);
+        // Female participation is halfway to 0.9 in 2025
+        let women: i64 = snapshots[1].cohorts.iter().filter(|c| (15..60).contains(&c.age)).map(|c| c.female).sum();
+        assert!((labor_force[1].female_labor_force - 0.8 * women as f64).abs() < 1e-9);
+
+        request.participation_targets = Some(vec![
+            ParticipationTarget { year: 2024, min_age: 60, max_age: 69, male: Some(0.3), female: None },
+        ]);
+        assert!(run_projection(&request).unwrap_err().contains("after the base year"));
+    }
+
+    #[test]
+    fn test_run_projection_service_demand() {
+        let mut request = sample_request();
+        request.demand_profiles = Some(serde_json::from_value(serde_json::json!([
+            { "name": "pupils", "values": [
+                { "age": 6, "male": 1.0, "female": 1.0 },
+                { "age": 15, "male": 0.0, "female": 0.0 },
+            ] },
+            { "name": "bedDays", "unit": "bed-days", "values": [{ "age": 0, "male": 2.0, "female": 1.5 }] },
+        ])).unwrap());
+
+        let result = run_projection(&request).unwrap();
+
+        let demand = result.service_demand.unwrap();
+        let snapshots = result.population_by_year.unwrap();
+        assert_eq!(demand.len(), snapshots.len());
+        let base = &demand[0];
+        assert_eq!(base.profiles[0].name, "pupils");
+        assert_eq!(base.profiles[0].total, 0.0);
+        let bed_days = &base.profiles[1];
+        assert_eq!(bed_days.regions[0].region_id, REGION_ID);
+        let expected = 2.0 * snapshots[0].total_male as f64 + 1.5 * snapshots[0].total_female as f64;
+        assert!((bed_days.total - expected).abs() < 1e-9);
+
+        let pupils = request.demand_profiles.as_ref().unwrap()[0].clone();
+        request.demand_profiles = Some(vec![pupils.clone(), pupils]);
+        assert!(run_projection(&request).unwrap_err().contains("Duplicate demand profile"));
+    }
+
+    #[test]
+    fn test_run_projection_fiscal() {
+        let mut request = sample_request();
+        request.fiscal = Some(serde_json::from_value(serde_json::json!({
+            "productivityGrowth": 0.02,
+            "profiles": [
+                { "name": "contributions", "flow": "revenue", "values": [
+                    { "age": 20, "male": 5.0, "female": 4.0 },
+                    { "age": 65, "male": 0.0, "female": 0.0 },
+                ] },
+                { "name": "education", "flow": "expenditure", "values": [
+                    { "age": 0, "male": 1.0, "female": 1.0 },
+                    { "age": 25, "male": 0.0, "female": 0.0 },
+                ] },
+            ],
+        })).unwrap());
+
+        let result = run_projection(&request).unwrap();
+
+        let fiscal = result.fiscal.unwrap();
+        let snapshots = result.population_by_year.unwrap();
+        assert_eq!(fiscal.len(), snapshots.len());
+        // 2000 men and 2000 women aged 30 pay; 3900 children use schools
+        let base = &fiscal[0];
+        assert!((base.revenue - 18000.0).abs() < 1e-9);
+        assert!((base.expenditure - 3900.0).abs() < 1e-9);
+        assert!((base.balance - 14100.0).abs() < 1e-9);
+        // Values grow by 2% a year
+        let contributors = |sex: fn(&CohortSnapshot) -> i64| snapshots[1].cohorts.iter()
+            .filter(|c| (20..65).contains(&c.age))
+            .map(sex)
+            .sum::<i64>() as f64;
+        let expected = (5.0 * contributors(|c| c.male) + 4.0 * contributors(|c| c.female)) * 1.02;
+        assert!((fiscal[1].revenue - expected).abs() < 1e-6);
+
+        request.fiscal.as_mut().unwrap().productivity_growth = -2.0;
+        assert!(run_projection(&request).unwrap_err().contains("Productivity growth"));
+    }
+
+    #[test]
+    fn test_run_projection_shocks() {
+        let mut request = sample_request();
+        let baseline = run_projection(&request).unwrap();
+        request.shocks = Some(serde_json::from_value(serde_json::json!([
+            {
+                "id": "boom", "name": "Baby Boom", "type": "fertility",
+                "startYear": 2024, "endYear": 2026,
+                "targetRegions": "all", "targetGenders": ["female"], "targetAges": { "min": 20, "max": 40 },
+                "modifier": 1.5,
+            },
+            {
+                "id": "refugees", "name": "Refugee Influx", "type": "migration",
+                "startYear": 2025, "endYear": 2025,
+                "targetRegions": "all", "targetGenders": "all", "targetAges": "all",
+                "modifier": { "type": "absolute", "value": 1000.0 },
+            },
+        ])).unwrap());
+
+        let result = run_projection(&request).unwrap();
+
+        assert_eq!(result.years[0].births, (baseline.years[0].births as f64 * 1.5).round() as i64);
+        assert_eq!(result.years[1].net_migration - baseline.years[1].net_migration, 1000);
+        assert_eq!(result.years[2].net_migration, baseline.years[2].net_migration);
+
+        request.shocks.as_mut().unwrap()[0].end_year = 2020;
+        assert!(run_projection(&request).unwrap_err().contains("ends before it starts"));
+    }
+
+    #[test]
+    fn test_run_projection_function_shock() {
+        let mut request = sample_request();
+        let baseline = run_projection(&request).unwrap();
+        let shock = |expression: &str| serde_json::json!([{
+            "id": "boom", "name": "Baby Boom", "type": "fertility",
+            "startYear": 2024, "endYear": 2026,
+            "targetRegions": "all", "targetGenders": "all", "targetAges": "all",
+            "modifier": { "type": "function", "expression": expression },
+        }]);
+        request.shocks = Some(serde_json::from_value(shock("base × (1 + 0.5·exp(−t))")).unwrap());
+
+        let result = run_projection(&request).unwrap();
+
+        assert_eq!(result.years[0].births, (baseline.years[0].births as f64 * 1.5).round() as i64);
+
+        request.shocks = Some(serde_json::from_value(shock("base * (1 +")).unwrap());
+        assert!(run_projection(&request).unwrap_err().contains("Shock Baby Boom: Invalid expression"));
+    }
+
+    /// Full-age request split 60/40 into regions A and B, with 10% of A's
+    /// cohorts up to age 40 moving to B every year
+    fn interregional_request() -> ProjectionRunRequest {
+        let mut request = full_age_request();
+        request.migration = Some(vec![MigrationRow { age: 25, male: 100.0, female: 100.0 }]);
+        let region = |id: &str, share: f64| serde_json::json!({
+            "id": id,
+            "population": request.population.iter()
+                .map(|row| serde_json::json!({ "age": row.age, "male": row.male * share, "female": row.female * share }))
+                .collect::<Vec<_>>(),
+        });
+        let input = serde_json::json!({
+            "regions": [region("A", 0.6), region("B", 0.4)],
+            "tables": [{
+                "year": 2024,
+                "flows": (0..=40).map(|age| serde_json::json!({
+                    "originRegionId": "A", "destinationRegionId": "B", "age": age, "male": 0.1, "female": 0.1,
+                })).collect::<Vec<_>>(),
+            }],
+        });
+        request.interregional_migration = Some(serde_json::from_value(input).unwrap());
+        request.population = Vec::new();
+        request
+    }
+
+    #[test]
+    fn test_run_projection_interregional_migration() {
+        let mut request = interregional_request();
+        let mut plain = full_age_request();
+        plain.migration = request.migration.clone();
+
+        let result = run_projection(&request).unwrap();
+        let expected = run_projection(&plain).unwrap();
+
+        // Regions share the rates, so moves between them leave national totals unchanged
+        for (year, plain_year) in result.years.iter().zip(&expected.years) {
+            assert!((year.total_population - plain_year.total_population).abs() <= 1);
+            assert!((year.births - plain_year.births).abs() <= 1);
+            assert_eq!(year.net_migration, plain_year.net_migration);
+            assert!((year.indicators.mean_age - plain_year.indicators.mean_age).abs() < 1e-6);
+        }
+        assert_eq!(result.input_stats.unwrap().total_initial_population, 82_000);
+
+        // Audited, the moves cancel out nationally and every year balances
+        request.audit = Some(true);
+        for year in run_projection(&request).unwrap().years {
+            let audit = year.audit.unwrap();
+            assert!(audit.internal_migration.abs() < 1e-6);
+            assert!(audit.discrepancy.abs() < 1e-6);
+        }
+
+        let tables = &mut request.interregional_migration.as_mut().unwrap().tables;
+        tables[0].flows[0].destination_region_id = "Z".to_string();
+        assert!(run_projection(&request).unwrap_err().contains("Invalid flow from A to Z"));
+    }
+
+    #[test]
+    fn test_run_projection_region_results() {
+        // B has doubled mortality and its own migrants instead of a share
+        let mut request = interregional_request();
+        let mortality: Vec<MortalityRow> = request.mortality.iter()
+            .map(|row| MortalityRow { age: row.age, male: row.male * 2.0, female: row.female * 2.0 })
+            .collect();
+        let regions = &mut request.interregional_migration.as_mut().unwrap().regions;
+        regions[1].mortality = Some(mortality);
+        regions[1].migration = Some(vec![MigrationRow { age: 30, male: 50.0, female: 50.0 }]);
+
+        let result = run_projection(&request).unwrap();
+
+        for year in &result.years {
+            let regions = year.regions.as_ref().unwrap();
+            let sum = |value: fn(&RegionYearResult) -> i64| regions.iter().map(value).sum::<i64>();
+            assert!((sum(|region| region.total_population) - year.total_population).abs() <= 1);
+            assert!((sum(|region| region.births) - year.births).abs() <= 1);
+            assert!((sum(|region| region.deaths) - year.deaths).abs() <= 1);
+            assert!((sum(|region| region.net_migration) - year.net_migration).abs() <= 1);
+            // Internal moves balance to zero
+            assert_eq!(sum(|region| region.internal_in), sum(|region| region.internal_out));
+            assert!(regions[0].internal_out > 0 && regions[0].internal_in == 0);
+        }
+        let first = result.years[0].regions.as_ref().unwrap();
+        // A takes all of the request's 200 migrants, B its own 100
+        assert_eq!((first[0].net_migration, first[1].net_migration), (200, 100));
+        assert!(first[1].e0_male.unwrap() < first[0].e0_male.unwrap());
+        assert!(result.years[0].e0_male.is_none());
+        assert_eq!(first[1].population.year, result.years[0].year + 1);
+        assert!(run_projection(&interregional_request()).unwrap().years[0].e0_male.is_some());
+
+        let mut invalid = request.clone();
+        invalid.interregional_migration.as_mut().unwrap().regions[1].migration_share = Some(0.5);
+        assert!(run_projection(&invalid).unwrap_err().contains("own migration and a migration share"));
+        let mut invalid = request.clone();
+        invalid.include_life_tables = Some(true);
+        assert!(run_projection(&invalid).unwrap_err().contains("shared by all regions"));
+    }
+
+    #[test]
+    fn test_run_projection_region_indicators() {
+        // B has twice A's fertility
+        let mut request = interregional_request();
+        let fertility: Vec<FertilityRow> = request.fertility.iter()
+            .map(|row| FertilityRow { age: row.age, rate: row.rate * 2.0 })
+            .collect();
+        request.interregional_migration.as_mut().unwrap().regions[1].fertility = Some(fertility);
+
+        let result = run_projection(&request).unwrap();
+
+        let year = &result.years[0];
+        let regions = year.regions.as_ref().unwrap();
+        let (a, b) = (&regions[0].indicators, &regions[1].indicators);
+        assert!((b.tfr - 2.0 * a.tfr).abs() < 1e-9);
+        assert!(b.crude_birth_rate > a.crude_birth_rate);
+        // Movers aged up to 40 make B younger
+        assert!(b.mean_age < a.mean_age);
+        // The national TFR lies between the regions'
+        assert!(a.tfr < year.indicators.tfr && year.indicators.tfr < b.tfr);
+    }
+
+    #[test]
+    fn test_run_projection_region_derived_models() {
+        let mut request = interregional_request();
+        request.demand_profiles = Some(serde_json::from_value(serde_json::json!([
+            { "name": "persons", "values": [{ "age": 0, "male": 1.0, "female": 1.0 }] },
+        ])).unwrap());
+        request.headship_rates = Some(vec![HeadshipInput {
+            household_type: "single".to_string(),
+            year: None,
+            rows: vec![HeadshipRow { age: 20, male: 0.5, female: 0.5 }],
+        }]);
+        request.participation_rates = Some(vec![ParticipationInput {
+            year: None,
+            rows: vec![ParticipationRow { age: 15, male: 0.8, female: 0.7 }],
+        }]);
+        let mut plain = full_age_request();
+        plain.migration = request.migration.clone();
+        plain.headship_rates = request.headship_rates.clone();
+        plain.participation_rates = request.participation_rates.clone();
+
+        let result = run_projection(&request).unwrap();
+        let expected = run_projection(&plain).unwrap();
+
+        // 82 000 persons split 60/40 in the base year
+        let persons = &result.service_demand.unwrap()[0].profiles[0];
+        let regions: Vec<(&str, f64)> = persons.regions.iter()
+            .map(|region| (region.region_id.as_str(), region.demand))
+            .collect();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].0, "A");
+        assert!((regions[0].1 - 49_200.0).abs() < 1e-6);
+        assert_eq!(regions[1].0, "B");
+        assert!((regions[1].1 - 32_800.0).abs() < 1e-6);
+        // Regions share the rates, so households and labor force match the single-region run
+        let households = result.households.unwrap();
+        for (year, plain_year) in households.iter().zip(expected.households.as_ref().unwrap()) {
+            assert!(year.total_households > 0.0);
+            assert!((year.total_households - plain_year.total_households).abs() < 1e-6);
+        }
+        let labor_force = result.labor_force.unwrap();
+        for (year, plain_year) in labor_force.iter().zip(expected.labor_force.as_ref().unwrap()) {
+            assert!(year.male_labor_force > 0.0);
+            assert!((year.male_labor_force - plain_year.male_labor_force).abs() < 1e-6);
+            assert!((year.female_labor_force - plain_year.female_labor_force).abs() < 1e-6);
+        }
+    }
+
+    /// Full-age request with its population split 60/40 into two states
+    fn dimension_request() -> ProjectionRunRequest {
+        let mut request = full_age_request();
+        request.migration = Some(vec![MigrationRow { age: 25, male: 100.0, female: 100.0 }]);
+        let state = |id: &str, share: f64| StateInput {
+            id: id.to_string(),
+            population: request.population.iter()
+                .map(|row| PopulationRow { age: row.age, male: row.male * share, female: row.female * share })
+                .collect(),
+            relative_fertility: None,
+            relative_mortality_male: None,
+            relative_mortality_female: None,
+            migration_share: None,
+        };
+        request.dimension = Some(DimensionInput {
+            name: "education".to_string(),
+            states: vec![state("low", 0.6), state("high", 0.4)],
+            transitions: Vec::new(),
+            birth_state: None,
+        });
+        request.population = Vec::new();
+        request
+    }
+
+    #[test]
+    fn test_run_projection_dimension_without_differentials_matches_total() {
+        let request = dimension_request();
+        let mut plain = full_age_request();
+        plain.migration = request.migration.clone();
+
+        let result = run_projection(&request).unwrap();
+        let expected = run_projection(&plain).unwrap();
+
+        for (year, plain_year) in result.years.iter().zip(&expected.years) {
+            assert_eq!(year.total_population, plain_year.total_population);
+            assert_eq!(year.births, plain_year.births);
+            assert_eq!(year.net_migration, plain_year.net_migration);
+            assert!((year.indicators.tfr - plain_year.indicators.tfr).abs() < 1e-9);
+        }
+        let states = result.states.unwrap();
+        assert_eq!(states.len(), 2 * result.years.len());
+        assert_eq!((states[0].net_migration, states[1].net_migration), (120, 80));
+    }
+
+    #[test]
+    fn test_run_projection_dimension_migration_shares() {
+        // The unset state takes what the explicit share leaves
+        let mut request = dimension_request();
+        request.dimension.as_mut().unwrap().states[0].migration_share = Some(0.3);
+
+        let states = run_projection(&request).unwrap().states.unwrap();
+
+        assert_eq!((states[0].net_migration, states[1].net_migration), (60, 140));
+
+        let mut invalid = request.clone();
+        invalid.dimension.as_mut().unwrap().states[1].migration_share = Some(0.3);
+        assert!(run_projection(&invalid).unwrap_err().contains("sum to 1"));
+    }
+
+    #[test]
+    fn test_run_projection_dimension_transitions_and_differentials() {
+        let mut request = dimension_request();
+        let dimension = request.dimension.as_mut().unwrap();
+        dimension.states[1].relative_fertility = Some(0.5);
+        dimension.transitions.push(TransitionInput {
+            from: "low".to_string(),
+            to: "high".to_string(),
+            year: None,
+            rates: vec![TransitionRow { age: 20, male: 0.5, female: 0.5 }],
+        });
+        dimension.birth_state = Some("low".to_string());
+
+        let result = run_projection(&request).unwrap();
+
+        let states = result.states.unwrap();
+        let (low, high) = (&states[0], &states[1]);
+        // Half of 600 + 600 aged 20 move up; high-state newborns move down
+        assert_eq!(high.transfers_in, 600);
+        assert_eq!(low.transfers_in, high.births);
+        // 40 women aged 30 at half fertility, 60 at full: 0.1 × (600 + 200)
+        assert_eq!(result.years[0].births, 80);
+        assert!(high.population.cohorts.iter().all(|cohort| cohort.age > 0 || cohort.male + cohort.female == 0));
+
+        let mut invalid = dimension_request();
+        invalid.dimension.as_mut().unwrap().birth_state = Some("none".to_string());
+        assert!(run_projection(&invalid).unwrap_err().contains("birth state"));
+        let mut invalid = dimension_request();
+        invalid.audit = Some(true);
+        assert!(run_projection(&invalid).is_err());
+        let mut invalid = dimension_request();
+        invalid.population = full_age_request().population;
+        assert!(run_projection(&invalid).unwrap_err().contains("by state"));
+    }
+
+    /// Request in five-year groups with survival ratios
+    fn abridged_request() -> ProjectionRunRequest {
+        let mut request = sample_request();
+        request.age_interval = Some(5);
+        request.mortality = vec![];
+        request.population = (0..=80).step_by(5).map(|age| PopulationRow { age, male: 5000.0, female: 5000.0 }).collect();
+        request.fertility = vec![FertilityRow { age: 25, rate: 0.08 }, FertilityRow { age: 30, rate: 0.1 }];
+        request.survival_ratios = Some((0..=100).step_by(5).map(|age| SurvivalRow { age, male: 0.99, female: 0.995 }).collect());
+        request.birth_survival = Some(BirthSurvivalRow { male: 0.995, female: 0.996 });
+        request.end_year = 2034;
+        request
+    }
+
+    #[test]
+    fn test_run_projection_abridged_steps() {
+        let result = run_projection(&abridged_request()).unwrap();
+
+        // 2024, 2029, 2034
+        assert_eq!(result.years.iter().map(|y| y.year).collect::<Vec<_>>(), vec![2024, 2029, 2034]);
+        assert!(result.years[0].births > 0);
+        let snapshots = result.population_by_year.unwrap();
+        assert_eq!(snapshots[1].year, 2029);
+        assert!(snapshots[1].cohorts.iter().all(|c| c.age % 5 == 0));
+    }
+
+    #[test]
+    fn test_run_projection_abridged_rejects_partial_steps_and_single_year_models() {
+        let mut request = abridged_request();
+        request.end_year = 2036;
+        assert!(run_projection(&request).unwrap_err().contains("multiple of 5 years"));
+
+        let mut request = abridged_request();
+        request.participation_rates = Some(vec![ParticipationInput {
+            year: None,
+            rows: vec![ParticipationRow { age: 15, male: 0.7, female: 0.6 }],
+        }]);
+        assert!(run_projection(&request).unwrap_err().contains("single-year age groups"));
+    }
+
+    #[test]
+    fn test_run_projection_error_abridged_without_survival() {
+        let mut request = abridged_request();
+        request.survival_ratios = None;
+
+        let result = run_projection(&request);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Survival ratios"));
+    }
+
+    #[test]
+    fn test_run_projection_error_invalid_age_interval() {
+        let mut request = sample_request();
+        request.age_interval = Some(10);
+
+        let result = run_projection(&request);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Age interval"));
+    }
+
     #[test]
     fn test_run_projection_error_empty_population() {
         let mut request = sample_request();
@@ -574,7 +2924,7 @@ mod tests {
         let result = run_projection(&request).unwrap();
         
         // Should record processing time
-        assert!(result.processing_time_ms >= 0);
+        assert!(result.processing_time_ms < 10_000);
     }
 
     #[test]
@@ -0,0 +1,407 @@
+//! Arithmetic expressions for function shock modifiers
+//!
+//! A small, side-effect free language evaluated per cohort and year:
+//!
+//! - Numbers (`0.5`, `1e-3`), the operators `+ - * / ^` and parentheses;
+//!   `·` and `×` also multiply and `−` also subtracts
+//! - Variables: `base` (the unmodified value), `year`, `age` and `t`
+//!   (years since the shock started)
+//! - Functions: `exp`, `ln`, `sqrt`, `abs`, `min`, `max` and `pow`
+//!
+//! Expressions have no loops, assignments or access to anything beyond the
+//! variables, and their length and nesting are limited, so evaluation time
+//! is bounded by their size.
+
+/// Longest accepted expression (characters)
+const MAX_LENGTH: usize = 1000;
+
+/// Deepest accepted nesting of operators, calls and parentheses
+const MAX_DEPTH: usize = 32;
+
+/// Values of the variables in one evaluation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Variables {
+    pub base: f64,
+    pub year: f64,
+    pub age: f64,
+    pub t: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Variable {
+    Base,
+    Year,
+    Age,
+    T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Exp,
+    Ln,
+    Sqrt,
+    Abs,
+    Min,
+    Max,
+    Pow,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "exp" => Function::Exp,
+            "ln" => Function::Ln,
+            "sqrt" => Function::Sqrt,
+            "abs" => Function::Abs,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "pow" => Function::Pow,
+            _ => return None,
+        })
+    }
+
+    /// Accepted argument counts
+    fn arity(self) -> (usize, usize) {
+        match self {
+            Function::Exp | Function::Ln | Function::Sqrt | Function::Abs => (1, 1),
+            Function::Pow => (2, 2),
+            Function::Min | Function::Max => (1, usize::MAX),
+        }
+    }
+
+    fn call(self, args: &[f64]) -> f64 {
+        match self {
+            Function::Exp => args[0].exp(),
+            Function::Ln => args[0].ln(),
+            Function::Sqrt => args[0].sqrt(),
+            Function::Abs => args[0].abs(),
+            Function::Pow => args[0].powf(args[1]),
+            Function::Min => args.iter().copied().fold(f64::INFINITY, f64::min),
+            Function::Max => args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Variable(Variable),
+    Negate(Box<Node>),
+    Binary(char, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+impl Node {
+    fn evaluate(&self, variables: &Variables) -> f64 {
+        match self {
+            Node::Number(value) => *value,
+            Node::Variable(Variable::Base) => variables.base,
+            Node::Variable(Variable::Year) => variables.year,
+            Node::Variable(Variable::Age) => variables.age,
+            Node::Variable(Variable::T) => variables.t,
+            Node::Negate(operand) => -operand.evaluate(variables),
+            Node::Binary(operator, left, right) => {
+                let (left, right) = (left.evaluate(variables), right.evaluate(variables));
+                match operator {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    '/' => left / right,
+                    _ => left.powf(right),
+                }
+            }
+            Node::Call(function, args) => {
+                let args: Vec<f64> = args.iter().map(|arg| arg.evaluate(variables)).collect();
+                function.call(&args)
+            }
+        }
+    }
+}
+
+/// Parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    root: Node,
+}
+
+impl Expression {
+    /// Parse an expression, describing the first problem found
+    pub fn parse(source: &str) -> Result<Self, String> {
+        if source.chars().count() > MAX_LENGTH {
+            return Err(format!("Expression is longer than {} characters", MAX_LENGTH));
+        }
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens: &tokens, position: 0, depth: 0 };
+        let root = parser.expression()?;
+        match parser.peek() {
+            None => Ok(Self { root }),
+            Some(token) => Err(format!("Unexpected {}", token)),
+        }
+    }
+
+    /// Value for the given variables (may be infinite or NaN)
+    pub fn evaluate(&self, variables: &Variables) -> f64 {
+        self.root.evaluate(variables)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Operator(char),
+    Open,
+    Close,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "number {}", value),
+            Token::Name(name) => write!(f, "'{}'", name),
+            Token::Operator(operator) => write!(f, "'{}'", operator),
+            Token::Open => write!(f, "'('"),
+            Token::Close => write!(f, "')'"),
+            Token::Comma => write!(f, "','"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '0'..='9' | '.' => {
+                let mut end = start;
+                while let Some(&(index, c)) = chars.peek() {
+                    let exponent_sign = matches!(c, '+' | '-')
+                        && matches!(source[..index].chars().last(), Some('e' | 'E'));
+                    if !(c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign) {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                let text = &source[start..end];
+                let value = text.parse::<f64>().map_err(|_| format!("Invalid number '{}'", text))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = start;
+                while let Some(&(index, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Name(source[start..end].to_string()));
+            }
+            _ => {
+                chars.next();
+                tokens.push(match c {
+                    '+' | '-' | '*' | '/' | '^' => Token::Operator(c),
+                    '·' | '×' => Token::Operator('*'),
+                    '−' => Token::Operator('-'),
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    ',' => Token::Comma,
+                    _ => return Err(format!("Unexpected character '{}'", c)),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser; `^` binds tighter than unary minus and is
+/// right-associative
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if *token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {}, found {}", expected, token)),
+            None => Err(format!("Expected {} at the end", expected)),
+        }
+    }
+
+    /// Enter one level of nesting
+    fn descend(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!("Expression is nested deeper than {} levels", MAX_DEPTH));
+        }
+        Ok(())
+    }
+
+    fn binary_level(
+        &mut self,
+        operators: [char; 2],
+        operand: fn(&mut Self) -> Result<Node, String>,
+    ) -> Result<Node, String> {
+        let mut node = operand(self)?;
+        while let Some(&Token::Operator(operator)) = self.peek() {
+            if !operators.contains(&operator) {
+                break;
+            }
+            self.position += 1;
+            node = Node::Binary(operator, Box::new(node), Box::new(operand(self)?));
+        }
+        Ok(node)
+    }
+
+    fn expression(&mut self) -> Result<Node, String> {
+        self.descend()?;
+        let node = self.binary_level(['+', '-'], Self::term);
+        self.depth -= 1;
+        node
+    }
+
+    fn term(&mut self) -> Result<Node, String> {
+        self.binary_level(['*', '/'], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        match self.peek() {
+            Some(Token::Operator('-')) => {
+                self.position += 1;
+                self.descend()?;
+                let operand = self.unary();
+                self.depth -= 1;
+                Ok(Node::Negate(Box::new(operand?)))
+            }
+            Some(Token::Operator('+')) => {
+                self.position += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Node, String> {
+        let base = self.primary()?;
+        if self.peek() != Some(&Token::Operator('^')) {
+            return Ok(base);
+        }
+        self.position += 1;
+        self.descend()?;
+        let exponent = self.unary();
+        self.depth -= 1;
+        Ok(Node::Binary('^', Box::new(base), Box::new(exponent?)))
+    }
+
+    fn primary(&mut self) -> Result<Node, String> {
+        match self.next().cloned() {
+            Some(Token::Number(value)) => Ok(Node::Number(value)),
+            Some(Token::Open) => {
+                let node = self.expression()?;
+                self.expect(Token::Close)?;
+                Ok(node)
+            }
+            Some(Token::Name(name)) => {
+                if self.peek() == Some(&Token::Open) {
+                    self.position += 1;
+                    return self.call(&name);
+                }
+                Ok(Node::Variable(match name.as_str() {
+                    "base" => Variable::Base,
+                    "year" => Variable::Year,
+                    "age" => Variable::Age,
+                    "t" => Variable::T,
+                    _ => return Err(format!("Unknown variable '{}'", name)),
+                }))
+            }
+            Some(token) => Err(format!("Unexpected {}", token)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+
+    /// Arguments of a call whose opening parenthesis was consumed
+    fn call(&mut self, name: &str) -> Result<Node, String> {
+        let function = Function::from_name(name).ok_or_else(|| format!("Unknown function '{}'", name))?;
+        let mut args = Vec::new();
+        if self.peek() != Some(&Token::Close) {
+            loop {
+                args.push(self.expression()?);
+                if self.peek() != Some(&Token::Comma) {
+                    break;
+                }
+                self.position += 1;
+            }
+        }
+        self.expect(Token::Close)?;
+        let (min_args, max_args) = function.arity();
+        if args.len() < min_args || args.len() > max_args {
+            return Err(format!("Wrong number of arguments to '{}'", name));
+        }
+        Ok(Node::Call(function, args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str, variables: Variables) -> f64 {
+        Expression::parse(source).unwrap().evaluate(&variables)
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        let none = Variables::default();
+        assert_eq!(evaluate("1 + 2 * 3", none), 7.0);
+        assert_eq!(evaluate("(1 + 2) * 3", none), 9.0);
+        assert_eq!(evaluate("8 / 4 / 2", none), 1.0);
+        assert_eq!(evaluate("2 ^ 3 ^ 2", none), 512.0);
+        assert_eq!(evaluate("-2 ^ 2", none), -4.0);
+        assert_eq!(evaluate("2 * -3", none), -6.0);
+        assert_eq!(evaluate("1.5e2 + 2E-1", none), 150.2);
+    }
+
+    #[test]
+    fn test_variables_and_functions() {
+        let variables = Variables { base: 0.01, year: 2027.0, age: 70.0, t: 2.0 };
+
+        let value = evaluate("base × (1 + 0.5·exp(−t/2))", variables);
+        assert!((value - 0.01 * (1.0 + 0.5 * (-1.0_f64).exp())).abs() < 1e-15);
+        assert_eq!(evaluate("min(age, 65, year)", variables), 65.0);
+        assert_eq!(evaluate("max(abs(-3), sqrt(16))", variables), 4.0);
+        assert_eq!(evaluate("pow(2, t) + ln(1)", variables), 4.0);
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_rejected() {
+        let error = |source: &str| Expression::parse(source).unwrap_err();
+
+        assert!(error("base * ").contains("end"));
+        assert!(error("income * 2").contains("Unknown variable 'income'"));
+        assert!(error("system(1)").contains("Unknown function"));
+        assert!(error("exp(1, 2)").contains("number of arguments"));
+        assert!(error("(1 + 2").contains("Expected ')'"));
+        assert!(error("2 $ 3").contains("Unexpected character"));
+        assert!(error("1 2").contains("Unexpected number"));
+        assert!(error(&"(".repeat(40)).contains("nested deeper"));
+        assert!(error(&"1+".repeat(600)).contains("longer than"));
+    }
+}
@@ -3,6 +3,7 @@
 //! These types mirror the TypeScript definitions in @popula/shared-types
 
 mod demographic;
+mod expression;
 mod scenario;
 mod shock;
 mod messages;
@@ -1,6 +1,9 @@
 //! Shock types for demographic rate modifiers
 
-use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use super::expression::{Expression, Variables};
 use super::{AgeGroup, Gender};
 
 /// Type of demographic shock
@@ -12,29 +15,77 @@ pub enum ShockType {
     Migration,
 }
 
-/// Target specification for shocks
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+/// Target specification for shocks ("all" or a list)
+#[derive(Debug, Clone)]
 pub enum Target<T> {
     All,
     Specific(Vec<T>),
 }
 
+/// The "all" keyword of targets
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum AllKeyword {
+    All,
+}
+
+/// Wire form of targets
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum TargetRepr<T> {
+    All(AllKeyword),
+    Specific(T),
+}
+
+impl<T: Serialize> Serialize for Target<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Target::All => TargetRepr::<&[T]>::All(AllKeyword::All).serialize(serializer),
+            Target::Specific(values) => TargetRepr::Specific(values).serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Target<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match TargetRepr::<Vec<T>>::deserialize(deserializer)? {
+            TargetRepr::All(_) => Target::All,
+            TargetRepr::Specific(values) => Target::Specific(values),
+        })
+    }
+}
+
 impl<T> Target<T> {
     pub fn is_all(&self) -> bool {
         matches!(self, Target::All)
     }
 }
 
-/// Age target for shocks
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+/// Age target for shocks ("all" or a range)
+#[derive(Debug, Clone)]
 pub enum AgeTarget {
-    #[serde(rename = "all")]
     All,
     Range(AgeGroup),
 }
 
+impl Serialize for AgeTarget {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            AgeTarget::All => TargetRepr::<AgeGroup>::All(AllKeyword::All).serialize(serializer),
+            AgeTarget::Range(group) => TargetRepr::Specific(group).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AgeTarget {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match TargetRepr::<AgeGroup>::deserialize(deserializer)? {
+            TargetRepr::All(_) => AgeTarget::All,
+            TargetRepr::Specific(group) => AgeTarget::Range(group),
+        })
+    }
+}
+
 impl AgeTarget {
     pub fn contains(&self, age: u32) -> bool {
         match self {
@@ -52,8 +103,14 @@ pub enum ShockModifier {
     Multiplier { value: f64 },
     /// Add an absolute value
     Absolute { value: f64 },
-    /// Custom function (evaluated at runtime)
-    Function { expression: String },
+    /// Expression of `base`, `year`, `age` and `t` (years since the shock
+    /// started), e.g. `base * (1 + 0.5 * exp(-t / 2))`
+    Function {
+        expression: String,
+        /// Parsed on first use
+        #[serde(skip)]
+        parsed: OnceLock<Result<Expression, String>>,
+    },
 }
 
 impl ShockModifier {
@@ -65,25 +122,75 @@ impl ShockModifier {
         Self::Absolute { value }
     }
 
-    /// Apply the modifier to a base value
-    pub fn apply(&self, base_value: f64) -> f64 {
+    #[cfg(test)]
+    pub fn function(expression: &str) -> Self {
+        Self::Function { expression: expression.to_string(), parsed: OnceLock::new() }
+    }
+
+    /// Check that a function modifier's expression parses
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ShockModifier::Function { expression, parsed } => parsed
+                .get_or_init(|| Expression::parse(expression))
+                .as_ref()
+                .map(|_| ())
+                .map_err(|error| format!("Invalid expression '{}': {}", expression, error)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply the modifier to a base value of a cohort of `age` in `year`,
+    /// `t` years after the shock started
+    ///
+    /// Function modifiers whose expression does not parse or evaluates to
+    /// a non-finite number leave the base value unchanged.
+    pub fn apply(&self, base_value: f64, year: u32, age: u32, t: u32) -> f64 {
         match self {
             ShockModifier::Multiplier { value } => base_value * value,
             ShockModifier::Absolute { value } => base_value + value,
-            ShockModifier::Function { .. } => {
-                // Function modifiers would require an expression evaluator
-                // For now, just return the base value
-                base_value
+            ShockModifier::Function { expression, parsed } => {
+                let Ok(expression) = parsed.get_or_init(|| Expression::parse(expression)) else {
+                    return base_value;
+                };
+                let value = expression.evaluate(&Variables {
+                    base: base_value,
+                    year: year as f64,
+                    age: age as f64,
+                    t: t as f64,
+                });
+                if value.is_finite() { value } else { base_value }
             }
         }
     }
 }
 
+/// Modifier given as a bare number (a multiplier) or a tagged modifier
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ModifierInput {
+    Multiplier(f64),
+    Modifier(ShockModifier),
+}
+
+fn deserialize_modifier<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ShockModifier, D::Error> {
+    Ok(match ModifierInput::deserialize(deserializer)? {
+        ModifierInput::Multiplier(value) => ShockModifier::Multiplier { value },
+        ModifierInput::Modifier(modifier) => modifier,
+    })
+}
+
 /// Shock: Modifier applied to demographic rates
+///
+/// Mortality and fertility shocks modify the rates of the targeted cohorts.
+/// Migration shocks modify their net migration counts, except absolute ones,
+/// which add a total number of net migrants per year spread over the
+/// targeted cohorts by population. Emigration rates are not shocked.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Shock {
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub description: String,
     #[serde(rename = "type")]
     pub shock_type: ShockType,
@@ -92,7 +199,12 @@ pub struct Shock {
     pub target_regions: Target<String>,
     pub target_genders: Target<Gender>,
     pub target_ages: AgeTarget,
+    #[serde(deserialize_with = "deserialize_modifier")]
     pub modifier: ShockModifier,
+    /// Share of the full effect by year from the start year (0-1); the last
+    /// value holds for later years. None: full effect throughout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intensity_curve: Option<Vec<f64>>,
 }
 
 impl Shock {
@@ -130,6 +242,24 @@ impl Shock {
 
         true
     }
+
+    /// Share of the full effect in a year of the shock
+    pub fn intensity(&self, year: u32) -> f64 {
+        match &self.intensity_curve {
+            Some(curve) if !curve.is_empty() => {
+                let offset = year.saturating_sub(self.start_year) as usize;
+                curve[offset.min(curve.len() - 1)]
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Apply the modifier to a cohort's base value, scaled by the year's intensity
+    pub fn modify(&self, base_value: f64, year: u32, age: u32) -> f64 {
+        let t = year.saturating_sub(self.start_year);
+        let modified = self.modifier.apply(base_value, year, age, t);
+        base_value + (modified - base_value) * self.intensity(year)
+    }
 }
 
 /// Create a pandemic shock template
@@ -158,6 +288,7 @@ pub fn pandemic_shock(
             .map(|min| AgeTarget::Range(AgeGroup::range(min, 120)))
             .unwrap_or(AgeTarget::All),
         modifier: ShockModifier::multiplier(mortality_increase),
+        intensity_curve: None,
     }
 }
 
@@ -190,6 +321,7 @@ pub fn war_shock(
         target_genders: Target::Specific(vec![Gender::Male]),
         target_ages: AgeTarget::Range(AgeGroup::range(min, max)),
         modifier: ShockModifier::multiplier(mortality_increase),
+        intensity_curve: None,
     }
 }
 
@@ -200,13 +332,29 @@ mod tests {
     #[test]
     fn test_shock_modifier_multiplier() {
         let modifier = ShockModifier::multiplier(1.5);
-        assert_eq!(modifier.apply(100.0), 150.0);
+        assert_eq!(modifier.apply(100.0, 2025, 0, 0), 150.0);
     }
 
     #[test]
     fn test_shock_modifier_absolute() {
         let modifier = ShockModifier::absolute(50.0);
-        assert_eq!(modifier.apply(100.0), 150.0);
+        assert_eq!(modifier.apply(100.0, 2025, 0, 0), 150.0);
+    }
+
+    #[test]
+    fn test_shock_modifier_function() {
+        let mut shock = pandemic_shock("test", "Test", 2025, 2030, 1.0, None);
+        shock.modifier = ShockModifier::function("base * (1 + 0.5 * exp(-t / 2)) + age / 1e4");
+
+        assert!((shock.modify(0.01, 2025, 50) - (0.015 + 0.005)).abs() < 1e-12);
+        let decayed = 0.01 * (1.0 + 0.5 * (-1.0_f64).exp()) + 0.005;
+        assert!((shock.modify(0.01, 2027, 50) - decayed).abs() < 1e-12);
+
+        // Invalid expressions are reported and otherwise leave the value alone
+        let invalid = ShockModifier::function("base * income");
+        assert!(invalid.validate().unwrap_err().contains("Unknown variable"));
+        assert_eq!(invalid.apply(0.01, 2025, 50, 0), 0.01);
+        assert_eq!(ShockModifier::function("base / 0").apply(0.01, 2025, 50, 0), 0.01);
     }
 
     #[test]
@@ -222,6 +370,137 @@ mod tests {
         assert!(!shock.applies(2025, 50, Gender::Male, "CZ")); // Wrong age
     }
 
+    #[test]
+    fn test_intensity_curve_scales_effect() {
+        let mut shock = pandemic_shock("test", "Test", 2025, 2029, 1.5, None);
+        shock.intensity_curve = Some(vec![0.2, 1.0, 0.5]);
+
+        assert!((shock.modify(0.01, 2025, 70) - 0.011).abs() < 1e-12);
+        assert!((shock.modify(0.01, 2026, 70) - 0.015).abs() < 1e-12);
+        assert!((shock.modify(0.01, 2029, 70) - 0.0125).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_shock_from_typescript_json() {
+        let shock: Shock = serde_json::from_value(serde_json::json!({
+            "id": "exodus",
+            "name": "Mass Emigration",
+            "type": "migration",
+            "startYear": 2025,
+            "endYear": 2027,
+            "targetRegions": "all",
+            "targetGenders": ["male"],
+            "targetAges": { "min": 20, "max": 40 },
+            "modifier": { "type": "absolute", "value": -50000.0 },
+        })).unwrap();
+
+        assert!(shock.target_regions.is_all());
+        assert!(shock.applies(2026, 30, Gender::Male, "CZ"));
+        assert!(!shock.applies(2026, 30, Gender::Female, "CZ"));
+        assert_eq!(shock.modifier.apply(0.0, 2026, 30, 1), -50000.0);
+
+        // A bare number is a multiplier; targets round-trip through "all"
+        let shock: Shock = serde_json::from_value(serde_json::json!({
+            "id": "boom", "name": "Baby Boom", "type": "fertility",
+            "startYear": 2025, "endYear": 2030,
+            "targetRegions": "all", "targetGenders": "all", "targetAges": "all",
+            "modifier": 1.4,
+        })).unwrap();
+        assert!((shock.modifier.apply(1.0, 2025, 30, 0) - 1.4).abs() < 1e-12);
+        let json = serde_json::to_value(&shock).unwrap();
+        assert_eq!(json["targetAges"], "all");
+        assert_eq!(json["startYear"], 2025);
+    }
+
+    /// Convert the `defaults` object literals of the TypeScript
+    /// `SHOCK_TEMPLATES` into JSON: drop comments, quote keys, swap quotes
+    /// and trailing commas.
+    fn template_defaults(source: &str) -> Vec<serde_json::Value> {
+        let start = source.find("export const SHOCK_TEMPLATES").unwrap();
+        let templates = &source[start..];
+        let templates = &templates[..templates.find("\n];").unwrap()];
+        let mut defaults = Vec::new();
+        let mut rest = templates;
+        while let Some(offset) = rest.find("defaults: {") {
+            let body = &rest[offset + "defaults: ".len()..];
+            let mut depth = 0;
+            let end = body
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                })
+                .unwrap()
+                .0;
+            let literal: String = body[..=end]
+                .lines()
+                .map(|line| line.split("//").next().unwrap())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let mut json = String::new();
+            let mut chars = literal.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '\'' {
+                    json.push('"');
+                    json.extend(chars.by_ref().take_while(|&c| c != '\''));
+                    json.push('"');
+                } else if c.is_ascii_alphabetic() {
+                    let mut key = c.to_string();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric()) {
+                        key.push(c);
+                        chars.next();
+                    }
+                    json.push_str(&format!("\"{}\"", key));
+                } else if c == ',' {
+                    let next = literal[literal.len() - chars.clone().count()..].trim_start();
+                    if !next.starts_with('}') && !next.starts_with(']') {
+                        json.push(c);
+                    }
+                } else {
+                    json.push(c);
+                }
+            }
+            defaults.push(serde_json::from_str(&json).unwrap());
+            rest = &body[end..];
+        }
+        defaults
+    }
+
+    #[test]
+    fn test_typescript_templates_deserialize() {
+        let source = include_str!("../../../packages/shared-types/src/shock.ts");
+        let defaults = template_defaults(source);
+        assert_eq!(defaults.len(), 7);
+
+        for mut value in defaults {
+            value["id"] = "template".into();
+            value["startYear"] = 2025.into();
+            value["endYear"] = 2030.into();
+            let shock: Shock = serde_json::from_value(value).unwrap();
+
+            // Base 0 exposes absolute counts, base 1 exposes multipliers
+            let (base, expected) = match shock.name.as_str() {
+                "Moderate Pandemic" => (1.0, 1.3),
+                "Severe Pandemic" => (1.0, 1.5),
+                "War Casualties" => (1.0, 2.0),
+                "Baby Boom" => (1.0, 1.4),
+                "Fertility Decline" => (1.0, 0.7),
+                "Mass Emigration" => (0.0, -50000.0),
+                "Refugee Influx" => (0.0, 100000.0),
+                name => panic!("unexpected template {}", name),
+            };
+            if shock.shock_type == ShockType::Migration {
+                assert!(matches!(shock.modifier, ShockModifier::Absolute { .. }), "{}", shock.name);
+            }
+            assert!((shock.modifier.apply(base, 2025, 30, 0) - expected).abs() < 1e-9, "{}", shock.name);
+        }
+    }
+
     #[test]
     fn test_war_shock_targets_males_only() {
         let shock = war_shock("war", "War", 2025, 2026, 3.0, None, None);